@@ -0,0 +1,37 @@
+-- Enemy behaviors
+--
+-- An archetype with `behavior = "name"` calls the global function `name` every tick once its
+-- entry path is done. It gets a read-only copy of the enemy and returns what it wants:
+--   enemy.id, enemy.x, enemy.y, enemy.hp, enemy.max_hp
+--   enemy.player_x, enemy.player_y
+--   enemy.time          sim seconds since the run started
+--   enemy.fire_ready    fire timer has run out
+-- Return a table (all fields optional) or nil for the built-in AI this tick:
+--   vx, vy              velocity in px/s (capped at enemy_behavior.lua_max_speed)
+--   fire                shoot when the timer allows (stunned/high-up enemies still can't)
+--   weapon              one of the archetype's weapons (default: random)
+--   aim_x, aim_y        point to shoot at (default: the archetype's fire_direction)
+-- All behaviors share enemy_behavior.lua_instruction_budget instructions per tick; enemies past
+-- it use the built-in AI until the next tick. A behavior that errors is dropped for the run.
+-- Keep them deterministic (no math.random) so seeded runs and replays stay identical.
+
+-- Weave side to side at a standoff distance above the player, shotgun up close
+function strafe(enemy)
+	local phase = enemy.time * 1.5 + enemy.id
+	local vx = math.sin(phase) * 140
+
+	local standoff = 260
+	local gap = enemy.player_y - enemy.y
+	local vy = (gap - standoff) * 0.8
+	if enemy.hp < enemy.max_hp * 0.3 then
+		vy = -80 -- Badly hurt: back off
+	end
+
+	local close = math.abs(enemy.player_x - enemy.x) < 120
+	return {
+		vx = vx,
+		vy = vy,
+		fire = enemy.fire_ready,
+		weapon = close and "Shotgun" or "BurstRifle",
+	}
+end
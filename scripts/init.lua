@@ -1,8 +1,61 @@
 -- Helper functions available to all Lua scripts
+--
+-- The `game` table talks to the running game. Queries see the state from the start of the
+-- tick; actions are queued and applied right after the callback returns.
+--   game.player_pos()        -> x, y
+--   game.player_hp()         -> hp, max_hp
+--   game.player_energy()     -> energy
+--   game.enemy_count()       -> enemies alive
+--   game.arena()             -> width, height
+--   game.spawn_enemy(type, x[, path])   archetype name, column, EntryPath name (default: the archetype's)
+--   game.set_formation(name)            "VShape" | "Line" | "Circle" (ignored without enough ghosts queued)
+--   game.grant_energy(n)                negative takes energy away
+--   game.shake(duration, intensity)
+--   game.on(event, handler)             global event handler (see below)
+--
+-- Event handlers: a wave table can have on_<event> fields (live while that wave runs), and
+-- game.on(event, handler) registers one for the whole run. Events and their arguments:
+--   enemy_killed    (type, x, y, killer)   killer = "Player" | "Ghost" | "Enemy" | nil
+--   player_hit      (damage, x, y)
+--   parry           (deflected, x, y)
+--   ghost_summoned  (type, x, y)
+--   tick            (seconds since the last call, every spawning.tick_hook_interval)
+-- A handler that raises an error is logged and removed; the game keeps running.
 function print_wave_start(wave_num, name)
 	print(" Wave " .. wave_num .. ": " .. name)
 end
 
 function print_wave_complete(wave_num)
 	print("✓ Wave " .. wave_num .. " Complete!")
-end
\ No newline at end of file
+end
+
+-- Wave timelines
+--
+-- A wave may return a `run` function next to (or instead of) `spawns`. It runs as a coroutine
+-- resumed once per tick, and the wave only completes after it returns and the field is clear.
+-- Built-in waits (only inside run):
+--   wait(seconds)            sim time, always at least one tick
+--   wait_until_clear()       until no enemies are left
+--   parallel{f, g, ...}      run the functions side by side until all have returned
+
+-- Spawn one enemy (x defaults to a random column, path to the archetype's entry path)
+function spawn(enemy_type, x, path)
+	game.spawn_enemy(enemy_type, x, path)
+end
+
+-- Spawn `count` enemies, `interval` seconds apart
+function spawn_group(enemy_type, count, interval)
+	for i = 1, count do
+		spawn(enemy_type)
+		if i < count then
+			wait(interval)
+		end
+	end
+end
+
+-- Wait until `condition()` returns true (checked once per tick)
+function wait_until(condition)
+	repeat
+		wait(0)
+	until condition()
+end
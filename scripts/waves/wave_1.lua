@@ -1,4 +1,8 @@
 -- Wave 1: First Contact
+
+-- Mini-objective: take down all three Splitters for an energy bonus
+local splitters_down = 0
+
 return {
 	wave_number = 1,
 	name = "First Contact",
@@ -24,6 +28,17 @@ return {
 		print_wave_start(1, "First Contact")
 	end,
 
+	on_enemy_killed = function(kind)
+		if kind ~= "Splitter" then
+			return
+		end
+		splitters_down = splitters_down + 1
+		if splitters_down == 3 then
+			print("✔ Objective complete: all Splitters down (+40 energy)")
+			game.grant_energy(40)
+		end
+	end,
+
 	on_complete = function()
 		print_wave_complete(1)
 	end
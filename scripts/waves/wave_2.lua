@@ -31,5 +31,7 @@ return {
 
 	on_complete = function()
 		print_wave_complete(2)
+		-- Top the player up before the heavy wave
+		game.grant_energy(30)
 	end
 }
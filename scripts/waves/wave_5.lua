@@ -45,6 +45,12 @@ return {
 
 	on_start = function()
 		print_wave_start(5, "Final Stand")
+		game.shake(0.6, 10.0)
+
+		-- Healers drift in along both edges to keep the big ones alive
+		local width = game.arena()
+		game.spawn_enemy("Healer", width * 0.1, "Gentle")
+		game.spawn_enemy("Healer", width * 0.9, "Gentle")
 	end,
 
 	on_complete = function()
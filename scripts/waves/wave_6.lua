@@ -0,0 +1,52 @@
+-- Wave 6: Crossfire (timeline-driven)
+return {
+	wave_number = 6,
+	name = "Crossfire",
+	prep_time = 3.0,
+
+	run = function()
+		local width = game.arena()
+
+		-- Three snipers take up positions, then a short breather
+		spawn("Sniper", width * 0.25)
+		spawn("Sniper", width * 0.5)
+		spawn("Sniper", width * 0.75)
+		wait(2.0)
+
+		-- Fighters stream in while gunships and splitters join them
+		parallel {
+			function()
+				spawn_group("BasicFighter", 8, 0.75)
+			end,
+			function()
+				spawn_group("Gunship", 2, 3.0)
+			end,
+			function()
+				wait(1.5)
+				spawn_group("Splitter", 3, 1.0)
+			end,
+		}
+		wait(0) -- Let the last spawns land before counting
+
+		-- Tank rush once half of them are down
+		local total = game.enemy_count()
+		wait_until(function()
+			return game.enemy_count() <= total / 2
+		end)
+		game.shake(0.5, 8.0)
+		for i = 1, 3 do
+			spawn("Tank", width * i / 4, "Heavy")
+		end
+
+		wait_until_clear()
+		spawn("Elite", width * 0.5, "Showcase")
+	end,
+
+	on_start = function()
+		print_wave_start(6, "Crossfire")
+	end,
+
+	on_complete = function()
+		print_wave_complete(6)
+	end
+}
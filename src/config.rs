@@ -7,8 +7,9 @@ pub struct GameConfig {
     pub window: WindowConfig,
     pub player: PlayerConfig,
     pub energy: EnergyConfig,
-    pub entities: EntitiesConfig,
-    pub weapons: WeaponsConfig,
+    pub graze: GrazeConfig,               // Near-miss energy rewards
+    pub archetypes: Vec<ArchetypeConfig>, // Enemy/ghost kinds ([[archetypes]], referenced by name)
+    pub weapons: Vec<WeaponConfig>,       // Weapon definitions ([[weapons]], referenced by name)
     pub spawning: SpawningConfig,
     pub formations: FormationsConfig,
     pub debug: DebugConfig,
@@ -20,8 +21,14 @@ pub struct GameConfig {
     pub screen_shake: ScreenShakeConfig,
     pub particles: ParticleConfig,
     pub background: BackgroundConfig,
-    pub animations: AnimationConfig, // Animation system config
-    pub dash: DashConfig,            // Dash mechanic config
+    pub animations: AnimationConfig,  // Animation system config
+    pub dash: DashConfig,             // Dash mechanic config
+    pub upgrades: UpgradesConfig,     // Weapon level rewards and penalties
+    pub modifiers: ModifiersConfig,   // Chain/split tuning shared by all affixes
+    pub damage: DamageConfig,         // Damage pipeline: owner multipliers, crits, armor
+    pub status: StatusConfig,         // Status effect ticks and tints
+    pub simulation: SimulationConfig, // Fixed-timestep loop config
+    pub bindings: BindingsConfig,     // Key -> action table
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +39,68 @@ pub struct WindowConfig {
     pub target_fps: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    pub tick_rate: f32,      // Fixed simulation steps per second (independent of FPS)
+    pub max_frame_time: f32, // Clamp long frames to avoid a catch-up spiral (seconds)
+    pub seed: u64, // RNG seed for reproducible runs (0 = random each run, reseeds on reload)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradesConfig {
+    pub levels_per_wave: usize,    // Levels awarded when a wave is cleared
+    pub pickup_drop_chance: f32,   // Chance a killed enemy drops an upgrade pickup (0.0-1.0)
+    pub pickup_fall_speed: f32,    // Pickups drift down at this speed (pixels/sec)
+    pub pickup_radius: f32,        // Collected when the player is this close
+    pub pickup_lifetime: f32,      // Seconds before an uncollected pickup vanishes
+    pub levels_lost_on_hit: usize, // Levels the highest slot loses when the player is hit
+    pub downgrade_grace: f32,      // Seconds after a downgrade before hits cost levels again
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiersConfig {
+    pub chain_range: f32,        // Max distance of one chain jump (pixels)
+    pub chain_damage: f32,       // Damage multiplier per jump (compounds)
+    pub split_damage: f32,       // Sub-projectile damage as a fraction of the parent's
+    pub split_spread_angle: f32, // Degrees between sub-projectiles
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageConfig {
+    pub enemy_multiplier: f32, // Damage of enemy-fired weapons (0.75 = 75%)
+    pub ghost_multiplier: f32, // Damage of ghost-fired weapons
+    pub crit_multiplier: f32,  // Damage of a critical hit (2.0 = double)
+    pub armor_scale: f32, // Armor equal to this halves damage (damage * scale / (scale + armor))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusConfig {
+    pub tick_interval: f32,  // Seconds between burn damage ticks
+    pub burn_tint: [f32; 3], // RGB the affected entity is tinted towards
+    pub slow_tint: [f32; 3],
+    pub stun_tint: [f32; 3],
+    pub drain_tint: [f32; 3],
+    pub tint_strength: f32, // 0.0-1.0 blend towards the tint
+}
+
+/// Key names per action (macroquad KeyCode names, e.g. "W", "Up", "LeftShift", "F1", "Key1")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingsConfig {
+    pub move_up: Vec<String>,
+    pub move_down: Vec<String>,
+    pub move_left: Vec<String>,
+    pub move_right: Vec<String>,
+    pub dash: Vec<String>,            // Held together with a direction
+    pub fire_slots: Vec<Vec<String>>, // Index = weapon slot (Bullet, Laser, Missile, Plasma, Bombs)
+    pub parry: Vec<String>,
+    pub cancel_summon: Vec<String>,
+    pub summon_formation: Vec<String>, // Summon full formation from the ghost queue
+    pub formation_line: Vec<String>,
+    pub formation_circle: Vec<String>,
+    pub formation_v: Vec<String>,
+    pub summon: std::collections::BTreeMap<String, Vec<String>>, // Entity type name -> keys
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerConfig {
     pub starting_health: f32,
@@ -48,8 +117,15 @@ pub struct PlayerConfig {
 
     // Physics-based movement
     pub acceleration: f32,   // How fast you speed up (px/s²)
-    pub friction: f32,       // Momentum decay (0.88 = 12% slowdown/frame)
+    pub friction: f32,       // Momentum decay per 60 Hz frame (0.88 = 12% slowdown/frame)
     pub responsiveness: f32, // How quickly you change direction (higher = tighter turns)
+
+    #[serde(default)]
+    pub armor: f32, // Damage reduction rating (see [damage])
+
+    // Affixes added to the player's shots, per weapon name
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub weapon_modifiers: std::collections::BTreeMap<String, Modifiers>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,63 +135,373 @@ pub struct EnergyConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EntitiesConfig {
-    pub basic_fighter: EntityStats,
-    pub sniper: EntityStats,
-    pub tank: EntityStats,
-    pub elite: EntityStats,
-    pub healer: HealerStats,     // Special stats for healer enemy
-    pub splitter: SplitterStats, // Special stats for splitter enemy
+pub struct GrazeConfig {
+    pub radius: f32, // Enemy shots passing this close (but not hitting) count as a graze
+    pub energy: f32, // Energy granted per grazed projectile
 }
 
+/// One enemy/ghost kind - adding a table to `[[archetypes]]` adds a new enemy
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EntityStats {
+pub struct ArchetypeConfig {
+    pub name: String, // Referenced by wave scripts and [bindings.summon]
     pub health: f32,
     pub damage: f32,
     pub energy_cost: f32,
     pub fire_interval: f32,
     pub weapons: Vec<String>, // Weapon types this entity can use
+    pub color: [f32; 3],      // RGB (0-1)
+    pub shape: Shape,
+    pub entry_path: EntryPath,
+    pub fire_direction: FireDirection,
+    pub random_spawn_weight: u32, // Weight in classic random spawning (0 = never)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healer: Option<HealerTrait>, // Heals nearby allies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub splitter: Option<SplitterTrait>, // Splits into smaller copies on death
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shield: Option<ShieldTrait>, // Regenerating shield over its front, also as a ghost
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behavior: Option<String>, // Lua function (scripts/behaviors.lua) steering it after its entry path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai: Option<AiNode>, // Behavior tree run after its entry path (a Lua behavior takes precedence)
+    #[serde(default, skip_serializing_if = "Modifiers::is_none")]
+    pub ghost_modifiers: Modifiers, // Affixes added to its shots while it fights as a ghost
+    #[serde(default)]
+    pub armor: f32, // Damage reduction rating (see [damage]), also as a ghost
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub resistances: std::collections::BTreeMap<String, f32>, // Weapon name -> damage resisted (0.5 = half)
+}
+
+/// Body shape drawn for an archetype
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    Circle,
+    Square, // Rotated 45°
+    Star,
+}
+
+/// Bezier entry style used when an archetype spawns
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EntryPath {
+    Swoop,     // Curves/loops in from the top
+    SideSweep, // Sweeps in from the left or right edge
+    Heavy,     // Diagonal slam or overhead arc
+    Showcase,  // Figure-8, spiral or dive around the center
+    Gentle,    // Soft randomized curve
+}
+
+/// How an archetype aims its shots
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FireDirection {
+    Down,       // Straight down
+    AtPlayer,   // At the player's current position
+    LeadPlayer, // Predicts where the player will be
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct HealerStats {
-    pub health: f32,
-    pub damage: f32,
-    pub energy_cost: f32,
-    pub fire_interval: f32,
-    pub weapons: Vec<String>,
-    // Healer-specific fields
+pub struct HealerTrait {
     pub heal_rate: f32,   // HP healed per second
     pub heal_radius: f32, // Range of healing field (pixels)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SplitterStats {
-    pub health: f32,
-    pub damage: f32,
-    pub energy_cost: f32,
-    pub fire_interval: f32,
-    pub weapons: Vec<String>,
-    // Splitter-specific fields
-    pub split_count: usize,          // How many splits to spawn (2-3)
-    pub split_health_ratio: f32,     // HP ratio for each split (0.3 = 30%)
+pub struct SplitterTrait {
+    pub split_into: String, // Archetype the splits become (should not split again)
+    pub split_count: usize, // How many splits to spawn (2-3)
+    pub split_health_ratio: f32, // HP ratio for each split (0.3 = 30%)
     pub split_speed_multiplier: f32, // Speed boost for splits (1.5 = 50% faster)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WeaponsConfig {
-    pub bullet: WeaponStats,
-    pub laser: WeaponStats,
-    pub missile: WeaponStats,
-    pub plasma: WeaponStats,
-    pub bombs: WeaponStats,
+/// Shield layer that soaks hits arriving from the front (see game/shields.rs)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ShieldTrait {
+    pub capacity: f32,       // Damage absorbed before it breaks
+    pub arc: f32,            // Degrees covered, centered on the facing (360 = all around)
+    pub regen_rate: f32,     // Shield points per second while recharging
+    pub recharge_delay: f32, // Seconds without a hit before it recharges (also after a break)
+    pub break_stagger: f32,  // Seconds stunned when it breaks
+}
+
+/// Behavior tree node (see game/ai.rs) - composites take `children`, decorators one `child`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "node")]
+pub enum AiNode {
+    Sequence {
+        children: Vec<AiNode>,
+    }, // Children in order until one fails or is running
+    Selector {
+        children: Vec<AiNode>,
+    }, // First child that doesn't fail
+    Parallel {
+        children: Vec<AiNode>,
+    }, // Every child; fails if any fails, succeeds once all do
+    Cooldown {
+        seconds: f32,
+        child: Box<AiNode>,
+    }, // Fails for `seconds` after its child succeeded
+    Inverter {
+        child: Box<AiNode>,
+    }, // Swaps success and failure
+    MoveTo {
+        x: f32,
+        y: f32,
+        speed: f32,
+    }, // Arena fractions (0-1), succeeds on arrival
+    Strafe {
+        speed: f32,
+    }, // Sideways, turning at the edges (always running)
+    KeepDistance {
+        distance: f32,
+        speed: f32,
+    }, // From the player, succeeds once in range
+    Retreat {
+        below_health: f32,
+        speed: f32,
+    }, // Away from the player below that health fraction
+    HideBehind {
+        archetype: String,
+        distance: f32,
+        speed: f32,
+    }, // Nearest ally of that kind, away from the player
+    FireAt,   // Shoot at the player (fails until the fire timer allows)
+    FireLead, // Same, leading the player's movement
+    WaitForAllies {
+        count: usize,
+    }, // Holds until `count` other enemies are alive
+}
+
+impl AiNode {
+    /// Name shown by the debug overlay
+    pub fn name(&self) -> &'static str {
+        match self {
+            AiNode::Sequence { .. } => "Sequence",
+            AiNode::Selector { .. } => "Selector",
+            AiNode::Parallel { .. } => "Parallel",
+            AiNode::Cooldown { .. } => "Cooldown",
+            AiNode::Inverter { .. } => "Inverter",
+            AiNode::MoveTo { .. } => "MoveTo",
+            AiNode::Strafe { .. } => "Strafe",
+            AiNode::KeepDistance { .. } => "KeepDistance",
+            AiNode::Retreat { .. } => "Retreat",
+            AiNode::HideBehind { .. } => "HideBehind",
+            AiNode::FireAt => "FireAt",
+            AiNode::FireLead => "FireLead",
+            AiNode::WaitForAllies { .. } => "WaitForAllies",
+        }
+    }
+
+    pub fn children(&self) -> &[AiNode] {
+        match self {
+            AiNode::Sequence { children }
+            | AiNode::Selector { children }
+            | AiNode::Parallel { children } => children,
+            AiNode::Cooldown { child, .. } | AiNode::Inverter { child } => {
+                std::slice::from_ref(child.as_ref())
+            }
+            _ => &[],
+        }
+    }
+
+    /// Nodes in this subtree (runtime memory is indexed by preorder position)
+    pub fn size(&self) -> usize {
+        1 + self.children().iter().map(AiNode::size).sum::<usize>()
+    }
+
+    /// This node and everything below it, in preorder
+    pub fn nodes(&self) -> Vec<&AiNode> {
+        let mut nodes = vec![self];
+        for child in self.children() {
+            nodes.extend(child.nodes());
+        }
+        nodes
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WeaponStats {
-    pub damage: f32,           // Base damage per projectile
-    pub fire_rate: f32,        // Cooldown between shots (in seconds)
+pub struct WeaponConfig {
+    pub name: String,
+    pub damage: f32, // Base damage per projectile
+    #[serde(default)]
+    pub crit_chance: f32, // Chance a hit is critical (0.0-1.0, see [damage])
+    pub fire_rate: f32, // Cooldown between shots (in seconds)
     pub projectile_speed: f32, // How fast projectiles travel (pixels/sec)
+
+    // Firing pattern
+    pub projectile_count: usize, // Projectiles per shot, fanned around the aim direction
+    pub spread_angle: f32,       // Degrees between neighbouring projectiles
+    pub burst_count: usize,      // Shots per trigger pull (1 = single shot)
+    pub burst_interval: f32,     // Seconds between shots of a burst
+
+    // Projectile behavior
+    #[serde(default, skip_serializing_if = "Modifiers::is_none")]
+    pub modifiers: Modifiers, // Pierce/ricochet/chain/split built into the weapon
+    pub explosion_radius: f32, // AOE damage on impact (0 = single target)
+    pub lifetime: f32,         // Seconds before the projectile expires
+    pub parryable: bool,       // Player can parry it back when fired by enemies
+
+    // Visuals
+    pub color: [f32; 3], // Player/ghost projectile color (enemy shots are red)
+    pub size: f32,
+    pub glow: bool,
+    pub trail_color: [f32; 4],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enemy_trail_color: Option<[f32; 4]>, // Trail override for enemy-owned shots
+    pub trail_interval: f32, // Multiplier on particles.trail_spawn_interval
+    pub trail_particles: usize, // Particles per trail spawn
+    pub hit_particles: usize, // Impact burst size (0 = small sparks)
+    pub hit_color: [f32; 3],
+    pub shake_intensity: f32, // Screen shake when it hits an enemy
+
+    // Upgrade tiers: levels 2-5 for player slots (level 1 = the pattern above)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub levels: Vec<WeaponLevel>,
+
+    // Optional player resource model (neither = free-fire, fire_rate cooldown only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heat: Option<HeatTrait>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ammo: Option<AmmoTrait>,
+
+    // Optional homing (steers towards a locked target)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homing: Option<HomingTrait>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enemy_homing: Option<HomingTrait>, // Override for enemy-owned shots (falls back to homing)
+
+    // Optional bomb behavior (fuse, detonation, ammo)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bomb: Option<BombTrait>,
+
+    // Optional beam mode (continuous ray instead of projectiles)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beam: Option<BeamTrait>,
+
+    // Status effects put on whatever it hits ([[weapons.status]])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<StatusTrait>,
+}
+
+/// Projectile affixes (counts add up across weapon, player and ghost modifiers)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Modifiers {
+    pub pierce: u32,   // Enemies it passes through before despawning
+    pub ricochet: u32, // Bounces off the screen edges
+    pub chain: u32,    // Hits jump on to this many nearby enemies
+    pub split: u32,    // Sub-projectiles spawned on hit
+}
+
+impl Modifiers {
+    pub fn is_none(&self) -> bool {
+        *self == Modifiers::default()
+    }
+
+    /// Stack two sets of affixes
+    pub fn combine(self, other: Modifiers) -> Modifiers {
+        Modifiers {
+            pierce: self.pierce + other.pierce,
+            ricochet: self.ricochet + other.ricochet,
+            chain: self.chain + other.chain,
+            split: self.split + other.split,
+        }
+    }
+}
+
+/// Fire pattern of one upgrade tier
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WeaponLevel {
+    pub projectile_count: usize,
+    pub spread_angle: f32,
+    pub burst_count: usize,
+    pub damage_multiplier: f32, // On top of the weapon's damage (beams: damage_per_second)
+}
+
+impl WeaponConfig {
+    /// Highest upgrade level (1 = not upgradable)
+    pub fn max_level(&self) -> usize {
+        1 + self.levels.len()
+    }
+
+    /// Fire pattern at a level (clamped to 1..=max_level)
+    pub fn level(&self, level: usize) -> WeaponLevel {
+        match level.min(self.max_level()) {
+            0 | 1 => WeaponLevel {
+                projectile_count: self.projectile_count,
+                spread_angle: self.spread_angle,
+                burst_count: self.burst_count,
+                damage_multiplier: 1.0,
+            },
+            level => self.levels[level - 2],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HeatTrait {
+    pub per_shot: f32,   // Heat added per trigger pull (overheats at 1.0)
+    pub cooling: f32,    // Heat lost per second
+    pub recover_at: f32, // Overheated slots unlock once heat drops to this
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AmmoTrait {
+    pub max: u32,        // Rounds carried
+    pub regen_time: f32, // Seconds to regain one round (0 = no regen)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HomingTrait {
+    pub turn_rate: f32,        // Max heading change in degrees/second
+    pub max_speed: f32,        // Cruise speed it accelerates towards (pixels/sec)
+    pub acceleration: f32,     // Speed gained per second until max_speed
+    pub lock_cone: f32, // Degrees either side of the heading a target must stay in (180 = anywhere)
+    pub reacquire: bool, // Lock a new target when the old one is lost (false = fly straight)
+    pub proximity_radius: f32, // Hits/detonates within this distance of a target (0 = contact only)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeamTrait {
+    pub warmup: f32,   // Charge-up before it deals damage (drawn as a thin telegraph)
+    pub duration: f32, // Max firing time after warm-up (player beams also stop on release)
+    pub length: f32,   // Reach in pixels
+    pub width: f32,    // Thickness (added to target radii for hits)
+    pub damage_per_second: f32, // Damage to everything the beam crosses
+}
+
+/// Lasting effect a weapon puts on what it hits
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StatusTrait {
+    pub kind: StatusKind,
+    pub duration: f32,  // Seconds
+    pub magnitude: f32, // Per stack - Burn: damage/sec, Slow: fraction of speed lost, EnergyDrain: energy/sec
+    pub stacking: Stacking,
+    pub max_stacks: u32, // Stack limit (Stacking::Stack)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusKind {
+    Burn,        // Damage over time (through the damage pipeline)
+    Slow,        // Slower movement and fire timers
+    Stun,        // No movement, no firing
+    EnergyDrain, // Drains the player's energy (no effect on enemies and ghosts)
+}
+
+/// What a re-applied effect does to one that is already running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stacking {
+    Refresh, // Restart the duration
+    Extend,  // Add the duration on top (capped at max_stacks * duration)
+    Stack,   // Add a stack (up to max_stacks) and restart the duration
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BombTrait {
+    pub fuse_time: f32,   // Detonates by itself after this many seconds (0 = no fuse)
+    pub contact: bool,    // Detonates when it touches a target
+    pub remote: bool,     // Pressing the fire key again detonates the player's bombs
+    pub edge_damage: f32, // Damage multiplier at the blast edge (1.0 = no falloff)
+    pub clears_projectiles: bool, // Player bombs also destroy enemy projectiles in the blast
+    pub shockwave_duration: f32, // Seconds the shockwave ring takes to expand
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,6 +510,7 @@ pub struct SpawningConfig {
     pub wave_count: usize, // Number of waves (if wave_mode = true)
     pub enemy_spawn_interval: f32, // Random spawn timer (if wave_mode = false)
     pub initial_delay: f32, // Delay before first spawn (random mode)
+    pub tick_hook_interval: f32, // Seconds between Lua on_tick calls
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -148,6 +535,7 @@ pub struct CollisionConfig {
     pub enemy_radius: f32,
     pub player_radius: f32,
     pub ghost_radius: f32,
+    pub spatial_cell_size: f32, // Broadphase grid cell size in pixels (0 = brute force)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -168,6 +556,8 @@ pub struct EnemyBehaviorConfig {
     pub fire_threshold_y: f32,
     pub screen_boundary_bottom: f32,
     pub basic_projectile_speed_y: f32,
+    pub lua_instruction_budget: u32, // Lua VM instructions all behaviors may use per tick
+    pub lua_max_speed: f32,          // Cap on the velocity a behavior asks for (px/s)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -188,12 +578,7 @@ pub struct ProjectileBoundsConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenShakeConfig {
-    // Weapon-specific hit shake (when player/ghost hits enemy)
-    pub bullet_hit_intensity: f32,
-    pub laser_hit_intensity: f32,
-    pub missile_hit_intensity: f32,
-    pub plasma_hit_intensity: f32,
-    pub bomb_hit_intensity: f32,
+    // Weapon hit shake (intensity comes from the weapon's shake_intensity)
     pub weapon_hit_duration: f32, // Duration for all weapon hits
 
     // Event-specific shake
@@ -226,14 +611,6 @@ pub struct ParticleConfig {
     pub spark_speed_min: f32,
     pub spark_speed_max: f32,
 
-    // Weapon-specific particle counts
-    pub bullet_particle_count: usize,
-    pub laser_particle_count: usize,
-    pub missile_particle_count: usize,
-    pub plasma_particle_count: usize,
-    pub bomb_red_particle_count: usize,
-    pub bomb_orange_particle_count: usize,
-
     // Death explosion counts
     pub death_red_count: usize,
     pub death_orange_count: usize,
@@ -244,7 +621,7 @@ pub struct ParticleConfig {
     pub parry_white_count: usize,
 
     // Physics
-    pub friction: f32,   // Velocity multiplier per frame (0.95 = 5% slowdown)
+    pub friction: f32,   // Velocity multiplier per 60 Hz frame (0.95 = 5% slowdown)
     pub size_decay: f32, // Pixels per second
 
     // Projectile trails
@@ -282,9 +659,138 @@ impl GameConfig {
     pub fn try_load_from_file() -> Result<Self, Box<dyn std::error::Error>> {
         let contents = fs::read_to_string("config.toml")?;
         let config: GameConfig = toml::from_str(&contents)?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Reject configs the game can't run with (archetype/weapon indices must be stable and unique)
+    pub fn validate(&self) -> Result<(), String> {
+        let simulation = &self.simulation;
+        if !(simulation.tick_rate.is_finite() && simulation.tick_rate > 0.0) {
+            return Err("[simulation] tick_rate must be a positive number".to_string());
+        }
+        if !(simulation.max_frame_time.is_finite() && simulation.max_frame_time > 0.0) {
+            return Err("[simulation] max_frame_time must be a positive number".to_string());
+        }
+        if self.weapons.is_empty() {
+            return Err("at least one [[weapons]] entry is required".to_string());
+        }
+        if self.weapons.len() > u16::MAX as usize {
+            return Err("too many weapons".to_string());
+        }
+        if self.graze.radius <= self.collision.player_radius {
+            println!("✗ [graze] radius is inside the player hitbox - nothing can graze");
+        }
+        for (i, weapon) in self.weapons.iter().enumerate() {
+            if self.weapons[..i].iter().any(|w| w.name == weapon.name) {
+                return Err(format!("duplicate weapon name '{}'", weapon.name));
+            }
+            let mut patterns =
+                std::iter::once(weapon.level(1)).chain(weapon.levels.iter().copied());
+            if patterns.any(|level| level.projectile_count == 0 || level.burst_count == 0) {
+                return Err(format!(
+                    "weapon {} needs projectile_count and burst_count of at least 1",
+                    weapon.name
+                ));
+            }
+            if weapon
+                .status
+                .iter()
+                .any(|status| status.duration <= 0.0 || status.max_stacks == 0)
+            {
+                return Err(format!(
+                    "weapon {} has a [[weapons.status]] without duration or max_stacks",
+                    weapon.name
+                ));
+            }
+            if weapon.max_level() > 5 {
+                return Err(format!(
+                    "weapon {} has more than 4 [[weapons.levels]] (levels 2-5)",
+                    weapon.name
+                ));
+            }
+        }
+        for weapon in &self.player.starting_weapons {
+            if crate::models::WeaponType::from_name(weapon, &self.weapons).is_none() {
+                println!("✗ Unknown starting weapon '{weapon}'");
+            }
+        }
+        for weapon in self.player.weapon_modifiers.keys() {
+            if crate::models::WeaponType::from_name(weapon, &self.weapons).is_none() {
+                println!("✗ Unknown weapon '{weapon}' in [player.weapon_modifiers]");
+            }
+        }
+
+        if self.archetypes.is_empty() {
+            return Err("at least one [[archetypes]] entry is required".to_string());
+        }
+        if self.archetypes.len() > u16::MAX as usize {
+            return Err("too many archetypes".to_string());
+        }
+
+        for (i, archetype) in self.archetypes.iter().enumerate() {
+            if self.archetypes[..i]
+                .iter()
+                .any(|a| a.name == archetype.name)
+            {
+                return Err(format!("duplicate archetype name '{}'", archetype.name));
+            }
+            if let Some(splitter) = &archetype.splitter
+                && !self
+                    .archetypes
+                    .iter()
+                    .any(|a| a.name == splitter.split_into)
+            {
+                return Err(format!(
+                    "archetype {} splits into unknown archetype '{}'",
+                    archetype.name, splitter.split_into
+                ));
+            }
+            if let Some(shield) = &archetype.shield
+                && (shield.capacity <= 0.0 || shield.arc <= 0.0)
+            {
+                return Err(format!(
+                    "archetype {} has a shield without capacity or arc",
+                    archetype.name
+                ));
+            }
+            let hide_targets = archetype
+                .ai
+                .iter()
+                .flat_map(AiNode::nodes)
+                .filter_map(|node| match node {
+                    AiNode::HideBehind { archetype, .. } => Some(archetype),
+                    _ => None,
+                });
+            for target in hide_targets {
+                if !self.archetypes.iter().any(|a| &a.name == target) {
+                    return Err(format!(
+                        "archetype {} hides behind unknown archetype '{}'",
+                        archetype.name, target
+                    ));
+                }
+            }
+            for weapon in &archetype.weapons {
+                if crate::models::WeaponType::from_name(weapon, &self.weapons).is_none() {
+                    println!(
+                        "✗ Unknown weapon '{weapon}' on archetype {}",
+                        archetype.name
+                    );
+                }
+            }
+            for weapon in archetype.resistances.keys() {
+                if crate::models::WeaponType::from_name(weapon, &self.weapons).is_none() {
+                    println!(
+                        "✗ Unknown weapon '{weapon}' in resistances of archetype {}",
+                        archetype.name
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Create default config file for mods/testers
     #[allow(dead_code)] // Utility for generating config.toml template
     pub fn create_template() -> std::io::Result<()> {
@@ -8,7 +8,8 @@ pub fn default_config() -> GameConfig {
         window: default_window(),
         player: default_player(),
         energy: default_energy(),
-        entities: default_entities(),
+        graze: default_graze(),
+        archetypes: default_archetypes(),
         weapons: default_weapons(),
         spawning: default_spawning(),
         formations: default_formations(),
@@ -23,6 +24,12 @@ pub fn default_config() -> GameConfig {
         background: default_background(),
         animations: default_animations(), // Animation defaults
         dash: default_dash(),             // Dash mechanic defaults
+        upgrades: default_upgrades(),     // Weapon level rewards
+        modifiers: default_modifiers(),   // Chain/split tuning
+        damage: default_damage(),         // Owner multipliers, crits, armor
+        status: default_status(),         // Burn ticks, status tints
+        simulation: default_simulation(), // Fixed-timestep defaults
+        bindings: default_bindings(),     // QWERTY key layout
     }
 }
 
@@ -35,6 +42,50 @@ fn default_window() -> WindowConfig {
     }
 }
 
+fn default_bindings() -> BindingsConfig {
+    let keys = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+    BindingsConfig {
+        move_up: keys(&["W", "Up"]),
+        move_down: keys(&["S", "Down"]),
+        move_left: keys(&["A", "Left"]),
+        move_right: keys(&["D", "Right"]),
+        dash: keys(&["LeftShift", "RightShift"]),
+        fire_slots: vec![
+            keys(&["H"]),         // Bullets
+            keys(&["J"]),         // Lasers
+            keys(&["K"]),         // Missiles
+            keys(&["L"]),         // Plasma
+            keys(&["Semicolon"]), // Bombs
+        ],
+        parry: keys(&["X"]),
+        cancel_summon: keys(&["C"]),
+        summon_formation: keys(&["Space"]),
+        formation_line: keys(&["Key1"]),
+        formation_circle: keys(&["Key2"]),
+        formation_v: keys(&["Key3"]),
+        summon: [
+            ("BasicFighter", "F1"),
+            ("Sniper", "F2"),
+            ("Tank", "F3"),
+            ("Healer", "F4"),
+            ("Splitter", "F5"),
+            ("Elite", "F6"),
+        ]
+        .into_iter()
+        .map(|(entity, key)| (entity.to_string(), keys(&[key])))
+        .collect(),
+    }
+}
+
+fn default_simulation() -> SimulationConfig {
+    SimulationConfig {
+        tick_rate: 120.0,     // 120 Hz physics, rendering interpolates in between
+        max_frame_time: 0.25, // Drop time beyond 250ms (debugger pauses, window drags)
+        seed: 0,              // Pick a fresh seed each run (printed at startup)
+    }
+}
+
 fn default_player() -> PlayerConfig {
     PlayerConfig {
         starting_health: 250.0,
@@ -47,6 +98,7 @@ fn default_player() -> PlayerConfig {
             "Laser".to_string(),
             "Missile".to_string(),
             "Plasma".to_string(),
+            "Bombs".to_string(),
         ],
 
         // Parry system
@@ -58,6 +110,16 @@ fn default_player() -> PlayerConfig {
         acceleration: 5000.0, // Fast acceleration for responsive feel
         friction: 1.0,        // Gentle drift (4% slowdown per frame)
         responsiveness: 15.0, // Fast turn speed
+        armor: 0.0,
+
+        weapon_modifiers: [(
+            "Plasma".to_string(),
+            Modifiers {
+                ricochet: 1, // Plasma bounces off the screen edges once
+                ..Default::default()
+            },
+        )]
+        .into(),
     }
 }
 
@@ -68,101 +130,699 @@ fn default_energy() -> EnergyConfig {
     }
 }
 
-fn default_entities() -> EntitiesConfig {
-    EntitiesConfig {
-        basic_fighter: EntityStats {
+fn default_graze() -> GrazeConfig {
+    GrazeConfig {
+        radius: 40.0, // Well outside the 15 px hitbox
+        energy: 2.0,
+    }
+}
+
+fn default_archetypes() -> Vec<ArchetypeConfig> {
+    vec![
+        ArchetypeConfig {
+            name: "BasicFighter".to_string(),
             health: 50.0,
             damage: 10.0,
             energy_cost: 10.0,
             fire_interval: 2.0,
             weapons: vec!["Bullet".to_string()],
+            color: [0.90, 0.16, 0.22], // Red
+            shape: Shape::Circle,
+            entry_path: EntryPath::Swoop,
+            fire_direction: FireDirection::Down,
+            random_spawn_weight: 2,
+            healer: None,
+            splitter: None,
+            shield: None,
+            behavior: None,
+            ai: None,
+            ghost_modifiers: Modifiers::default(),
+            armor: 0.0,
+            resistances: Default::default(),
         },
-        sniper: EntityStats {
+        ArchetypeConfig {
+            name: "Sniper".to_string(),
             health: 30.0,
             damage: 25.0,
             energy_cost: 25.0,
             fire_interval: 3.5,
-            weapons: vec!["Laser".to_string()],
+            weapons: vec!["SniperLaser".to_string()],
+            color: [0.00, 0.47, 0.95], // Blue
+            shape: Shape::Circle,
+            entry_path: EntryPath::SideSweep,
+            fire_direction: FireDirection::LeadPlayer,
+            random_spawn_weight: 2,
+            healer: None,
+            splitter: None,
+            shield: None,
+            behavior: None,
+            ai: Some(sniper_ai()),
+            ghost_modifiers: Modifiers::default(),
+            armor: 0.0,
+            resistances: Default::default(),
         },
-        tank: EntityStats {
+        ArchetypeConfig {
+            name: "Tank".to_string(),
             health: 150.0,
             damage: 15.0,
             energy_cost: 40.0,
             fire_interval: 1.5,
             weapons: vec!["Missile".to_string()],
+            color: [0.00, 0.89, 0.19], // Green
+            shape: Shape::Circle,
+            entry_path: EntryPath::Heavy,
+            fire_direction: FireDirection::LeadPlayer,
+            random_spawn_weight: 2,
+            healer: None,
+            splitter: None,
+            shield: Some(ShieldTrait {
+                capacity: 80.0,
+                arc: 120.0, // Front third
+                regen_rate: 20.0,
+                recharge_delay: 3.0,
+                break_stagger: 1.0,
+            }),
+            behavior: None,
+            ai: None,
+            ghost_modifiers: Modifiers {
+                split: 3, // Missiles burst into shrapnel
+                ..Default::default()
+            },
+            armor: 50.0, // Soaks a third of every hit
+            resistances: [("Bullet".to_string(), 0.5)].into(), // Half damage from Bullets
         },
-        elite: EntityStats {
+        ArchetypeConfig {
+            name: "Elite".to_string(),
             health: 500.0,
             damage: 50.0,
             energy_cost: 80.0,
             fire_interval: 0.8,
             weapons: vec![
-                "Laser".to_string(),
-                "Missile".to_string(),
+                "SniperLaser".to_string(),
+                "Seeker".to_string(),
                 "Plasma".to_string(),
             ],
+            color: [1.00, 0.80, 0.00], // Gold
+            shape: Shape::Circle,
+            entry_path: EntryPath::Showcase,
+            fire_direction: FireDirection::LeadPlayer,
+            random_spawn_weight: 1,
+            healer: None,
+            splitter: None,
+            shield: Some(ShieldTrait {
+                capacity: 150.0,
+                arc: 160.0,
+                regen_rate: 30.0,
+                recharge_delay: 2.5,
+                break_stagger: 0.6,
+            }),
+            behavior: None,
+            ai: Some(elite_ai()),
+            ghost_modifiers: Modifiers {
+                chain: 2,
+                ..Default::default()
+            },
+            armor: 25.0,
+            resistances: Default::default(),
         },
-        healer: HealerStats {
+        ArchetypeConfig {
+            name: "Healer".to_string(),
             health: 60.0,
             damage: 5.0,
             energy_cost: 20.0,
             fire_interval: 3.0,
-            weapons: vec!["Bullet".to_string()],
-            // Healing stats
-            heal_rate: 15.0,    // Heals 15 HP/sec to allies in range
-            heal_radius: 150.0, // 150 pixel radius healing field
+            weapons: vec!["Bullet".to_string(), "EMP".to_string()], // EMP drains energy
+            color: [0.00, 0.62, 0.18],                              // Lime
+            shape: Shape::Circle,
+            entry_path: EntryPath::Gentle,
+            fire_direction: FireDirection::AtPlayer,
+            random_spawn_weight: 0, // Wave-only
+            healer: Some(HealerTrait {
+                heal_rate: 15.0,    // Heals 15 HP/sec to allies in range
+                heal_radius: 150.0, // 150 pixel radius healing field
+            }),
+            splitter: None,
+            shield: None,
+            behavior: None,
+            ai: Some(healer_ai()),
+            ghost_modifiers: Modifiers::default(),
+            armor: 0.0,
+            resistances: Default::default(),
         },
-        splitter: SplitterStats {
+        ArchetypeConfig {
+            name: "Splitter".to_string(),
             health: 80.0,
             damage: 12.0,
             energy_cost: 25.0,
             fire_interval: 2.0,
             weapons: vec!["Bullet".to_string()],
-            // Splitting stats
-            split_count: 3,
-            split_health_ratio: 0.3,
-            split_speed_multiplier: 2.0,
+            color: [0.50, 0.42, 0.31], // Brown
+            shape: Shape::Circle,
+            entry_path: EntryPath::Gentle,
+            fire_direction: FireDirection::AtPlayer,
+            random_spawn_weight: 2,
+            healer: None,
+            splitter: Some(SplitterTrait {
+                split_into: "BasicFighter".to_string(),
+                split_count: 3,
+                split_health_ratio: 0.3,
+                split_speed_multiplier: 2.0,
+            }),
+            shield: None,
+            behavior: None,
+            ai: None,
+            ghost_modifiers: Modifiers::default(),
+            armor: 0.0,
+            resistances: Default::default(),
         },
+        ArchetypeConfig {
+            name: "Gunship".to_string(),
+            health: 220.0,
+            damage: 20.0,
+            energy_cost: 50.0,
+            fire_interval: 1.2,
+            weapons: vec!["Shotgun".to_string(), "BurstRifle".to_string()],
+            color: [0.55, 0.60, 0.70], // Gunmetal
+            shape: Shape::Square,
+            entry_path: EntryPath::SideSweep,
+            fire_direction: FireDirection::AtPlayer,
+            random_spawn_weight: 0, // Wave-only
+            healer: None,
+            splitter: None,
+            shield: None,
+            behavior: Some("strafe".to_string()), // scripts/behaviors.lua
+            ai: None,
+            ghost_modifiers: Modifiers {
+                ricochet: 1,
+                ..Default::default()
+            },
+            armor: 25.0,
+            resistances: Default::default(),
+        },
+    ]
+}
+
+/// Snipers hold long range and slide sideways between shots
+fn sniper_ai() -> AiNode {
+    AiNode::Parallel {
+        children: vec![
+            AiNode::KeepDistance {
+                distance: 420.0,
+                speed: 90.0,
+            },
+            AiNode::Selector {
+                children: vec![AiNode::FireLead, AiNode::Strafe { speed: 120.0 }],
+            },
+        ],
     }
 }
 
-// Weapon config with balanced stats
-fn default_weapons() -> WeaponsConfig {
-    WeaponsConfig {
-        bullet: WeaponStats {
+/// Elites strafe at mid range and pull back when badly hurt
+fn elite_ai() -> AiNode {
+    AiNode::Selector {
+        children: vec![
+            AiNode::Retreat {
+                below_health: 0.25,
+                speed: 120.0,
+            },
+            AiNode::Parallel {
+                children: vec![
+                    AiNode::KeepDistance {
+                        distance: 300.0,
+                        speed: 80.0,
+                    },
+                    AiNode::Strafe { speed: 150.0 },
+                    AiNode::FireLead,
+                ],
+            },
+        ],
+    }
+}
+
+/// Healers hide behind the nearest Tank, or hang back when there is none
+fn healer_ai() -> AiNode {
+    AiNode::Parallel {
+        children: vec![
+            AiNode::Selector {
+                children: vec![
+                    AiNode::HideBehind {
+                        archetype: "Tank".to_string(),
+                        distance: 70.0,
+                        speed: 110.0,
+                    },
+                    AiNode::KeepDistance {
+                        distance: 380.0,
+                        speed: 80.0,
+                    },
+                ],
+            },
+            AiNode::FireAt,
+        ],
+    }
+}
+
+// Weapon registry with balanced stats (entities refer to these by name)
+fn default_weapons() -> Vec<WeaponConfig> {
+    vec![
+        WeaponConfig {
+            name: "Bullet".to_string(),
             damage: 10.0,
+            crit_chance: 0.05,
             fire_rate: 0.1,
             projectile_speed: 350.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 5.0,
+            parryable: false,
+            color: [0.99, 0.98, 0.0], // Yellow
+            size: 4.0,
+            glow: false,
+            trail_color: [1.0, 1.0, 0.6, 0.6], // Faint yellow
+            enemy_trail_color: None,
+            trail_interval: 0.5,
+            trail_particles: 2,
+            hit_particles: 0, // Small sparks
+            hit_color: [0.99, 0.98, 0.0],
+            shake_intensity: 0.8,
+            levels: vec![
+                level(2, 6.0, 1, 1.0),
+                level(3, 8.0, 1, 1.0),
+                level(4, 8.0, 1, 1.1),
+                level(5, 10.0, 1, 1.2),
+            ],
+            heat: None,
+            ammo: None,
+            homing: None,
+            enemy_homing: None,
+            bomb: None,
+            beam: None,
+            status: Vec::new(),
         },
-        laser: WeaponStats {
-            damage: 60.0,
+        WeaponConfig {
+            name: "Laser".to_string(),
+            damage: 0.0, // Beams use damage_per_second
+            crit_chance: 0.0,
             fire_rate: 1.5,
             projectile_speed: 850.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 5.0,
+            parryable: false,
+            color: [0.0, 0.89, 0.19], // Green
+            size: 6.0,
+            glow: true,
+            trail_color: [0.2, 0.8, 1.0, 0.9], // Cyan glow
+            enemy_trail_color: None,
+            trail_interval: 0.01,
+            trail_particles: 20,
+            hit_particles: 18,
+            hit_color: [0.4, 0.75, 1.0], // Sky blue
+            shake_intensity: 1.5,        // Repeats while the beam touches enemies
+            levels: vec![
+                level(1, 0.0, 1, 1.25),
+                level(1, 0.0, 1, 1.5),
+                level(1, 0.0, 1, 1.75),
+                level(1, 0.0, 1, 2.0),
+            ],
+            heat: Some(HeatTrait {
+                per_shot: 0.4, // Three beams in a row overheat it
+                cooling: 0.35,
+                recover_at: 0.3,
+            }),
+            ammo: None,
+            homing: None,
+            enemy_homing: None,
+            bomb: None,
+            beam: Some(BeamTrait {
+                warmup: 0.1,
+                duration: 1.0,
+                length: 700.0,
+                width: 6.0,
+                damage_per_second: 90.0,
+            }),
+            status: Vec::new(),
+        },
+        WeaponConfig {
+            name: "SniperLaser".to_string(),
+            damage: 0.0, // Beams use damage_per_second
+            crit_chance: 0.0,
+            fire_rate: 4.0,
+            projectile_speed: 0.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 0.0,
+            parryable: false,
+            color: [1.0, 0.25, 0.3],
+            size: 4.0,
+            glow: true,
+            trail_color: [1.0, 0.3, 0.3, 0.8],
+            enemy_trail_color: None,
+            trail_interval: 1.0,
+            trail_particles: 0,
+            hit_particles: 0,
+            hit_color: [1.0, 0.35, 0.35],
+            shake_intensity: 3.0,
+            levels: Vec::new(),
+            heat: None,
+            ammo: None,
+            homing: None,
+            enemy_homing: None,
+            bomb: None,
+            beam: Some(BeamTrait {
+                warmup: 0.9, // Long, visible charge-up
+                duration: 0.35,
+                length: 900.0,
+                width: 4.0,
+                damage_per_second: 120.0,
+            }),
+            status: Vec::new(),
         },
-        missile: WeaponStats {
+        WeaponConfig {
+            name: "Missile".to_string(),
             damage: 30.0,
+            crit_chance: 0.1,
             fire_rate: 0.5,
             projectile_speed: 250.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 5.0,
+            parryable: true,
+            color: [1.0, 0.63, 0.0], // Orange
+            size: 5.0,
+            glow: true,
+            trail_color: [1.0, 0.6, 0.2, 0.8], // Orange smoke
+            enemy_trail_color: Some([0.9, 0.2, 0.2, 0.8]), // Red smoke
+            trail_interval: 0.5,
+            trail_particles: 3,
+            hit_particles: 22,
+            hit_color: [1.0, 0.63, 0.0],
+            shake_intensity: 2.5,
+            levels: vec![
+                level(1, 0.0, 2, 1.0),
+                level(3, 20.0, 1, 1.0),
+                level(3, 20.0, 2, 1.0),
+                level(5, 15.0, 2, 1.0),
+            ],
+            heat: None,
+            ammo: Some(AmmoTrait {
+                max: 8,
+                regen_time: 1.5,
+            }),
+            homing: Some(HomingTrait {
+                turn_rate: 270.0,
+                max_speed: 450.0,
+                acceleration: 400.0,
+                lock_cone: 120.0,
+                reacquire: true, // Picks the next enemy when its target dies
+                proximity_radius: 0.0,
+            }),
+            enemy_homing: Some(HomingTrait {
+                turn_rate: 90.0, // Wide turns - can be outrun
+                max_speed: 230.0,
+                acceleration: 60.0,
+                lock_cone: 100.0,
+                reacquire: false,
+                proximity_radius: 0.0,
+            }),
+            bomb: None,
+            beam: None,
+            status: vec![StatusTrait {
+                kind: StatusKind::Stun,
+                duration: 0.4,
+                magnitude: 0.0,
+                stacking: Stacking::Refresh,
+                max_stacks: 1,
+            }],
+        },
+        WeaponConfig {
+            name: "Seeker".to_string(),
+            damage: 20.0,
+            crit_chance: 0.0,
+            fire_rate: 0.9,
+            projectile_speed: 150.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 6.0,
+            parryable: true,
+            color: [1.0, 0.85, 0.2], // Gold
+            size: 4.0,
+            glow: true,
+            trail_color: [1.0, 0.85, 0.4, 0.8], // Gold smoke
+            enemy_trail_color: Some([1.0, 0.35, 0.6, 0.8]), // Pink smoke
+            trail_interval: 0.5,
+            trail_particles: 3,
+            hit_particles: 16,
+            hit_color: [1.0, 0.85, 0.2],
+            shake_intensity: 2.0,
+            levels: Vec::new(),
+            heat: None,
+            ammo: None,
+            homing: Some(HomingTrait {
+                turn_rate: 360.0,
+                max_speed: 380.0,
+                acceleration: 250.0,
+                lock_cone: 180.0,
+                reacquire: true,
+                proximity_radius: 18.0,
+            }),
+            enemy_homing: Some(HomingTrait {
+                turn_rate: 200.0, // Tight tracker - parry it
+                max_speed: 300.0,
+                acceleration: 150.0,
+                lock_cone: 180.0,
+                reacquire: true,
+                proximity_radius: 14.0, // Detonates on near misses
+            }),
+            bomb: None,
+            beam: None,
+            status: Vec::new(),
         },
-        plasma: WeaponStats {
+        WeaponConfig {
+            name: "Plasma".to_string(),
             damage: 25.0,
+            crit_chance: 0.1,
             fire_rate: 0.4,
             projectile_speed: 500.0,
+            projectile_count: 3, // Spread shot
+            spread_angle: 15.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 5.0,
+            parryable: false,
+            color: [0.78, 0.48, 1.0], // Purple
+            size: 5.0,
+            glow: true,
+            trail_color: [0.8, 0.2, 1.0, 0.8], // Purple energy
+            enemy_trail_color: None,
+            trail_interval: 0.75,
+            trail_particles: 3,
+            hit_particles: 16,
+            hit_color: [0.78, 0.48, 1.0],
+            shake_intensity: 1.5,
+            levels: vec![
+                level(4, 12.0, 1, 1.0),
+                level(5, 12.0, 1, 1.0),
+                level(6, 10.0, 1, 1.1),
+                level(7, 10.0, 1, 1.2),
+            ],
+            heat: Some(HeatTrait {
+                per_shot: 0.15,
+                cooling: 0.25,
+                recover_at: 0.4,
+            }),
+            ammo: None,
+            homing: None,
+            enemy_homing: None,
+            bomb: None,
+            beam: None,
+            status: vec![StatusTrait {
+                kind: StatusKind::Burn,
+                duration: 3.0,
+                magnitude: 4.0, // Damage/sec per stack
+                stacking: Stacking::Stack,
+                max_stacks: 3,
+            }],
         },
-        bombs: WeaponStats {
+        WeaponConfig {
+            name: "Bombs".to_string(),
             damage: 80.0,
+            crit_chance: 0.0,
             fire_rate: 2.0,
             projectile_speed: 200.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 80.0, // AOE on impact
+            lifetime: 5.0,
+            parryable: false,
+            color: [0.9, 0.16, 0.22], // Red
+            size: 8.0,
+            glow: true,
+            trail_color: [1.0, 0.3, 0.0, 0.7], // Fiery red-orange
+            enemy_trail_color: None,
+            trail_interval: 1.0,
+            trail_particles: 3,
+            hit_particles: 55,
+            hit_color: [0.9, 0.16, 0.22],
+            shake_intensity: 5.0,
+            levels: Vec::new(),
+            heat: None,
+            ammo: Some(AmmoTrait {
+                max: 3,
+                regen_time: 8.0, // Slowly regained
+            }),
+            homing: None,
+            enemy_homing: None,
+            bomb: Some(BombTrait {
+                fuse_time: 1.5,
+                contact: true,
+                remote: true,
+                edge_damage: 0.3,
+                clears_projectiles: true,
+                shockwave_duration: 0.4,
+            }),
+            beam: None,
+            status: Vec::new(),
         },
-    }
+        WeaponConfig {
+            name: "Shotgun".to_string(),
+            damage: 12.0,
+            crit_chance: 0.0,
+            fire_rate: 0.8,
+            projectile_speed: 450.0,
+            projectile_count: 5, // 5-way fan
+            spread_angle: 10.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 0.8, // Short range
+            parryable: false,
+            color: [1.0, 0.85, 0.4],
+            size: 3.5,
+            glow: false,
+            trail_color: [1.0, 0.8, 0.4, 0.5],
+            enemy_trail_color: None,
+            trail_interval: 0.5,
+            trail_particles: 1,
+            hit_particles: 0,
+            hit_color: [1.0, 0.85, 0.4],
+            shake_intensity: 1.2,
+            levels: Vec::new(),
+            heat: None,
+            ammo: None,
+            homing: None,
+            enemy_homing: None,
+            bomb: None,
+            beam: None,
+            status: Vec::new(),
+        },
+        WeaponConfig {
+            name: "BurstRifle".to_string(),
+            damage: 14.0,
+            crit_chance: 0.05,
+            fire_rate: 0.6,
+            projectile_speed: 600.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 3, // 3-round burst
+            burst_interval: 0.08,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 5.0,
+            parryable: false,
+            color: [0.5, 1.0, 0.8],
+            size: 3.5,
+            glow: true,
+            trail_color: [0.5, 1.0, 0.8, 0.6],
+            enemy_trail_color: None,
+            trail_interval: 0.5,
+            trail_particles: 2,
+            hit_particles: 8,
+            hit_color: [0.5, 1.0, 0.8],
+            shake_intensity: 1.0,
+            levels: Vec::new(),
+            heat: None,
+            ammo: None,
+            homing: None,
+            enemy_homing: None,
+            bomb: None,
+            beam: None,
+            status: Vec::new(),
+        },
+        WeaponConfig {
+            name: "EMP".to_string(),
+            damage: 4.0,
+            crit_chance: 0.0,
+            fire_rate: 1.0,
+            projectile_speed: 260.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+            modifiers: Modifiers::default(),
+            explosion_radius: 0.0,
+            lifetime: 5.0,
+            parryable: true,
+            color: [0.4, 0.6, 1.0], // Electric blue
+            size: 6.0,
+            glow: true,
+            trail_color: [0.4, 0.6, 1.0, 0.6],
+            enemy_trail_color: None,
+            trail_interval: 0.5,
+            trail_particles: 2,
+            hit_particles: 10,
+            hit_color: [0.4, 0.6, 1.0],
+            shake_intensity: 0.6,
+            levels: Vec::new(),
+            heat: None,
+            ammo: None,
+            homing: None,
+            enemy_homing: None,
+            bomb: None,
+            beam: None,
+            status: vec![StatusTrait {
+                kind: StatusKind::EnergyDrain,
+                duration: 3.0,
+                magnitude: 15.0, // Energy/sec
+                stacking: Stacking::Refresh,
+                max_stacks: 1,
+            }],
+        },
+    ]
 }
 
 fn default_spawning() -> SpawningConfig {
     SpawningConfig {
         wave_mode: false,          // Enable Lua wave system by default
-        wave_count: 5,             // 5 waves total
+        wave_count: 6,             // 6 waves total
         enemy_spawn_interval: 2.2, // Random spawn timer
         initial_delay: 3.0,        // Initial delay before spawning
+        tick_hook_interval: 0.25,  // Lua on_tick four times a second
     }
 }
 
@@ -190,6 +850,7 @@ fn default_collision() -> CollisionConfig {
         enemy_radius: 15.0,
         player_radius: 15.0,
         ghost_radius: 12.0,
+        spatial_cell_size: 64.0, // ~2x the largest entity diameter
     }
 }
 
@@ -212,6 +873,8 @@ fn default_enemy_behavior() -> EnemyBehaviorConfig {
         fire_threshold_y: 50.0,
         screen_boundary_bottom: 650.0,
         basic_projectile_speed_y: 250.0,
+        lua_instruction_budget: 200_000, // Shared by every scripted enemy each tick
+        lua_max_speed: 300.0,
     }
 }
 
@@ -235,11 +898,6 @@ fn default_projectile_bounds() -> ProjectileBoundsConfig {
 
 fn default_screen_shake() -> ScreenShakeConfig {
     ScreenShakeConfig {
-        bullet_hit_intensity: 0.8,
-        laser_hit_intensity: 4.0,
-        missile_hit_intensity: 2.5,
-        plasma_hit_intensity: 1.5,
-        bomb_hit_intensity: 5.0,
         weapon_hit_duration: 0.5,
 
         // Event-specific shake
@@ -273,14 +931,6 @@ fn default_particles() -> ParticleConfig {
         spark_speed_min: 80.0,
         spark_speed_max: 200.0,
 
-        // Weapon-specific particle counts
-        bullet_particle_count: 10,
-        laser_particle_count: 18,
-        missile_particle_count: 22,
-        plasma_particle_count: 16,
-        bomb_red_particle_count: 30,
-        bomb_orange_particle_count: 25,
-
         // Death explosion counts
         death_red_count: 15,
         death_orange_count: 10,
@@ -335,6 +985,62 @@ fn default_animations() -> AnimationConfig {
     }
 }
 
+/// Upgrade tier shorthand: projectile_count, spread_angle, burst_count, damage_multiplier
+fn level(
+    projectile_count: usize,
+    spread_angle: f32,
+    burst_count: usize,
+    damage_multiplier: f32,
+) -> WeaponLevel {
+    WeaponLevel {
+        projectile_count,
+        spread_angle,
+        burst_count,
+        damage_multiplier,
+    }
+}
+
+fn default_modifiers() -> ModifiersConfig {
+    ModifiersConfig {
+        chain_range: 160.0,
+        chain_damage: 0.6, // Each jump deals 60% of the previous one
+        split_damage: 0.4,
+        split_spread_angle: 30.0,
+    }
+}
+
+fn default_damage() -> DamageConfig {
+    DamageConfig {
+        enemy_multiplier: 0.75, // Enemies deal 75% damage
+        ghost_multiplier: 0.5,  // Ghosts deal 50% damage
+        crit_multiplier: 2.0,
+        armor_scale: 100.0, // 100 armor = half damage
+    }
+}
+
+fn default_status() -> StatusConfig {
+    StatusConfig {
+        tick_interval: 0.25,         // Burn damage four times a second
+        burn_tint: [1.0, 0.45, 0.1], // Orange
+        slow_tint: [0.3, 0.6, 1.0],  // Icy blue
+        stun_tint: [1.0, 1.0, 0.3],  // Yellow
+        drain_tint: [0.6, 0.3, 1.0], // Violet
+        tint_strength: 0.5,
+    }
+}
+
+fn default_upgrades() -> UpgradesConfig {
+    UpgradesConfig {
+        levels_per_wave: 1,       // One slot levels up per cleared wave
+        pickup_drop_chance: 0.08, // ~1 in 12 kills drops a pickup
+        pickup_fall_speed: 60.0,
+        pickup_radius: 24.0,
+        pickup_lifetime: 10.0,
+        levels_lost_on_hit: 1,
+        downgrade_grace: 2.0, // A beam or spread can't strip several levels at once
+    }
+}
+
 fn default_dash() -> DashConfig {
     DashConfig {
         enabled: true,         // Dash enabled by default
@@ -0,0 +1,358 @@
+//! Behavior trees - enemies with an `[archetypes.ai]` tree steer and shoot by it
+//!
+//! The tree is evaluated from the root every tick once the entry path is done, so a higher
+//! priority branch takes over as soon as it applies. Movement leaves add velocity only while
+//! running; fire leaves request a shot that `update_enemies` takes through the usual fire
+//! timer. Cooldowns and strafe directions live in `AiMemory`, one slot per node in preorder.
+
+use crate::config::{AiNode, ArchetypeConfig};
+use crate::game::weapons::FiringDirection;
+use crate::models::*;
+
+const ARRIVE_RADIUS: f32 = 8.0; // MoveTo/HideBehind have arrived this close
+const RANGE_TOLERANCE: f32 = 20.0; // KeepDistance is in range within this much
+const EDGE_MARGIN: f32 = 40.0; // Strafe turns around this close to the arena sides
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NodeStatus {
+    Success,
+    Failure,
+    Running,
+}
+
+/// Per-enemy tree state
+#[derive(Clone, Debug, Default)]
+pub struct AiMemory {
+    slots: Vec<f32>,               // Per node: cooldown end time, strafe direction
+    pub active: Vec<&'static str>, // Leaves that ran or succeeded last tick (debug overlay)
+}
+
+/// What a tree sees for one enemy (read-only)
+pub struct AiContext<'a> {
+    pub id: EntityId,
+    pub pos: Position,
+    pub health_fraction: f32,
+    pub fire_ready: bool, // Fire timer done, below the fire line, not stunned
+    pub player_pos: Position,
+    pub player_vel: Position,
+    pub arena: Position,
+    pub time: f32,
+    pub allies: &'a [(EntityId, EntityType, Position)], // Every enemy at the start of the tick
+    pub archetypes: &'a [ArchetypeConfig],
+}
+
+/// What a tree asked for this tick (applied by `update_enemies`)
+#[derive(Clone, Copy, Default)]
+pub struct AiOutput {
+    pub velocity: Position,
+    pub fire: Option<FiringDirection>,
+}
+
+/// Evaluate `tree` for one enemy
+pub fn tick_tree(tree: &AiNode, memory: &mut AiMemory, ctx: &AiContext) -> AiOutput {
+    memory.slots.resize(tree.size(), 0.0);
+    memory.active.clear();
+    let mut out = AiOutput::default();
+    tick_node(tree, 0, memory, ctx, &mut out);
+    out
+}
+
+fn tick_node(
+    node: &AiNode,
+    index: usize,
+    memory: &mut AiMemory,
+    ctx: &AiContext,
+    out: &mut AiOutput,
+) -> NodeStatus {
+    use NodeStatus::*;
+
+    match node {
+        AiNode::Sequence { children } => {
+            let mut child_index = index + 1;
+            for child in children {
+                let status = tick_node(child, child_index, memory, ctx, out);
+                if status != Success {
+                    return status;
+                }
+                child_index += child.size();
+            }
+            Success
+        }
+        AiNode::Selector { children } => {
+            let mut child_index = index + 1;
+            for child in children {
+                let status = tick_node(child, child_index, memory, ctx, out);
+                if status != Failure {
+                    return status;
+                }
+                child_index += child.size();
+            }
+            Failure
+        }
+        AiNode::Parallel { children } => {
+            let mut child_index = index + 1;
+            let mut result = Success;
+            for child in children {
+                match tick_node(child, child_index, memory, ctx, out) {
+                    Failure => result = Failure,
+                    Running if result == Success => result = Running,
+                    _ => {}
+                }
+                child_index += child.size();
+            }
+            result
+        }
+        AiNode::Cooldown { seconds, child } => {
+            if ctx.time < memory.slots[index] {
+                return Failure;
+            }
+            let status = tick_node(child, index + 1, memory, ctx, out);
+            if status == Success {
+                memory.slots[index] = ctx.time + seconds;
+            }
+            status
+        }
+        AiNode::Inverter { child } => match tick_node(child, index + 1, memory, ctx, out) {
+            Success => Failure,
+            Failure => Success,
+            Running => Running,
+        },
+        leaf => {
+            let status = tick_leaf(leaf, index, memory, ctx, out);
+            if status != Failure {
+                memory.active.push(leaf.name());
+            }
+            status
+        }
+    }
+}
+
+fn tick_leaf(
+    leaf: &AiNode,
+    index: usize,
+    memory: &mut AiMemory,
+    ctx: &AiContext,
+    out: &mut AiOutput,
+) -> NodeStatus {
+    use NodeStatus::*;
+
+    let away_from_player = (ctx.pos - ctx.player_pos)
+        .try_normalize()
+        .unwrap_or(Position::NEG_Y);
+
+    match leaf {
+        AiNode::MoveTo { x, y, speed } => {
+            let target = Position::new(x * ctx.arena.x, y * ctx.arena.y);
+            steer(ctx.pos, target, *speed, out)
+        }
+        AiNode::Strafe { speed } => {
+            let direction = &mut memory.slots[index];
+            if *direction == 0.0 {
+                *direction = if ctx.id.0.is_multiple_of(2) {
+                    1.0
+                } else {
+                    -1.0
+                }; // Neighbours split up
+            }
+            if ctx.pos.x < EDGE_MARGIN {
+                *direction = 1.0;
+            } else if ctx.pos.x > ctx.arena.x - EDGE_MARGIN {
+                *direction = -1.0;
+            }
+            out.velocity.x += *direction * speed;
+            Running
+        }
+        AiNode::KeepDistance { distance, speed } => {
+            let gap = ctx.pos.distance(ctx.player_pos) - distance;
+            if gap.abs() <= RANGE_TOLERANCE {
+                return Success;
+            }
+            out.velocity -= away_from_player * gap.signum() * *speed;
+            Running
+        }
+        AiNode::Retreat {
+            below_health,
+            speed,
+        } => {
+            if ctx.health_fraction > *below_health {
+                return Failure;
+            }
+            out.velocity += away_from_player * *speed;
+            Running
+        }
+        AiNode::HideBehind {
+            archetype,
+            distance,
+            speed,
+        } => {
+            let cover = ctx
+                .allies
+                .iter()
+                .filter(|(id, kind, _)| *id != ctx.id && kind.name(ctx.archetypes) == archetype)
+                .map(|&(_, _, pos)| pos)
+                .min_by(|a, b| {
+                    a.distance_squared(ctx.pos)
+                        .total_cmp(&b.distance_squared(ctx.pos))
+                });
+            let Some(cover) = cover else {
+                return Failure;
+            };
+            let behind = cover
+                + (cover - ctx.player_pos)
+                    .try_normalize()
+                    .unwrap_or(Position::NEG_Y)
+                    * *distance;
+            steer(ctx.pos, behind, *speed, out)
+        }
+        AiNode::FireAt | AiNode::FireLead => {
+            if !ctx.fire_ready {
+                return Failure;
+            }
+            out.fire = Some(match leaf {
+                AiNode::FireAt => FiringDirection::AtTarget(ctx.player_pos),
+                _ => FiringDirection::LeadTarget {
+                    target_pos: ctx.player_pos,
+                    target_vel: ctx.player_vel,
+                },
+            });
+            Success
+        }
+        AiNode::WaitForAllies { count } => {
+            let others = ctx.allies.iter().filter(|(id, ..)| *id != ctx.id).count();
+            if others >= *count { Success } else { Running }
+        }
+        AiNode::Sequence { .. }
+        | AiNode::Selector { .. }
+        | AiNode::Parallel { .. }
+        | AiNode::Cooldown { .. }
+        | AiNode::Inverter { .. } => unreachable!("composites are handled by tick_node"),
+    }
+}
+
+/// Head for `target` at `speed` (succeeds once there)
+fn steer(from: Position, to: Position, speed: f32, out: &mut AiOutput) -> NodeStatus {
+    if from.distance(to) <= ARRIVE_RADIUS {
+        return NodeStatus::Success;
+    }
+    out.velocity += (to - from).normalize() * speed;
+    NodeStatus::Running
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::update_enemies;
+
+    fn context<'a>(
+        allies: &'a [(EntityId, EntityType, Position)],
+        archetypes: &'a [ArchetypeConfig],
+    ) -> AiContext<'a> {
+        AiContext {
+            id: EntityId(1),
+            pos: Position::new(400.0, 100.0),
+            health_fraction: 1.0,
+            fire_ready: true,
+            player_pos: Position::new(400.0, 500.0),
+            player_vel: Position::ZERO,
+            arena: Position::new(800.0, 600.0),
+            time: 0.0,
+            allies,
+            archetypes,
+        }
+    }
+
+    #[test]
+    fn test_cooldown_and_selector_fall_through() {
+        let archetypes = crate::config::GameConfig::default().archetypes;
+        let tree = AiNode::Selector {
+            children: vec![
+                AiNode::Cooldown {
+                    seconds: 2.0,
+                    child: Box::new(AiNode::FireAt),
+                },
+                AiNode::Strafe { speed: 100.0 },
+            ],
+        };
+        let mut memory = AiMemory::default();
+        let mut ctx = context(&[], &archetypes);
+
+        let out = tick_tree(&tree, &mut memory, &ctx);
+        assert!(out.fire.is_some());
+        assert_eq!(out.velocity, Position::ZERO);
+        assert_eq!(memory.active, ["FireAt"]);
+
+        ctx.time = 1.0; // Cooling down - strafes instead
+        let out = tick_tree(&tree, &mut memory, &ctx);
+        assert!(out.fire.is_none());
+        assert_eq!(out.velocity.x.abs(), 100.0);
+        assert_eq!(memory.active, ["Strafe"]);
+
+        ctx.time = 2.5;
+        assert!(tick_tree(&tree, &mut memory, &ctx).fire.is_some());
+    }
+
+    #[test]
+    fn test_sequence_inverter_and_wait_for_allies() {
+        let archetypes = crate::config::GameConfig::default().archetypes;
+        let tree = AiNode::Sequence {
+            children: vec![
+                AiNode::Inverter {
+                    child: Box::new(AiNode::Retreat {
+                        below_health: 0.5,
+                        speed: 50.0,
+                    }),
+                },
+                AiNode::WaitForAllies { count: 1 },
+                AiNode::MoveTo {
+                    x: 0.5,
+                    y: 0.5,
+                    speed: 100.0,
+                },
+            ],
+        };
+        let mut memory = AiMemory::default();
+
+        // Alone: holds still
+        let ctx = context(&[], &archetypes);
+        let out = tick_tree(&tree, &mut memory, &ctx);
+        assert_eq!(out.velocity, Position::ZERO);
+        assert_eq!(memory.active, ["WaitForAllies"]);
+
+        // With company: heads for the middle of the arena
+        let allies = [(EntityId(2), EntityType(0), Position::ZERO)];
+        let ctx = context(&allies, &archetypes);
+        let out = tick_tree(&tree, &mut memory, &ctx);
+        assert_eq!(out.velocity, Position::new(0.0, 100.0));
+
+        // Badly hurt: backs away from the player and the sequence stops there
+        let ctx = AiContext {
+            health_fraction: 0.3,
+            ..context(&allies, &archetypes)
+        };
+        let out = tick_tree(&tree, &mut memory, &ctx);
+        assert_eq!(out.velocity, Position::new(0.0, -50.0));
+        assert_eq!(memory.active, ["Retreat"]);
+    }
+
+    #[test]
+    fn test_healer_hides_behind_tank() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.player.pos = Position::new(400.0, 550.0);
+        let spawn = |state: &mut GameState, name, pos| {
+            let kind = EntityType::from_name(name, &state.config.archetypes).unwrap();
+            let id = state.entity_ids.next();
+            let enemy = Enemy::from_entity_type(id, kind, pos, &state.config);
+            state.enemies.push(enemy);
+        };
+        spawn(&mut state, "Tank", Position::new(400.0, 200.0));
+        spawn(&mut state, "Healer", Position::new(150.0, 80.0));
+
+        for _ in 0..480 {
+            update_enemies(&mut state, 1.0 / 120.0);
+        }
+        let (tank, healer) = (state.enemies[0].pos, state.enemies[1].pos);
+        assert!(healer.y < tank.y); // Tank between it and the player
+        assert!((healer.distance(tank) - 70.0).abs() < 20.0);
+        assert!(state.enemies[1].ai.active.contains(&"HideBehind"));
+    }
+}
@@ -0,0 +1,355 @@
+//! Beams - continuous rays that damage everything they cross (lasers, sniper shots)
+//!
+//! A beam warms up first (drawn as a thin telegraph, no damage), then fires for up to
+//! `duration`. Its origin follows the shooter; player beams also stop when the key is released.
+
+use crate::config::BeamTrait;
+use crate::game::damage::{DamageInfo, DamageTarget, apply_damage};
+use crate::game::events::{GameEvent, emit};
+use crate::game::spatial::EntityKind;
+use crate::game::utils::segment_circle_collision;
+use crate::game::weapons::{FireWeaponParams, FiringDirection};
+use crate::models::*;
+use macroquad::prelude::*;
+
+/// Minimum time between hit effects (particles, shake) of one beam
+const BEAM_FX_INTERVAL: f32 = 0.1;
+
+pub struct Beam {
+    pub owner: ProjectileOwner,
+    pub shooter: Option<EntityId>, // Origin follows this entity (None = fixed origin)
+    pub weapon: WeaponType,
+    pub origin: Position,
+    pub prev_origin: Position, // Origin at start of tick (render interpolation)
+    pub direction: Vec2,       // Unit vector, fixed when fired
+    pub elapsed: f32,          // Time since fired (warm-up included)
+    pub damage_multiplier: f32,
+    pub held: bool,         // Player beams: refreshed by input every tick
+    pub crit: bool,         // Rolled once when fired - every tick of a crit beam crits
+    touched: Vec<EntityId>, // Hit at least once (statuses and the crit event happen on first contact)
+    fx_timer: f32,          // Throttles hit effects
+    fx_damage: f32,         // Player damage not yet reported in a PlayerHit event
+}
+
+impl Beam {
+    /// Far end of the ray
+    pub fn end(&self, beam: &BeamTrait) -> Position {
+        self.origin + self.direction * beam.length
+    }
+
+    /// Past the warm-up (dealing damage)
+    pub fn is_firing(&self, beam: &BeamTrait) -> bool {
+        self.elapsed >= beam.warmup
+    }
+}
+
+/// Start a beam (called by `fire_weapon` for weapons with a `[weapons.beam]` table)
+pub fn fire_beam(params: &FireWeaponParams, state: &mut GameState) {
+    // Beams hit instantly, so lead aiming just points at the target
+    let direction = match params.direction {
+        FiringDirection::Up => Vec2::new(0.0, -1.0),
+        FiringDirection::Down => Vec2::new(0.0, 1.0),
+        FiringDirection::AtTarget(target)
+        | FiringDirection::LeadTarget {
+            target_pos: target, ..
+        } => (target - params.shooter_pos)
+            .try_normalize()
+            .unwrap_or(Vec2::new(0.0, 1.0)),
+    };
+
+    // One crit roll per beam, not per tick
+    let crit_chance = params.weapon.config(&state.config.weapons).crit_chance;
+    let crit = crit_chance > 0.0 && state.rng.gen_range(0.0, 1.0) < crit_chance;
+
+    state.beams.push(Beam {
+        owner: params.owner,
+        shooter: params.shooter,
+        weapon: params.weapon,
+        origin: params.shooter_pos,
+        prev_origin: params.shooter_pos,
+        direction,
+        elapsed: 0.0,
+        damage_multiplier: params.damage_multiplier,
+        held: true,
+        crit,
+        touched: Vec::new(),
+        fx_timer: 0.0,
+        fx_damage: 0.0,
+    });
+}
+
+/// Keep the player's beam of this weapon alive (returns false if there is none)
+pub fn hold_player_beam(state: &mut GameState, weapon: WeaponType) -> bool {
+    let mut holding = false;
+    for beam in &mut state.beams {
+        if beam.owner == ProjectileOwner::Player && beam.weapon == weapon {
+            beam.held = true;
+            holding = true;
+        }
+    }
+    holding
+}
+
+/// Follow shooters, expire beams and apply damage (needs a fresh spatial grid)
+pub fn update_beams(state: &mut GameState, delta: f32) {
+    let mut beams = std::mem::take(&mut state.beams);
+
+    beams.retain_mut(|beam| {
+        let Some(beam_cfg) = beam.weapon.config(&state.config.weapons).beam.clone() else {
+            return false;
+        };
+
+        // Player beams stop as soon as the key is released
+        if beam.owner == ProjectileOwner::Player && !beam.held {
+            return false;
+        }
+        beam.held = false;
+
+        // Follow the shooter (beam dies with it)
+        if let Some(id) = beam.shooter {
+            let Some(pos) = state.entity_pos(id) else {
+                return false;
+            };
+            beam.origin = pos;
+        }
+
+        beam.elapsed += delta;
+        if beam.elapsed > beam_cfg.warmup + beam_cfg.duration {
+            return false;
+        }
+
+        beam.fx_timer -= delta;
+        if beam.is_firing(&beam_cfg) {
+            apply_beam_damage(state, beam, &beam_cfg, delta);
+        }
+        true
+    });
+
+    state.beams = beams;
+}
+
+/// Damage everything the ray crosses this tick
+fn apply_beam_damage(state: &mut GameState, beam: &mut Beam, beam_cfg: &BeamTrait, delta: f32) {
+    let collision = &state.config.collision;
+    let end = beam.end(beam_cfg);
+    let damage = beam_cfg.damage_per_second * beam.damage_multiplier * delta;
+    let half_width = beam_cfg.width / 2.0;
+
+    // Broadphase: circle around the segment
+    let mid = (beam.origin + end) / 2.0;
+    let reach =
+        beam_cfg.length / 2.0 + half_width + collision.enemy_radius.max(collision.ghost_radius);
+
+    let mut hit_pos = None;
+    let mut targets = Vec::new();
+
+    match beam.owner {
+        ProjectileOwner::Player | ProjectileOwner::Ghost => {
+            let radius = collision.enemy_radius + half_width;
+            for entry in state.spatial.query(mid, reach, EntityKind::Enemy) {
+                if segment_circle_collision(beam.origin, end, entry.pos, radius) {
+                    targets.push(DamageTarget::Enemy(entry.index));
+                    hit_pos.get_or_insert(entry.pos);
+                }
+            }
+        }
+
+        ProjectileOwner::Enemy => {
+            // Player (skip if i-frames active!)
+            let player_radius = collision.player_radius + half_width;
+            if state.player.i_frame_timer <= 0.0
+                && segment_circle_collision(beam.origin, end, state.player.pos, player_radius)
+            {
+                targets.push(DamageTarget::Player);
+                hit_pos = Some(state.player.pos);
+            }
+
+            let ghost_radius = collision.ghost_radius + half_width;
+            for entry in state.spatial.query(mid, reach, EntityKind::Ghost) {
+                if segment_circle_collision(beam.origin, end, entry.pos, ghost_radius) {
+                    targets.push(DamageTarget::Ghost(entry.index));
+                }
+            }
+        }
+    }
+
+    for target in targets {
+        let id = match target {
+            DamageTarget::Player => EntityId::PLAYER,
+            DamageTarget::Enemy(index) => state.enemies[index].id,
+            DamageTarget::Ghost(index) => state.ghosts[index].id,
+        };
+        let info = DamageInfo {
+            crit: beam.crit,
+            periodic: true, // Every tick - no crit rolls or status stacking
+            ..DamageInfo::new(damage, beam.weapon, beam.owner).with_direction(beam.direction)
+        };
+        let dealt = apply_damage(state, target, info);
+        if target == DamageTarget::Player {
+            beam.fx_damage += dealt;
+        }
+
+        // First contact: weapon statuses and the crit event, once per target
+        if dealt > 0.0 && !beam.touched.contains(&id) {
+            beam.touched.push(id);
+            let contact = DamageInfo::new(dealt, beam.weapon, beam.owner);
+            crate::game::status::apply_weapon_status(state, target, &contact);
+            if beam.crit {
+                let pos = crate::game::damage::target_pos(state, target);
+                let weapon = beam.weapon;
+                emit(
+                    state,
+                    GameEvent::CriticalHit {
+                        weapon,
+                        pos,
+                        damage: dealt,
+                    },
+                );
+            }
+        }
+    }
+
+    // Hit effects (throttled - a beam hits every tick)
+    let Some(pos) = hit_pos else {
+        return;
+    };
+    if beam.fx_timer > 0.0 {
+        return;
+    }
+    beam.fx_timer = BEAM_FX_INTERVAL;
+
+    match beam.owner {
+        ProjectileOwner::Player | ProjectileOwner::Ghost => {
+            let weapon = beam.weapon;
+            emit(state, GameEvent::EnemyHit { weapon, pos });
+        }
+        ProjectileOwner::Enemy => {
+            let damage = std::mem::take(&mut beam.fx_damage);
+            emit(state, GameEvent::PlayerHit { pos, damage });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enemy_sniper_beam(state: &mut GameState) -> BeamTrait {
+        let weapon = WeaponType::from_name("SniperLaser", &state.config.weapons).unwrap();
+        let target = state.player.pos;
+        let params = FireWeaponParams {
+            shooter_pos: target - Vec2::new(0.0, 300.0),
+            shooter: None,
+            owner: ProjectileOwner::Enemy,
+            weapon,
+            direction: FiringDirection::AtTarget(target),
+            damage_multiplier: 1.0,
+            level: 1,
+            modifiers: Default::default(),
+        };
+        crate::game::fire_weapon(params, state);
+        crate::game::rebuild_spatial(state);
+        weapon.config(&state.config.weapons).beam.clone().unwrap()
+    }
+
+    #[test]
+    fn test_warmup_then_damage() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let beam = enemy_sniper_beam(&mut state);
+        let health = state.player.stats.health;
+        assert_eq!(state.beams.len(), 1);
+        assert!(state.projectiles.is_empty());
+
+        // Telegraph only
+        update_beams(&mut state, beam.warmup * 0.5);
+        assert_eq!(state.player.stats.health, health);
+
+        update_beams(&mut state, beam.warmup * 0.5 + 0.1);
+        assert!(state.player.stats.health < health);
+        assert!(
+            state
+                .events
+                .iter()
+                .any(|event| matches!(event, GameEvent::PlayerHit { .. }))
+        );
+
+        // Expires after the firing window
+        update_beams(&mut state, beam.duration);
+        assert!(state.beams.is_empty());
+    }
+
+    #[test]
+    fn test_player_beam_stops_on_release() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let laser = WeaponType::from_name("Laser", &state.config.weapons).unwrap();
+        let slot = state
+            .player
+            .weapon
+            .iter()
+            .position(|&w| w == laser)
+            .unwrap();
+        crate::game::rebuild_spatial(&mut state);
+
+        crate::game::player_fire_weapon(&mut state, slot);
+        update_beams(&mut state, 0.01);
+        assert_eq!(state.beams.len(), 1);
+
+        // Still held
+        crate::game::player_fire_weapon(&mut state, slot);
+        update_beams(&mut state, 0.01);
+        assert_eq!(state.beams.len(), 1);
+
+        // Released
+        update_beams(&mut state, 0.01);
+        assert!(state.beams.is_empty());
+    }
+
+    #[test]
+    fn test_crit_and_status_once_per_beam() {
+        use crate::config::{Stacking, StatusKind, StatusTrait};
+
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let laser = WeaponType::from_name("Laser", &state.config.weapons).unwrap();
+        let cfg = &mut state.config.weapons[laser.0 as usize];
+        cfg.crit_chance = 1.0;
+        cfg.status = vec![StatusTrait {
+            kind: StatusKind::Slow,
+            duration: 5.0,
+            magnitude: 0.1,
+            stacking: Stacking::Stack,
+            max_stacks: 10,
+        }];
+        let slot = state
+            .player
+            .weapon
+            .iter()
+            .position(|&w| w == laser)
+            .unwrap();
+        state.player.pos = Position::new(400.0, 500.0);
+        let basic = EntityType::from_name("BasicFighter", &state.config.archetypes).unwrap();
+        let id = state.entity_ids.next();
+        let enemy = Enemy::from_entity_type(id, basic, Position::new(400.0, 300.0), &state.config);
+        state.enemies.push(enemy);
+        crate::game::rebuild_spatial(&mut state);
+
+        let health = state.enemies[0].stats.health;
+        let mut crits = 0;
+        for _ in 0..30 {
+            crate::game::player_fire_weapon(&mut state, slot);
+            update_beams(&mut state, 0.01);
+            crits += state
+                .events
+                .iter()
+                .filter(|event| matches!(event, GameEvent::CriticalHit { .. }))
+                .count();
+            state.events.clear();
+        }
+
+        assert!(state.enemies[0].stats.health < health);
+        assert_eq!(crits, 1);
+        assert_eq!(
+            state.enemies[0].status.total(StatusKind::Slow),
+            0.1 // One stack, not one per tick
+        );
+    }
+}
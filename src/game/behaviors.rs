@@ -0,0 +1,120 @@
+//! Lua enemy behaviors - archetypes with `behavior = "name"` steer and fire from script
+//!
+//! Once an enemy is off its entry path, the named global function (scripts/behaviors.lua)
+//! is called every tick with a read-only view and returns an intent; `update_enemies`
+//! applies it. All behavior calls in a tick share `lua_instruction_budget` instructions -
+//! enemies past it, and behaviors that error or return nil, use the built-in AI.
+
+use crate::models::*;
+use crate::scripting::{BehaviorIntent, BehaviorView, LuaScripting};
+
+/// Ask the enemy's Lua behavior what it wants this tick (None = built-in AI)
+pub fn behavior_intent(
+    scripting: &LuaScripting,
+    enemy: &Enemy,
+    archetypes: &[crate::config::ArchetypeConfig],
+    player_pos: Position,
+    time: f32,
+) -> Option<BehaviorIntent> {
+    let name = enemy
+        .entity_type
+        .archetype(archetypes)
+        .behavior
+        .as_deref()?;
+    if !matches!(enemy.movement_state, EnemyMovementState::FreeMovement) {
+        return None; // Entry paths always play out
+    }
+    if enemy.status.is_stunned() {
+        return None;
+    }
+
+    let view = BehaviorView {
+        id: enemy.id.0,
+        pos: enemy.pos,
+        hp: enemy.stats.health,
+        max_hp: enemy.stats.max_health,
+        player_pos,
+        time,
+        fire_ready: enemy.fire_timer <= 0.0,
+    };
+    scripting.call_behavior(name, &view)
+}
+
+/// The weapon an intent asked for, if the enemy carries it
+pub fn intent_weapon(
+    intent: &BehaviorIntent,
+    enemy: &Enemy,
+    weapons: &[crate::config::WeaponConfig],
+) -> Option<WeaponType> {
+    let weapon = WeaponType::from_name(intent.weapon.as_deref()?, weapons)?;
+    enemy.weapon.contains(&weapon).then_some(weapon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::update_enemies;
+
+    /// Gunship past its entry path, below the fire threshold, running `behavior`
+    fn scripted_gunship(state: &mut GameState, behavior: &str) {
+        let gunship = EntityType::from_name("Gunship", &state.config.archetypes).unwrap();
+        state.config.archetypes[gunship.0 as usize].behavior = Some(behavior.to_string());
+        let id = state.entity_ids.next();
+        let pos = Position::new(400.0, 300.0);
+        let mut enemy = Enemy::from_entity_type(id, gunship, pos, &state.config);
+        enemy.movement_state = EnemyMovementState::FreeMovement;
+        enemy.fire_timer = 0.0;
+        state.enemies.push(enemy);
+    }
+
+    #[test]
+    fn test_intent_steers_and_fires() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state
+            .wave_manager
+            .scripting
+            .lua()
+            .load(
+                r#"
+                function test_strafe(enemy)
+                    return { vx = 100, vy = 0, fire = enemy.fire_ready, aim_x = enemy.x, aim_y = 0 }
+                end
+                "#,
+            )
+            .exec()
+            .unwrap();
+        scripted_gunship(&mut state, "test_strafe");
+
+        update_enemies(&mut state, 0.5);
+        assert_eq!(state.enemies[0].pos, Position::new(450.0, 300.0));
+        assert!(!state.projectiles.is_empty());
+        assert!(state.projectiles.iter().all(|p| p.velocity.y < 0.0)); // Aimed up, not at the player
+
+        // Steering into the wall stops at the arena edge
+        for _ in 0..10 {
+            update_enemies(&mut state, 0.5);
+        }
+        assert_eq!(state.enemies[0].pos, Position::new(800.0, 300.0));
+    }
+
+    #[test]
+    fn test_runaway_script_falls_back_to_builtin_ai() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.config.enemy_behavior.lua_instruction_budget = 50_000;
+        state
+            .wave_manager
+            .scripting
+            .lua()
+            .load("function test_spin(enemy) while true do end end")
+            .exec()
+            .unwrap();
+        scripted_gunship(&mut state, "test_spin");
+        let start = state.enemies[0].pos;
+
+        // Out of budget every tick - the built-in AI keeps it descending
+        update_enemies(&mut state, 0.5);
+        update_enemies(&mut state, 0.5);
+        assert_eq!(state.enemies[0].pos.x, start.x);
+        assert!(state.enemies[0].pos.y > start.y);
+    }
+}
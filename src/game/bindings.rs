@@ -0,0 +1,247 @@
+//! Keyboard -> Action mapping (built from `[bindings]` in config.toml)
+
+use crate::config::{ArchetypeConfig, BindingsConfig};
+use crate::game::input::{Action, InputState};
+use crate::models::{EntityType, GhostFormation};
+use macroquad::input::{KeyCode, is_key_down, is_key_pressed};
+
+/// Keys that can appear in `[bindings]` (matched by their KeyCode name)
+const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::Space,
+    KeyCode::Apostrophe,
+    KeyCode::Comma,
+    KeyCode::Minus,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Semicolon,
+    KeyCode::Equal,
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::LeftBracket,
+    KeyCode::Backslash,
+    KeyCode::RightBracket,
+    KeyCode::GraveAccent,
+    KeyCode::Escape,
+    KeyCode::Enter,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::Insert,
+    KeyCode::Delete,
+    KeyCode::Right,
+    KeyCode::Left,
+    KeyCode::Down,
+    KeyCode::Up,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Kp0,
+    KeyCode::Kp1,
+    KeyCode::Kp2,
+    KeyCode::Kp3,
+    KeyCode::Kp4,
+    KeyCode::Kp5,
+    KeyCode::Kp6,
+    KeyCode::Kp7,
+    KeyCode::Kp8,
+    KeyCode::Kp9,
+    KeyCode::KpDecimal,
+    KeyCode::KpDivide,
+    KeyCode::KpMultiply,
+    KeyCode::KpSubtract,
+    KeyCode::KpAdd,
+    KeyCode::KpEnter,
+    KeyCode::LeftShift,
+    KeyCode::LeftControl,
+    KeyCode::LeftAlt,
+    KeyCode::RightShift,
+    KeyCode::RightControl,
+    KeyCode::RightAlt,
+];
+
+/// Keys the game loop handles itself, outside `[bindings]` (see main.rs)
+pub const RESERVED_KEYS: &[(KeyCode, &str)] = &[
+    (KeyCode::R, "config reload"),
+    (KeyCode::F9, "replay save"),
+    (KeyCode::F10, "AI debug overlay"),
+];
+
+/// Parse a key name ("W", "up", "LeftShift", "F1", "Key1" or just "1")
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let name = name.trim();
+
+    // Bare digits are accepted as a shorthand for the number row
+    let name = match name {
+        digit if digit.len() == 1 && digit.chars().all(|c| c.is_ascii_digit()) => {
+            format!("Key{digit}")
+        }
+        other => other.to_string(),
+    };
+
+    BINDABLE_KEYS
+        .iter()
+        .copied()
+        .find(|key| format!("{key:?}").eq_ignore_ascii_case(&name))
+}
+
+/// Resolved key table (rebuilt whenever the config is reloaded)
+#[derive(Clone, Debug, Default)]
+pub struct Bindings {
+    keys: Vec<(KeyCode, Action)>,
+}
+
+impl Bindings {
+    /// Resolve key names from config, skipping (and reporting) unknown ones
+    pub fn from_config(config: &BindingsConfig, archetypes: &[ArchetypeConfig]) -> Self {
+        let mut bindings = Bindings::default();
+
+        bindings.bind(&config.move_up, Action::MoveUp);
+        bindings.bind(&config.move_down, Action::MoveDown);
+        bindings.bind(&config.move_left, Action::MoveLeft);
+        bindings.bind(&config.move_right, Action::MoveRight);
+        bindings.bind(&config.dash, Action::Dash);
+        for (slot, keys) in config.fire_slots.iter().enumerate() {
+            bindings.bind(keys, Action::FireSlot(slot));
+        }
+        bindings.bind(&config.parry, Action::Parry);
+        bindings.bind(&config.cancel_summon, Action::CancelSummon);
+        bindings.bind(&config.summon_formation, Action::SummonFormation);
+        bindings.bind(
+            &config.formation_line,
+            Action::SetFormation(GhostFormation::Line),
+        );
+        bindings.bind(
+            &config.formation_circle,
+            Action::SetFormation(GhostFormation::Circle),
+        );
+        bindings.bind(
+            &config.formation_v,
+            Action::SetFormation(GhostFormation::VShape),
+        );
+
+        for (entity_name, keys) in &config.summon {
+            match EntityType::from_name(entity_name, archetypes) {
+                Some(entity_type) => bindings.bind(keys, Action::SummonType(entity_type)),
+                None => println!("✗ Unknown archetype in [bindings.summon]: {entity_name}"),
+            }
+        }
+
+        bindings
+    }
+
+    fn bind(&mut self, key_names: &[String], action: Action) {
+        for name in key_names {
+            let reserved = RESERVED_KEYS
+                .iter()
+                .find(|(key, _)| format!("{key:?}").eq_ignore_ascii_case(name.trim()));
+            if let Some((_, purpose)) = reserved {
+                println!("✗ Key '{name}' is reserved for {purpose} - not bound to {action:?}");
+                continue;
+            }
+            match parse_key(name) {
+                Some(key) => self.keys.push((key, action)),
+                None => println!("✗ Unknown key '{name}' bound to {action:?}"),
+            }
+        }
+    }
+
+    /// Read the current keyboard state as actions (requires a window)
+    pub fn read_keyboard(&self) -> InputState {
+        let mut input = InputState::default();
+
+        for &(key, action) in &self.keys {
+            if is_key_down(key) {
+                input.held.insert(action);
+            }
+            if is_key_pressed(key) {
+                input.pressed.insert(action);
+            }
+        }
+
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_names() {
+        assert_eq!(parse_key("W"), Some(KeyCode::W));
+        assert_eq!(parse_key("leftshift"), Some(KeyCode::LeftShift));
+        assert_eq!(parse_key("F6"), Some(KeyCode::F6));
+        assert_eq!(parse_key("Key1"), Some(KeyCode::Key1));
+        assert_eq!(parse_key("1"), Some(KeyCode::Key1));
+        assert_eq!(parse_key("NotAKey"), None);
+    }
+
+    #[test]
+    fn test_reserved_keys_are_not_bound() {
+        let mut config = crate::defaults::default_config();
+        config.bindings.parry = vec!["R".to_string(), "X".to_string()];
+        config.bindings.dash = vec!["f9".to_string(), "F10".to_string()];
+        let bindings = Bindings::from_config(&config.bindings, &config.archetypes);
+
+        assert!(bindings.keys.contains(&(KeyCode::X, Action::Parry)));
+        for (reserved, _) in RESERVED_KEYS {
+            assert!(bindings.keys.iter().all(|(key, _)| key != reserved));
+        }
+    }
+
+    #[test]
+    fn test_default_bindings_resolve() {
+        let config = crate::defaults::default_config();
+        let bindings = Bindings::from_config(&config.bindings, &config.archetypes);
+
+        assert!(bindings.keys.contains(&(KeyCode::H, Action::FireSlot(0))));
+        assert!(
+            bindings
+                .keys
+                .contains(&(KeyCode::F1, Action::SummonType(EntityType(0))))
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Bombs - explosives that go off on a fuse, on contact or when the player presses fire again
+//!
+//! Every projectile with an `explosion_radius` detonates here instead of in
+//! `check_projectile_collisions`. Weapons with a `[weapons.bomb]` table add fuses,
+//! remote detonation, damage falloff and projectile clearing.
+
+use crate::config::{BombTrait, WeaponConfig};
+use crate::game::damage::{DamageInfo, DamageTarget, apply_damage, target_pos};
+use crate::game::events::{GameEvent, emit};
+use crate::game::spatial::EntityKind;
+use crate::game::utils::circle_collision;
+use crate::game::weapons::{
+    FireWeaponParams, FiringDirection, fire_weapon, player_modifiers, use_weapon_slot,
+};
+use crate::models::*;
+use macroquad::prelude::*;
+
+/// Expanding ring left behind by a detonation (cosmetic)
+#[derive(Clone, Copy)]
+pub struct Shockwave {
+    pub pos: Position,
+    pub radius: f32, // Final radius (the blast radius)
+    pub elapsed: f32,
+    pub duration: f32,
+    pub color: Color,
+}
+
+/// One detonation (copied out of the projectile so the lists can be mutated)
+struct Blast {
+    pos: Position,
+    radius: f32,
+    damage: f32,
+    owner: ProjectileOwner,
+    weapon: WeaponType,
+}
+
+pub fn bomb_trait(weapon: WeaponType, weapons: &[WeaponConfig]) -> Option<&BombTrait> {
+    weapon.config(weapons).bomb.as_ref()
+}
+
+/// Bomb weapon in a player slot (None for regular weapons and empty slots)
+pub fn bomb_slot_weapon(state: &GameState, slot: usize) -> Option<WeaponType> {
+    let weapon = *state.player.weapon.get(slot)?;
+    bomb_trait(weapon, &state.config.weapons).map(|_| weapon)
+}
+
+/// Bomb slot pressed: detonate the player's live bombs (remote) or throw a new one
+pub fn player_bomb_press(state: &mut GameState, slot: usize) {
+    let Some(weapon) = bomb_slot_weapon(state, slot) else {
+        return;
+    };
+
+    let remote = bomb_trait(weapon, &state.config.weapons).is_some_and(|bomb| bomb.remote);
+    if remote {
+        let mut detonated = false;
+        for projectile in &mut state.projectiles {
+            if projectile.owner == ProjectileOwner::Player && projectile.weapon_type == weapon {
+                projectile.detonate = true;
+                detonated = true;
+            }
+        }
+        if detonated {
+            return;
+        }
+    }
+
+    // Slot cooldown and ammo (doesn't block the other slots)
+    if !use_weapon_slot(state, slot) {
+        return;
+    }
+
+    fire_weapon(
+        FireWeaponParams {
+            shooter_pos: state.player.pos,
+            shooter: Some(EntityId::PLAYER),
+            owner: ProjectileOwner::Player,
+            weapon,
+            direction: FiringDirection::Up,
+            damage_multiplier: 1.0,
+            level: state.player.slots[slot].level,
+            modifiers: player_modifiers(state, weapon),
+        },
+        state,
+    );
+}
+
+/// Detonate explosives whose fuse ran out, that touched a target or that were
+/// remote-detonated (needs a fresh spatial grid)
+pub fn update_bombs(state: &mut GameState) {
+    let weapons = &state.config.weapons;
+    let mut detonations = Vec::new();
+
+    for (idx, projectile) in state.projectiles.iter().enumerate() {
+        if projectile.explosion_radius <= 0.0 {
+            continue;
+        }
+
+        let bomb = bomb_trait(projectile.weapon_type, weapons);
+        let fuse_out =
+            bomb.is_some_and(|b| b.fuse_time > 0.0 && projectile.lifetime >= b.fuse_time);
+        let contact = bomb.is_none_or(|b| b.contact) && touches_target(state, projectile);
+
+        if projectile.detonate || fuse_out || contact {
+            detonations.push(idx);
+        }
+    }
+
+    if detonations.is_empty() {
+        return;
+    }
+
+    let mut to_remove = detonations.clone();
+    for idx in detonations {
+        let projectile = &state.projectiles[idx];
+        let blast = Blast {
+            pos: projectile.pos,
+            radius: projectile.explosion_radius,
+            damage: projectile.damage,
+            owner: projectile.owner,
+            weapon: projectile.weapon_type,
+        };
+        to_remove.extend(detonate(state, blast));
+    }
+
+    // Remove bombs and cleared projectiles (reverse order to avoid index issues)
+    to_remove.sort_unstable();
+    to_remove.dedup();
+    for &idx in to_remove.iter().rev() {
+        state.projectiles.remove(idx);
+    }
+}
+
+/// Is the explosive touching something it is meant to hit?
+fn touches_target(state: &GameState, projectile: &Projectile) -> bool {
+    let cfg = &state.config.collision;
+    let projectile_radius = projectile.hit_radius(cfg.projectile_radius);
+
+    let touches = |kind: EntityKind, radius: f32| {
+        state
+            .spatial
+            .query(projectile.pos, projectile_radius + radius, kind)
+            .iter()
+            .any(|entry| circle_collision(projectile.pos, entry.pos, projectile_radius, radius))
+    };
+
+    match projectile.owner {
+        ProjectileOwner::Player | ProjectileOwner::Ghost => {
+            touches(EntityKind::Enemy, cfg.enemy_radius)
+        }
+        ProjectileOwner::Enemy => {
+            let touches_player = state.player.i_frame_timer <= 0.0
+                && circle_collision(
+                    projectile.pos,
+                    state.player.pos,
+                    projectile_radius,
+                    cfg.player_radius,
+                );
+            touches_player || touches(EntityKind::Ghost, cfg.ghost_radius)
+        }
+    }
+}
+
+/// Apply radial damage (falling off towards the edge), returns projectiles cleared by the blast
+fn detonate(state: &mut GameState, blast: Blast) -> Vec<usize> {
+    let bomb = bomb_trait(blast.weapon, &state.config.weapons);
+    let edge_damage = bomb.map_or(1.0, |b| b.edge_damage);
+    let clears_projectiles = bomb.is_some_and(|b| b.clears_projectiles);
+
+    // Full damage at the center, edge_damage * damage at the rim
+    let damage_at = |distance: f32| {
+        let t = (distance / blast.radius).min(1.0);
+        blast.damage * (1.0 - (1.0 - edge_damage) * t)
+    };
+
+    let mut cleared = Vec::new();
+    let mut hits = Vec::new(); // (target, damage)
+
+    match blast.owner {
+        ProjectileOwner::Player | ProjectileOwner::Ghost => {
+            for entry in state
+                .spatial
+                .query(blast.pos, blast.radius, EntityKind::Enemy)
+            {
+                let distance = state.enemies[entry.index].pos.distance(blast.pos);
+                if distance <= blast.radius {
+                    hits.push((DamageTarget::Enemy(entry.index), damage_at(distance)));
+                }
+            }
+
+            if clears_projectiles {
+                for (idx, projectile) in state.projectiles.iter().enumerate() {
+                    if projectile.owner == ProjectileOwner::Enemy
+                        && projectile.pos.distance(blast.pos) <= blast.radius
+                    {
+                        cleared.push(idx);
+                    }
+                }
+            }
+        }
+
+        ProjectileOwner::Enemy => {
+            // Player (skip if i-frames active!)
+            let distance = state.player.pos.distance(blast.pos);
+            if state.player.i_frame_timer <= 0.0 && distance <= blast.radius {
+                hits.push((DamageTarget::Player, damage_at(distance)));
+            }
+
+            for entry in state
+                .spatial
+                .query(blast.pos, blast.radius, EntityKind::Ghost)
+            {
+                let distance = state.ghosts[entry.index].pos.distance(blast.pos);
+                if distance <= blast.radius {
+                    hits.push((DamageTarget::Ghost(entry.index), damage_at(distance)));
+                }
+            }
+        }
+    }
+
+    for (target, damage) in hits {
+        // Pushes outward from the blast (shields only stop it from the front)
+        let outward = target_pos(state, target) - blast.pos;
+        let info = DamageInfo::new(damage, blast.weapon, blast.owner).with_direction(outward);
+        let damage = apply_damage(state, target, info);
+        if target == DamageTarget::Player {
+            let pos = state.player.pos;
+            emit(state, GameEvent::PlayerHit { pos, damage });
+        }
+    }
+
+    emit(
+        state,
+        GameEvent::BombDetonated {
+            weapon: blast.weapon,
+            pos: blast.pos,
+            radius: blast.radius,
+            owner: blast.owner,
+        },
+    );
+
+    cleared
+}
+
+/// Event subscriber - shockwave ring for every detonation
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    if let GameEvent::BombDetonated {
+        weapon,
+        pos,
+        radius,
+        ..
+    } = *event
+    {
+        let config = weapon.config(&state.config.weapons);
+        let [r, g, b] = config.hit_color;
+        let duration = config.bomb.as_ref().map_or(0.4, |b| b.shockwave_duration);
+
+        state.shockwaves.push(Shockwave {
+            pos,
+            radius,
+            elapsed: 0.0,
+            duration,
+            color: Color::new(r, g, b, 1.0),
+        });
+    }
+}
+
+/// Expand and fade shockwave rings
+pub fn update_shockwaves(state: &mut GameState, delta: f32) {
+    for wave in &mut state.shockwaves {
+        wave.elapsed += delta;
+    }
+    state.shockwaves.retain(|wave| wave.elapsed < wave.duration);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// State with one bomb thrown (returns the bomb slot)
+    fn state_with_bomb() -> (GameState, usize) {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let slot = (0..state.player.weapon.len())
+            .find(|&slot| bomb_slot_weapon(&state, slot).is_some())
+            .unwrap();
+        crate::game::rebuild_spatial(&mut state);
+        player_bomb_press(&mut state, slot);
+        (state, slot)
+    }
+
+    #[test]
+    fn test_fuse_detonates_and_press_uses_ammo() {
+        let (mut state, slot) = state_with_bomb();
+        let weapon = state.player.weapon[slot].config(&state.config.weapons);
+        let (fuse_time, max_ammo) = (
+            weapon.bomb.as_ref().unwrap().fuse_time,
+            weapon.ammo.unwrap().max,
+        );
+        assert_eq!(state.projectiles.len(), 1);
+        assert_eq!(state.player.slots[slot].ammo, max_ammo - 1);
+
+        state.projectiles[0].lifetime = fuse_time;
+        update_bombs(&mut state);
+
+        assert!(state.projectiles.is_empty());
+        assert!(matches!(
+            state.events.as_slice(),
+            [GameEvent::BombDetonated { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_remote_detonation_with_falloff() {
+        let (mut state, slot) = state_with_bomb();
+        let pos = state.projectiles[0].pos;
+        let radius = state.projectiles[0].explosion_radius;
+
+        // One enemy at the center, one near the edge (neither touching the bomb)
+        for offset in [Vec2::new(0.0, -30.0), Vec2::new(0.0, -(radius - 1.0))] {
+            let id = state.entity_ids.next();
+            let enemy = Enemy::from_entity_type(id, EntityType(0), pos + offset, &state.config);
+            state.enemies.push(enemy);
+        }
+        state.projectiles[0].pos = pos;
+        crate::game::rebuild_spatial(&mut state);
+
+        // Second press detonates instead of throwing another bomb
+        player_bomb_press(&mut state, slot);
+        update_bombs(&mut state);
+
+        assert!(state.projectiles.is_empty());
+        let near = state.enemies[0].stats.max_health - state.enemies[0].stats.health;
+        let far = state.enemies[1].stats.max_health - state.enemies[1].stats.health;
+        assert!(near > far && far > 0.0, "near {near}, far {far}");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::game::events::{GameEvent, emit};
 use crate::models::*;
 
 /// Cancel all summoned ghosts - they return to available queue
@@ -23,6 +24,5 @@ pub fn cancel_summon(state: &mut GameState) {
         }
     }
 
-    println!("✔ Cancel Deployed, {ghost_count} ghost(s)!");
-    // TODO: Play despawn sound/effect
+    emit(state, GameEvent::GhostsDismissed { count: ghost_count });
 }
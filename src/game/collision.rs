@@ -1,139 +1,110 @@
-use crate::game::particles::spawn_weapon_particles;
-use crate::game::screen_shake::{shake_on_player_hit, shake_on_weapon_hit};
+use crate::config::Modifiers;
+use crate::game::damage::{DamageInfo, DamageTarget, apply_damage};
+use crate::game::events::{GameEvent, emit};
+use crate::game::spatial::EntityKind;
 use crate::game::utils::circle_collision;
 use crate::models::*;
 
 /// Check all projectile collisions and apply damage
+/// Candidates come from the spatial grid (rebuilt after movement, so indices are valid here)
 pub fn check_projectile_collisions(state: &mut GameState) {
     let mut projectiles_to_remove = Vec::new();
     let collision_cfg = &state.config.collision;
-    let mut player_was_hit = false; // Track if player took damage
-    let mut player_hit_position: Option<Position> = None; // Track hit position for particles
+    let mut player_damage = 0.0; // Total damage taken this tick
+    let mut player_hit_position: Option<Position> = None; // Track hit position for the event
     let mut weapon_hits: Vec<(WeaponType, Position)> = Vec::new(); // Track weapon hits with positions
+    let mut hits: Vec<(usize, DamageTarget)> = Vec::new(); // (projectile, target), damage dealt below
+    let mut enemy_hits: Vec<(usize, usize)> = Vec::new(); // (projectile, enemy) for on-hit affixes
 
     for (proj_idx, projectile) in state.projectiles.iter().enumerate() {
+        // Explosives detonate in bombs::update_bombs (fuse, contact, remote)
+        if projectile.explosion_radius > 0.0 {
+            continue;
+        }
+        let projectile_radius = projectile.hit_radius(collision_cfg.projectile_radius);
+
         match projectile.owner {
             ProjectileOwner::Player | ProjectileOwner::Ghost => {
-                // Check if this is a bomb (AOE explosion)
-                if projectile.explosion_radius > 0.0 {
-                    let mut hit_any_enemy = false;
-
-                    // Check all enemies within explosion radius
-                    for enemy in &mut state.enemies {
-                        let distance = ((projectile.pos.x - enemy.pos.x).powi(2)
-                            + (projectile.pos.y - enemy.pos.y).powi(2))
-                        .sqrt();
-
-                        if distance <= projectile.explosion_radius {
-                            enemy.stats.health -= projectile.damage;
-                            enemy.anim.hit_flash_timer = state.config.animations.hit_flash_duration; // Flash on hit!
-                            hit_any_enemy = true;
-                        }
-                    }
-
-                    // Bomb explodes if it hits ANY enemy (AOE damage applied to all in radius)
-                    if hit_any_enemy {
-                        weapon_hits.push((projectile.weapon_type, projectile.pos)); // Track bomb hit
-                        projectiles_to_remove.push(proj_idx);
+                // Standard projectile collision (Bullet, Missile, Plasma)
+                // One enemy per tick - pierce carries the shot on to the next one
+                for entry in state.spatial.query(
+                    projectile.pos,
+                    projectile_radius + collision_cfg.enemy_radius,
+                    EntityKind::Enemy,
+                ) {
+                    if projectile.last_hit == Some(entry.id) {
+                        continue; // Still passing through the enemy it just hit
                     }
-                } else {
-                    // Standard projectile collision (Bullet, Laser, Missile, Plasma)
-                    for enemy in &mut state.enemies {
-                        if circle_collision(
-                            projectile.pos,
-                            enemy.pos,
-                            collision_cfg.projectile_radius,
-                            collision_cfg.enemy_radius,
-                        ) {
-                            enemy.stats.health -= projectile.damage;
-                            enemy.anim.hit_flash_timer = state.config.animations.hit_flash_duration; // Flash on hit!
-                            weapon_hits.push((projectile.weapon_type, projectile.pos)); // Track weapon hit
-
-                            // Only mark for removal if NOT piercing (lasers pierce through)
-                            if !projectile.piercing {
-                                projectiles_to_remove.push(proj_idx);
-                                break;
-                            }
-                            // Piercing projectiles continue after hit (no break)
-                        }
+                    if circle_collision(
+                        projectile.pos,
+                        state.enemies[entry.index].pos,
+                        projectile_radius,
+                        collision_cfg.enemy_radius,
+                    ) {
+                        hits.push((proj_idx, DamageTarget::Enemy(entry.index)));
+                        weapon_hits.push((projectile.weapon_type, projectile.pos)); // Track weapon hit
+                        enemy_hits.push((proj_idx, entry.index));
+                        break;
                     }
                 }
             }
 
             ProjectileOwner::Enemy => {
-                // Enemy bombs can also have AOE (if configured)
-                if projectile.explosion_radius > 0.0 {
-                    let mut hit_player_or_ghost = false;
-
-                    // Check player (skip if i-frames active!)
-                    let distance_to_player = ((projectile.pos.x - state.player.pos.x).powi(2)
-                        + (projectile.pos.y - state.player.pos.y).powi(2))
-                    .sqrt();
-
-                    if state.player.i_frame_timer <= 0.0
-                        && distance_to_player <= projectile.explosion_radius
-                    {
-                        state.player.stats.health -= projectile.damage;
-                        state.player.hit_flash_timer = state.config.animations.hit_flash_duration; // Flash on AOE hit!
-                        player_hit_position = Some(state.player.pos); // Track for particles
-                        player_was_hit = true; // Mark for shake trigger
-                        hit_player_or_ghost = true;
-                    }
-
-                    // Check ghosts
-                    for ghost in &mut state.ghosts {
-                        let distance = ((projectile.pos.x - ghost.pos.x).powi(2)
-                            + (projectile.pos.y - ghost.pos.y).powi(2))
-                        .sqrt();
-
-                        if distance <= projectile.explosion_radius {
-                            ghost.stats.health -= projectile.damage;
-                            ghost.anim.hit_flash_timer = state.config.animations.hit_flash_duration; // Flash on hit!
-                            hit_player_or_ghost = true;
-                        }
-                    }
+                // Check collision with player (skip if i-frames active!)
+                if state.player.i_frame_timer <= 0.0
+                    && circle_collision(
+                        projectile.pos,
+                        state.player.pos,
+                        projectile_radius,
+                        collision_cfg.player_radius,
+                    )
+                {
+                    hits.push((proj_idx, DamageTarget::Player));
+                    player_hit_position = Some(state.player.pos); // Save position for particle spawn
+                    // Enemy projectiles never pierce
+                    projectiles_to_remove.push(proj_idx);
+                }
 
-                    if hit_player_or_ghost {
-                        projectiles_to_remove.push(proj_idx);
-                    }
-                } else {
-                    // Standard enemy projectile collision
-                    // Check collision with player (skip if i-frames active!)
-                    if state.player.i_frame_timer <= 0.0
-                        && circle_collision(
-                            projectile.pos,
-                            state.player.pos,
-                            collision_cfg.projectile_radius,
-                            collision_cfg.player_radius,
-                        )
-                    {
-                        state.player.stats.health -= projectile.damage;
-                        state.player.hit_flash_timer = state.config.animations.hit_flash_duration; // Flash on hit!
-                        player_hit_position = Some(state.player.pos); // Save position for particle spawn
-                        player_was_hit = true; // Mark for shake trigger
-                        // Enemy projectiles never pierce
+                // Check collision with ghosts
+                for entry in state.spatial.query(
+                    projectile.pos,
+                    projectile_radius + collision_cfg.ghost_radius,
+                    EntityKind::Ghost,
+                ) {
+                    if circle_collision(
+                        projectile.pos,
+                        state.ghosts[entry.index].pos,
+                        projectile_radius,
+                        collision_cfg.ghost_radius,
+                    ) {
+                        hits.push((proj_idx, DamageTarget::Ghost(entry.index)));
                         projectiles_to_remove.push(proj_idx);
-                    }
-
-                    // Check collision with ghosts
-                    for ghost in &mut state.ghosts {
-                        if circle_collision(
-                            projectile.pos,
-                            ghost.pos,
-                            collision_cfg.projectile_radius,
-                            collision_cfg.ghost_radius,
-                        ) {
-                            ghost.stats.health -= projectile.damage;
-                            ghost.anim.hit_flash_timer = state.config.animations.hit_flash_duration; // Flash on hit!
-                            projectiles_to_remove.push(proj_idx);
-                            break;
-                        }
+                        break;
                     }
                 }
             }
         }
     }
 
+    // Deal the damage (crits, resistances, armor - see damage.rs)
+    for (proj_idx, target) in hits {
+        let projectile = &state.projectiles[proj_idx];
+        let info = DamageInfo::new(projectile.damage, projectile.weapon_type, projectile.owner)
+            .with_direction(projectile.velocity);
+        let dealt = apply_damage(state, target, info);
+        if target == DamageTarget::Player {
+            player_damage += dealt;
+        }
+    }
+
+    // Chain, split and pierce (spent projectiles are removed below)
+    let mut split_shots = Vec::new();
+    for (proj_idx, enemy_idx) in enemy_hits {
+        if !resolve_on_hit(state, proj_idx, enemy_idx, &mut split_shots) {
+            projectiles_to_remove.push(proj_idx);
+        }
+    }
+
     // Remove hit projectiles (reverse order to avoid index issues)
     projectiles_to_remove.sort_unstable();
     projectiles_to_remove.dedup(); // Remove duplicates (in case same projectile marked multiple times)
@@ -143,28 +114,249 @@ pub fn check_projectile_collisions(state: &mut GameState) {
         }
     }
 
-    // Trigger screen shake if player was hit
-    if player_was_hit {
-        shake_on_player_hit(state);
+    state.projectiles.extend(split_shots);
+
+    // Effects (shake, particles) subscribe to these
+    if let Some(pos) = player_hit_position {
+        emit(
+            state,
+            GameEvent::PlayerHit {
+                pos,
+                damage: player_damage,
+            },
+        );
     }
 
-    // Spawn player hit particles if player was damaged
-    if let Some(hit_pos) = player_hit_position {
-        crate::game::particles::spawn_player_hit_effect(state, hit_pos);
+    // Only the strongest hit of the tick gets an impact event
+    // (strongest = biggest shake_intensity)
+    let weapons = &state.config.weapons;
+    if let Some(&(weapon, pos)) = weapon_hits.iter().max_by(|(a, _), (b, _)| {
+        let a = a.config(weapons).shake_intensity;
+        let b = b.config(weapons).shake_intensity;
+        a.total_cmp(&b)
+    }) {
+        emit(state, GameEvent::EnemyHit { weapon, pos });
     }
+}
 
-    // Trigger weapon-specific shake and particles for each hit
-    if let Some(&(strongest_weapon, hit_pos)) = weapon_hits.iter().max_by_key(|(w, _)| {
-        // Priority order: Bombs > Missile > Laser > Plasma > Bullet
-        match w {
-            WeaponType::Bombs => 5,
-            WeaponType::Missile => 4,
-            WeaponType::Laser => 3,
-            WeaponType::Plasma => 2,
-            WeaponType::Bullet => 1,
+/// Apply a projectile's on-hit affixes after it hit an enemy
+/// (returns false once the projectile is used up)
+fn resolve_on_hit(
+    state: &mut GameState,
+    proj_idx: usize,
+    enemy_idx: usize,
+    split_shots: &mut Vec<Projectile>,
+) -> bool {
+    let enemy_id = state.enemies[enemy_idx].id;
+    let projectile = &state.projectiles[proj_idx];
+    let modifiers = projectile.modifiers;
+
+    if modifiers.chain > 0 {
+        let (damage, weapon, owner) = (projectile.damage, projectile.weapon_type, projectile.owner);
+        chain_damage(state, enemy_idx, modifiers.chain, damage, weapon, owner);
+    }
+
+    // Sub-projectiles fan out around the parent's heading (no affixes of their own)
+    if modifiers.split > 0 {
+        let cfg = &state.config.modifiers;
+        let projectile = &state.projectiles[proj_idx];
+        let spread = cfg.split_spread_angle.to_radians();
+        let center = (modifiers.split - 1) as f32 / 2.0;
+
+        for i in 0..modifiers.split {
+            let angle = (i as f32 - center) * spread;
+            split_shots.push(Projectile {
+                prev_pos: projectile.pos,
+                velocity: Position::from_angle(angle).rotate(projectile.velocity),
+                damage: projectile.damage * cfg.split_damage,
+                modifiers: Modifiers::default(),
+                homing: None,
+                target: None,
+                lifetime: 0.0,
+                trail_timer: 0.0,
+                last_hit: Some(enemy_id), // Don't hit the enemy that split it
+                ..*projectile
+            });
         }
-    }) {
-        shake_on_weapon_hit(state, strongest_weapon);
-        spawn_weapon_particles(state, hit_pos, strongest_weapon);
+    }
+
+    let projectile = &mut state.projectiles[proj_idx];
+    if projectile.modifiers.pierce == 0 {
+        return false;
+    }
+    projectile.modifiers.pierce -= 1;
+    projectile.last_hit = Some(enemy_id);
+    true
+}
+
+/// Jump damage from the hit enemy to the nearest enemy not hit yet, `jumps` times
+fn chain_damage(
+    state: &mut GameState,
+    enemy_idx: usize,
+    jumps: u32,
+    mut damage: f32,
+    weapon: WeaponType,
+    owner: ProjectileOwner,
+) {
+    let range = state.config.modifiers.chain_range;
+    let falloff = state.config.modifiers.chain_damage;
+
+    let mut chained = vec![state.enemies[enemy_idx].id];
+    let mut from = state.enemies[enemy_idx].pos;
+
+    for _ in 0..jumps {
+        let next = state
+            .spatial
+            .query(from, range, EntityKind::Enemy)
+            .into_iter()
+            .filter(|entry| !chained.contains(&entry.id))
+            .filter(|entry| state.enemies[entry.index].stats.health > 0.0)
+            .map(|entry| (entry, state.enemies[entry.index].pos.distance(from)))
+            .filter(|&(_, distance)| distance <= range)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((entry, _)) = next else {
+            break; // Nothing left in range
+        };
+
+        damage *= falloff;
+        let to = state.enemies[entry.index].pos;
+        let info = DamageInfo::new(damage, weapon, owner).with_direction(to - from);
+        apply_damage(state, DamageTarget::Enemy(entry.index), info);
+
+        emit(state, GameEvent::ChainJump { weapon, from, to });
+        chained.push(entry.id);
+        from = to;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{FireWeaponParams, FiringDirection, fire_weapon, rebuild_spatial};
+
+    fn spawn_enemy(state: &mut GameState, pos: Position) {
+        let basic = EntityType::from_name("BasicFighter", &state.config.archetypes).unwrap();
+        let id = state.entity_ids.next();
+        state
+            .enemies
+            .push(Enemy::from_entity_type(id, basic, pos, &state.config));
+    }
+
+    /// Player bullet with `modifiers`, fired up from `pos` (no crits)
+    fn shoot(state: &mut GameState, pos: Position, modifiers: Modifiers) {
+        let weapon = WeaponType::from_name("Bullet", &state.config.weapons).unwrap();
+        state.config.weapons[weapon.0 as usize].crit_chance = 0.0;
+        let params = FireWeaponParams {
+            shooter_pos: pos,
+            shooter: None,
+            owner: ProjectileOwner::Player,
+            weapon,
+            direction: FiringDirection::Up,
+            damage_multiplier: 1.0,
+            level: 1,
+            modifiers,
+        };
+        fire_weapon(params, state);
+    }
+
+    /// Move the first projectile onto `pos` and run one collision pass
+    fn hit_at(state: &mut GameState, pos: Position) {
+        state.projectiles[0].pos = pos;
+        rebuild_spatial(state);
+        check_projectile_collisions(state);
+    }
+
+    fn health(state: &GameState) -> Vec<f32> {
+        state.enemies.iter().map(|e| e.stats.health).collect()
+    }
+
+    #[test]
+    fn test_pierce_stops_after_n_hits() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let targets = [100.0, 200.0, 300.0].map(|y| Position::new(400.0, y));
+        for pos in targets {
+            spawn_enemy(&mut state, pos);
+        }
+        let full = health(&state)[0];
+        let pierce = Modifiers {
+            pierce: 2,
+            ..Default::default()
+        };
+        shoot(&mut state, Position::new(400.0, 500.0), pierce);
+
+        hit_at(&mut state, targets[2]);
+        hit_at(&mut state, targets[2]); // Still inside it: not hit twice
+        assert_eq!(state.projectiles[0].modifiers.pierce, 1);
+
+        hit_at(&mut state, targets[1]);
+        assert_eq!(state.projectiles[0].modifiers.pierce, 0);
+
+        hit_at(&mut state, targets[0]);
+        assert!(state.projectiles.is_empty());
+        assert!(health(&state).iter().all(|&hp| hp < full));
+        assert_eq!(health(&state)[2], health(&state)[0]); // One hit each
+    }
+
+    #[test]
+    fn test_chain_skips_hit_enemies_and_stops_at_range() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let range = state.config.modifiers.chain_range;
+        spawn_enemy(&mut state, Position::new(300.0, 300.0));
+        spawn_enemy(&mut state, Position::new(300.0 + range * 0.6, 300.0));
+        // Within range of the first enemy but not of the second: the chain has moved on
+        spawn_enemy(&mut state, Position::new(300.0 - range * 0.7, 300.0));
+        let full = health(&state)[0];
+        let chain = Modifiers {
+            chain: 3,
+            ..Default::default()
+        };
+        shoot(&mut state, Position::new(300.0, 500.0), chain);
+
+        hit_at(&mut state, Position::new(300.0, 300.0));
+        let jumps = state
+            .events
+            .iter()
+            .filter(|event| matches!(event, GameEvent::ChainJump { .. }))
+            .count();
+        assert_eq!(jumps, 1);
+
+        let [first, second, third] = health(&state)[..] else {
+            unreachable!()
+        };
+        let direct = full - first;
+        let expected = full - direct * state.config.modifiers.chain_damage;
+        assert!((second - expected).abs() < 0.01);
+        assert_eq!(third, full);
+    }
+
+    #[test]
+    fn test_split_children_do_not_split_again() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        spawn_enemy(&mut state, Position::new(400.0, 300.0));
+        let split = Modifiers {
+            split: 3,
+            ..Default::default()
+        };
+        shoot(&mut state, Position::new(400.0, 500.0), split);
+        let parent = state.projectiles[0].damage;
+
+        hit_at(&mut state, Position::new(400.0, 300.0));
+        assert_eq!(state.projectiles.len(), 3);
+        let cfg = &state.config.modifiers;
+        for child in &state.projectiles {
+            assert!(child.modifiers.is_none());
+            assert_eq!(child.damage, parent * cfg.split_damage);
+        }
+        // Fanned out around the parent's heading
+        let middle = state.projectiles[1].velocity;
+        let angle = middle.angle_between(state.projectiles[0].velocity);
+        assert!((angle.abs().to_degrees() - cfg.split_spread_angle).abs() < 0.01);
+
+        // Don't re-hit the enemy they came from, and a child hit only removes the child
+        hit_at(&mut state, Position::new(400.0, 300.0));
+        assert_eq!(state.projectiles.len(), 3);
+        spawn_enemy(&mut state, Position::new(400.0, 100.0));
+        hit_at(&mut state, Position::new(400.0, 100.0));
+        assert_eq!(state.projectiles.len(), 2);
     }
 }
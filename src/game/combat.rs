@@ -1,5 +1,4 @@
-use crate::game::particles::spawn_death_explosion;
-use crate::game::screen_shake::shake_on_enemy_death;
+use crate::game::events::{GameEvent, emit};
 // use crate::game::utils::circle_collision; // Uncomment when entity collision is implemented
 use crate::models::*;
 
@@ -34,7 +33,7 @@ pub fn cleanup_dead_entities(state: &mut GameState) {
     let dead_splitters: Vec<Enemy> = state
         .enemies
         .iter()
-        .filter(|e| e.stats.health <= 0.0 && e.entity_type == EntityType::Splitter)
+        .filter(|e| e.stats.health <= 0.0 && is_splitter(e.entity_type, &state.config))
         .cloned()
         .collect();
 
@@ -42,60 +41,76 @@ pub fn cleanup_dead_entities(state: &mut GameState) {
     let mut i = 0;
     while i < state.enemies.len() {
         if state.enemies[i].stats.health <= 0.0 {
-            let enemy_type = state.enemies[i].entity_type;
-            let enemy_pos = state.enemies[i].pos;
-            state.player.available_ghosts.push(enemy_type);
-            state.enemies.remove(i);
-            shake_on_enemy_death(state);
-            spawn_death_explosion(state, enemy_pos); // Particle explosion!
+            let enemy = state.enemies.remove(i);
+            state.player.available_ghosts.push(enemy.entity_type);
+            state.enemies_killed += 1;
+            emit(
+                state,
+                GameEvent::EnemyKilled {
+                    entity_type: enemy.entity_type,
+                    pos: enemy.pos,
+                    killer: enemy.last_hit_by,
+                },
+            );
         } else {
             i += 1;
         }
     }
 
     // Handle splitter enemies - spawn splits
-    let new_splits = crate::game::splitter::handle_enemy_splits(&dead_splitters, &state.config);
+    let new_splits = crate::game::splitter::handle_enemy_splits(
+        &dead_splitters,
+        &state.config,
+        &mut state.rng,
+        &mut state.entity_ids,
+    );
     for split in new_splits {
         state.enemies.push(split);
     }
 
-    // Spawn split animation particles for each dead splitter
-    for splitter in &dead_splitters {
-        let split_count = state.config.entities.splitter.split_count;
-        crate::game::splitter::spawn_split_particles(state, splitter.pos, split_count, false);
-    }
-
     // Collect dead ghost splitters before removing
     // Only collect NEWLY dead ghosts (not already despawning) to prevent infinite splitting!
     let dead_ghost_splitters: Vec<Ghost> = state
         .ghosts
         .iter()
         .filter(|g| {
-            g.stats.health <= 0.0 && g.entity_type == EntityType::Splitter && !g.anim.is_despawning
+            g.stats.health <= 0.0
+                && is_splitter(g.entity_type, &state.config)
+                && !g.anim.is_despawning
         })
         .cloned()
         .collect();
 
     // Remove dead ghosts
     // Trigger despawn animation instead of instant removal
+    let mut ghost_deaths = Vec::new();
     for ghost in state.ghosts.iter_mut() {
         if ghost.stats.health <= 0.0 && !ghost.anim.is_despawning {
             ghost
                 .anim
                 .start_despawn(state.config.animations.ghost_despawn_duration);
+            ghost_deaths.push(GameEvent::GhostKilled {
+                entity_type: ghost.entity_type,
+                pos: ghost.pos,
+            });
         }
     }
+    for event in ghost_deaths {
+        emit(state, event);
+    }
 
     // Handle ghost splitter splitting - spawn new ghost splits
-    let new_ghost_splits =
-        crate::game::splitter::handle_ghost_splits(&dead_ghost_splitters, &state.config);
+    let new_ghost_splits = crate::game::splitter::handle_ghost_splits(
+        &dead_ghost_splitters,
+        &state.config,
+        &mut state.rng,
+        &mut state.entity_ids,
+    );
     for split in new_ghost_splits {
         state.ghosts.push(split);
     }
+}
 
-    // Spawn split animation particles for each dead ghost splitter
-    for ghost_splitter in &dead_ghost_splitters {
-        let split_count = state.config.entities.splitter.split_count;
-        crate::game::splitter::spawn_split_particles(state, ghost_splitter.pos, split_count, true);
-    }
+fn is_splitter(entity_type: EntityType, config: &crate::config::GameConfig) -> bool {
+    entity_type.archetype(&config.archetypes).splitter.is_some()
 }
@@ -0,0 +1,242 @@
+//! Damage pipeline - every hit on the player, an enemy or a ghost goes through `apply_damage`
+//!
+//! Raw amount -> critical roll -> weapon resistance -> armor -> shield, then the on-damage hooks
+//! (hit flash, kill credit, status effects) and, for the blow that drops health to zero, the on-death hooks.
+//! Hooks run inside the tick and may change gameplay state, unlike event subscribers.
+
+use crate::game::events::{GameEvent, emit};
+use crate::models::*;
+
+/// Who takes the hit (indices are valid until `cleanup_dead_entities`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DamageTarget {
+    Player,
+    Enemy(usize),
+    Ghost(usize),
+}
+
+/// One hit, before mitigation (after `apply_damage`: what was actually dealt)
+#[derive(Clone, Copy, Debug)]
+pub struct DamageInfo {
+    pub amount: f32,
+    pub weapon: WeaponType,
+    pub source: ProjectileOwner,     // Credited with the kill
+    pub crit: bool,                  // Forced crit (otherwise rolled from the weapon's crit_chance)
+    pub periodic: bool, // Burn/beam tick (no crit roll or event, doesn't re-apply effects)
+    pub direction: Option<Position>, // Travel direction of the hit (shields only stop directed hits)
+}
+
+impl DamageInfo {
+    pub fn new(amount: f32, weapon: WeaponType, source: ProjectileOwner) -> Self {
+        Self {
+            amount,
+            weapon,
+            source,
+            crit: false,
+            periodic: false,
+            direction: None,
+        }
+    }
+
+    pub fn with_direction(mut self, direction: Position) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+}
+
+/// Reacts to a hit that landed (gets the final, mitigated damage)
+pub type DamageHook = fn(&mut GameState, DamageTarget, &DamageInfo);
+
+/// Run for every hit, in order
+const ON_DAMAGE: &[DamageHook] = &[
+    hit_flash,
+    credit_kill,
+    announce_crit,
+    crate::game::status::apply_weapon_status,
+];
+
+/// Run once, for the hit that drops health to zero
+const ON_DEATH: &[DamageHook] = &[player_down];
+
+/// Apply one hit through the pipeline (returns the damage dealt)
+pub fn apply_damage(state: &mut GameState, target: DamageTarget, mut info: DamageInfo) -> f32 {
+    let weapon = info.weapon.config(&state.config.weapons);
+    let cfg = &state.config.damage;
+
+    // Crit roll only uses the gameplay stream for weapons that can crit
+    if !info.crit && !info.periodic && weapon.crit_chance > 0.0 {
+        info.crit = state.rng.gen_range(0.0, 1.0) < weapon.crit_chance;
+    }
+    let mut amount = info.amount;
+    if info.crit {
+        amount *= cfg.crit_multiplier;
+    }
+
+    // Player armor, or the archetype's armor and resistances (enemies and ghosts)
+    let (armor, resistance) = match target {
+        DamageTarget::Player => (state.config.player.armor, 0.0),
+        DamageTarget::Enemy(index) => {
+            let archetype = state.enemies[index]
+                .entity_type
+                .archetype(&state.config.archetypes);
+            let resistance = archetype.resistances.get(&weapon.name).copied();
+            (archetype.armor, resistance.unwrap_or(0.0))
+        }
+        DamageTarget::Ghost(index) => {
+            let archetype = state.ghosts[index]
+                .entity_type
+                .archetype(&state.config.archetypes);
+            let resistance = archetype.resistances.get(&weapon.name).copied();
+            (archetype.armor, resistance.unwrap_or(0.0))
+        }
+    };
+    amount *= (1.0 - resistance).max(0.0);
+    if armor > 0.0 {
+        amount *= cfg.armor_scale / (cfg.armor_scale + armor);
+    }
+
+    // Shields soak directed hits from the front (a fully absorbed hit lands nowhere else)
+    let absorbed = amount > 0.0;
+    amount = crate::game::shields::absorb(state, target, &info, amount);
+    if absorbed && amount <= 0.0 {
+        return 0.0;
+    }
+    info.amount = amount;
+
+    let stats = target_stats(state, target);
+    let was_alive = stats.health > 0.0;
+    stats.health -= amount;
+    let died = was_alive && stats.health <= 0.0;
+
+    for hook in ON_DAMAGE {
+        hook(state, target, &info);
+    }
+    if died {
+        for hook in ON_DEATH {
+            hook(state, target, &info);
+        }
+    }
+
+    amount
+}
+
+/// Health (and shield) of whatever was hit
+pub fn target_stats(state: &mut GameState, target: DamageTarget) -> &mut Stats {
+    match target {
+        DamageTarget::Player => &mut state.player.stats,
+        DamageTarget::Enemy(index) => &mut state.enemies[index].stats,
+        DamageTarget::Ghost(index) => &mut state.ghosts[index].stats,
+    }
+}
+
+/// Where the target is (for effects)
+pub fn target_pos(state: &GameState, target: DamageTarget) -> Position {
+    match target {
+        DamageTarget::Player => state.player.pos,
+        DamageTarget::Enemy(index) => state.enemies[index].pos,
+        DamageTarget::Ghost(index) => state.ghosts[index].pos,
+    }
+}
+
+/// Hook: white flash on whatever was hit
+fn hit_flash(state: &mut GameState, target: DamageTarget, _info: &DamageInfo) {
+    let duration = state.config.animations.hit_flash_duration;
+    match target {
+        DamageTarget::Player => state.player.hit_flash_timer = duration,
+        DamageTarget::Enemy(index) => state.enemies[index].anim.hit_flash_timer = duration,
+        DamageTarget::Ghost(index) => state.ghosts[index].anim.hit_flash_timer = duration,
+    }
+}
+
+/// Hook: the last owner to hit an enemy gets the kill
+fn credit_kill(state: &mut GameState, target: DamageTarget, info: &DamageInfo) {
+    if let DamageTarget::Enemy(index) = target {
+        state.enemies[index].last_hit_by = Some(info.source);
+    }
+}
+
+/// Hook: critical hits get their own event (effects subscribe to it)
+fn announce_crit(state: &mut GameState, target: DamageTarget, info: &DamageInfo) {
+    if info.crit && !info.periodic {
+        let pos = target_pos(state, target);
+        emit(
+            state,
+            GameEvent::CriticalHit {
+                weapon: info.weapon,
+                pos,
+                damage: info.amount,
+            },
+        );
+    }
+}
+
+/// Hook: game over notice (the game over screen is still TODO)
+fn player_down(_state: &mut GameState, target: DamageTarget, info: &DamageInfo) {
+    if target == DamageTarget::Player {
+        println!(
+            "✘ Player down! ({:.0} damage, {:?})",
+            info.amount, info.source
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn(state: &mut GameState, name: &str) -> usize {
+        let entity_type = EntityType::from_name(name, &state.config.archetypes).unwrap();
+        let id = state.entity_ids.next();
+        let enemy = Enemy::from_entity_type(id, entity_type, Position::ZERO, &state.config);
+        state.enemies.push(enemy);
+        state.enemies.len() - 1
+    }
+
+    fn weapon(state: &GameState, name: &str) -> WeaponType {
+        WeaponType::from_name(name, &state.config.weapons).unwrap()
+    }
+
+    #[test]
+    fn test_armor_resistance_and_crit() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let fighter = spawn(&mut state, "BasicFighter");
+        let tank = spawn(&mut state, "Tank");
+        let (bullet, laser) = (weapon(&state, "Bullet"), weapon(&state, "Laser"));
+
+        let hit = DamageInfo::new(10.0, laser, ProjectileOwner::Player);
+        assert_eq!(
+            apply_damage(&mut state, DamageTarget::Enemy(fighter), hit),
+            10.0
+        );
+
+        // Tank: 50 armor (x2/3), and Bullets are resisted on top
+        let dealt = apply_damage(&mut state, DamageTarget::Enemy(tank), hit);
+        assert!((dealt - 10.0 * 2.0 / 3.0).abs() < 1e-4, "{dealt}");
+        let resisted = DamageInfo {
+            crit: true,
+            ..DamageInfo::new(10.0, bullet, ProjectileOwner::Player)
+        };
+        let dealt = apply_damage(&mut state, DamageTarget::Enemy(tank), resisted);
+        assert!((dealt - 20.0 * 0.5 * 2.0 / 3.0).abs() < 1e-4, "{dealt}");
+        assert_eq!(
+            state.enemies[tank].last_hit_by,
+            Some(ProjectileOwner::Player)
+        );
+        assert!(matches!(
+            state.events.as_slice(),
+            [GameEvent::CriticalHit { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_player_hit_flashes_and_uses_player_armor() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.config.player.armor = 100.0;
+        let health = state.player.stats.health;
+
+        let hit = DamageInfo::new(10.0, weapon(&state, "Laser"), ProjectileOwner::Enemy);
+        assert_eq!(apply_damage(&mut state, DamageTarget::Player, hit), 5.0);
+        assert_eq!(state.player.stats.health, health - 5.0);
+        assert!(state.player.hit_flash_timer > 0.0);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::config::{FireDirection, Modifiers};
+use crate::game::ai::{AiContext, tick_tree};
+use crate::game::behaviors::{behavior_intent, intent_weapon};
 use crate::game::weapons::{FireWeaponParams, FiringDirection, fire_weapon};
 use crate::models::*;
 use macroquad::prelude::*;
@@ -11,19 +14,36 @@ pub fn update_enemies(state: &mut GameState, delta: f32) {
     let fire_threshold_y = state.config.enemy_behavior.fire_threshold_y;
     let screen_boundary_bottom = state.config.enemy_behavior.screen_boundary_bottom;
     let hit_flash_duration = state.config.animations.hit_flash_duration;
+    let lua_max_speed = state.config.enemy_behavior.lua_max_speed;
 
     // Collect firing events first to avoid borrow issues
     // Store all data needed to fire (no references to state)
-    let mut fire_events: Vec<(Vec2, WeaponType, FiringDirection)> = Vec::new();
+    let mut fire_events: Vec<(Vec2, EntityId, WeaponType, FiringDirection)> = Vec::new();
 
     // Capture player data for firing direction
     let player_pos = state.player.pos;
     let player_vel = state.player.velocity;
 
+    // Behavior trees see where every enemy was at the start of the tick
+    let allies: Vec<(EntityId, EntityType, Position)> = state
+        .enemies
+        .iter()
+        .map(|e| (e.id, e.entity_type, e.pos))
+        .collect();
+    let arena = state.arena;
+
+    // Lua behaviors share one instruction budget per tick
+    let scripting = &state.wave_manager.scripting;
+    scripting.begin_behaviors(state.config.enemy_behavior.lua_instruction_budget);
+
     // Update each enemy (fire timers now embedded in Enemy struct!)
     for enemy in state.enemies.iter_mut() {
+        // Slow/stun scale movement and the fire timer
+        let time_scale = enemy.status.time_scale();
+        let scaled_delta = delta * time_scale;
+
         // Update fire timer
-        enemy.fire_timer = (enemy.fire_timer - delta).max(0.0);
+        enemy.fire_timer = (enemy.fire_timer - scaled_delta).max(0.0);
 
         // Update hit flash animation
         crate::game::animation::update_hit_flash(
@@ -32,6 +52,43 @@ pub fn update_enemies(state: &mut GameState, delta: f32) {
             hit_flash_duration,
         );
 
+        // Scripted archetypes decide for themselves once in free movement
+        let intent = behavior_intent(
+            scripting,
+            enemy,
+            &state.config.archetypes,
+            player_pos,
+            state.time,
+        );
+
+        // Behavior tree archetypes (a Lua behavior takes precedence)
+        let archetype = enemy.entity_type.archetype(&state.config.archetypes);
+        let ai = match &archetype.ai {
+            Some(tree)
+                if intent.is_none()
+                    && time_scale > 0.0
+                    && matches!(enemy.movement_state, EnemyMovementState::FreeMovement) =>
+            {
+                let ctx = AiContext {
+                    id: enemy.id,
+                    pos: enemy.pos,
+                    health_fraction: enemy.stats.health / enemy.stats.max_health,
+                    fire_ready: enemy.fire_timer <= 0.0 && enemy.pos.y > fire_threshold_y,
+                    player_pos,
+                    player_vel,
+                    arena,
+                    time: state.time,
+                    allies: &allies,
+                    archetypes: &state.config.archetypes,
+                };
+                Some(tick_tree(tree, &mut enemy.ai, &ctx))
+            }
+            _ => {
+                enemy.ai.active.clear();
+                None
+            }
+        };
+
         // Movement logic - Check if following Bezier path or free movement
         match &mut enemy.movement_state {
             EnemyMovementState::FollowingPath {
@@ -40,7 +97,7 @@ pub fn update_enemies(state: &mut GameState, delta: f32) {
                 elapsed_time,
             } => {
                 // Update path progress
-                *elapsed_time += delta;
+                *elapsed_time += scaled_delta;
                 *progress = (*elapsed_time / path.duration).min(1.0);
 
                 // Interpolate position along Bezier curve
@@ -59,61 +116,78 @@ pub fn update_enemies(state: &mut GameState, delta: f32) {
                 }
             }
             EnemyMovementState::FreeMovement => {
-                // Normal descent movement (original behavior)
-                if enemy.pos.y < movement_threshold_y {
-                    enemy.pos.y += fast_descent_speed * delta;
+                if let Some(intent) = &intent {
+                    // Scripted steering (speed capped by lua_max_speed, kept inside the arena)
+                    let velocity = intent.velocity.clamp_length_max(lua_max_speed);
+                    enemy.pos = (enemy.pos + velocity * scaled_delta).clamp(Vec2::ZERO, arena);
+                } else if let Some(ai) = &ai {
+                    // Behavior tree steering (kept inside the arena)
+                    enemy.pos = (enemy.pos + ai.velocity * scaled_delta).clamp(Vec2::ZERO, arena);
+                } else if enemy.pos.y < movement_threshold_y {
+                    // Normal descent movement (original behavior)
+                    enemy.pos.y += fast_descent_speed * scaled_delta;
                 } else {
-                    enemy.pos.y += slow_hover_speed * delta;
+                    enemy.pos.y += slow_hover_speed * scaled_delta;
                 }
             }
         }
 
         // Fire based on enemy type (only in free movement or near end of path)
-        let can_fire = match &enemy.movement_state {
-            EnemyMovementState::FollowingPath { progress, .. } => *progress > 0.7,
-            EnemyMovementState::FreeMovement => true,
-        };
+        let can_fire = time_scale > 0.0 // Stunned enemies hold fire
+            && match &enemy.movement_state {
+                EnemyMovementState::FollowingPath { progress, .. } => *progress > 0.7,
+                EnemyMovementState::FreeMovement => true,
+            }
+            && intent.as_ref().is_none_or(|intent| intent.fire)
+            && ai.as_ref().is_none_or(|ai| ai.fire.is_some());
 
         if can_fire && enemy.fire_timer <= 0.0 && enemy.pos.y > fire_threshold_y {
-            // Pick random weapon from enemy's arsenal
+            // Pick the scripted weapon, or a random one from enemy's arsenal
             if !enemy.weapon.is_empty() {
-                let random_idx = rand::gen_range(0, enemy.weapon.len());
-                let weapon = enemy.weapon[random_idx];
-
-                // Determine firing direction based on enemy type
-                let direction = match enemy.entity_type {
-                    EntityType::BasicFighter => {
-                        FiringDirection::Down // Shoots straight down
-                    }
-                    EntityType::Tank | EntityType::Sniper | EntityType::Elite => {
-                        FiringDirection::LeadTarget {
-                            target_pos: player_pos,
-                            target_vel: player_vel,
-                        }
-                    }
-                    EntityType::Healer | EntityType::Splitter => {
-                        FiringDirection::AtTarget(player_pos)
-                    }
+                let weapon = match intent
+                    .as_ref()
+                    .and_then(|intent| intent_weapon(intent, enemy, &state.config.weapons))
+                {
+                    Some(weapon) => weapon,
+                    None => enemy.weapon[state.rng.gen_range(0, enemy.weapon.len())],
+                };
+
+                // Determine firing direction from the archetype
+                let direction = match archetype.fire_direction {
+                    FireDirection::Down => FiringDirection::Down, // Shoots straight down
+                    FireDirection::LeadPlayer => FiringDirection::LeadTarget {
+                        target_pos: player_pos,
+                        target_vel: player_vel,
+                    },
+                    FireDirection::AtPlayer => FiringDirection::AtTarget(player_pos),
+                };
+                let direction = match intent.as_ref().and_then(|intent| intent.aim) {
+                    Some(aim) => FiringDirection::AtTarget(aim),
+                    None => ai.and_then(|ai| ai.fire).unwrap_or(direction),
                 };
 
-                fire_events.push((enemy.pos, weapon, direction));
+                fire_events.push((enemy.pos, enemy.id, weapon, direction));
 
                 // Reset fire timer immediately
-                enemy.fire_timer = enemy.entity_type.get_fire_interval(&state.config.entities);
+                enemy.fire_timer = archetype.fire_interval;
             }
         }
     }
+    scripting.end_behaviors();
 
     // Execute firing using unified system (no borrows active now)
-    for (shooter_pos, weapon, direction) in fire_events {
+    let damage_multiplier = state.config.damage.enemy_multiplier; // [damage] owner multiplier
+    for (shooter_pos, shooter, weapon, direction) in fire_events {
         fire_weapon(
             FireWeaponParams {
                 shooter_pos,
+                shooter: Some(shooter),
                 owner: ProjectileOwner::Enemy,
                 weapon,
                 direction,
-                damage_multiplier: 0.75, // Enemies deal 75% damage
-                enemies: None,           // Enemies don't need enemy positions for targeting
+                damage_multiplier,
+                level: 1,
+                modifiers: Modifiers::default(),
             },
             state,
         );
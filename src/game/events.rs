@@ -0,0 +1,162 @@
+//! Gameplay events - systems push what happened, subscribers (effects, logging) react
+//!
+//! The queue is cleared at the start of every tick and dispatched at the end, so the
+//! last tick's events stay readable from outside the simulation until the next tick.
+
+use crate::models::*;
+
+/// Something that happened during a tick
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    EnemyKilled {
+        entity_type: EntityType,
+        pos: Position,
+        killer: Option<ProjectileOwner>, // Owner of the last projectile that hit it
+    },
+    GhostKilled {
+        entity_type: EntityType,
+        pos: Position,
+    },
+    PlayerHit {
+        pos: Position,
+        damage: f32, // Total damage taken this tick
+    },
+    EnemyHit {
+        weapon: WeaponType, // Strongest weapon that landed this tick
+        pos: Position,
+    },
+    CriticalHit {
+        weapon: WeaponType,
+        pos: Position,
+        damage: f32, // After armor and resistances
+    },
+    ChainJump {
+        weapon: WeaponType,
+        from: Position, // Enemy the damage jumped from
+        to: Position,
+    },
+    ShieldBroken {
+        pos: Position,
+    },
+    BombDetonated {
+        weapon: WeaponType,
+        pos: Position,
+        radius: f32,
+        owner: ProjectileOwner,
+    },
+    Graze {
+        pos: Position, // Where the projectile left the graze radius
+    },
+    ParrySuccess {
+        pos: Position,
+        deflected: usize,
+    },
+    ParryMissed,
+    DashStarted {
+        pos: Position,
+        direction: Position,
+    },
+    GhostSummoned {
+        entity_type: EntityType,
+        pos: Position,
+    },
+    GhostsDismissed {
+        count: usize,
+    },
+    FormationChanged {
+        formation: GhostFormation,
+    },
+    WaveStarted {
+        wave: usize,
+    },
+    WaveCompleted {
+        wave: usize,
+    },
+    WeaponLevelChanged {
+        weapon: WeaponType,
+        level: usize,
+        upgraded: bool, // false = lost levels on hit
+    },
+}
+
+/// Reacts to a single event (may mutate cosmetic state, must not push new events)
+pub type EventSubscriber = fn(&mut GameState, &GameEvent);
+
+/// Everything that listens to gameplay events, in call order
+const SUBSCRIBERS: &[EventSubscriber] = &[
+    log_event,
+    crate::game::screen_shake::on_event,
+    crate::game::particles::on_event,
+    crate::game::bombs::on_event,
+];
+
+/// Queue an event for this tick's dispatch
+pub fn emit(state: &mut GameState, event: GameEvent) {
+    state.events.push(event);
+}
+
+/// Run every subscriber over this tick's events (in the order they were pushed)
+pub fn dispatch_events(state: &mut GameState) {
+    let events = std::mem::take(&mut state.events);
+
+    for event in &events {
+        for subscriber in SUBSCRIBERS {
+            subscriber(state, event);
+        }
+    }
+
+    state.events = events;
+}
+
+/// Console log subscriber
+fn log_event(state: &mut GameState, event: &GameEvent) {
+    match event {
+        GameEvent::ParrySuccess { deflected, .. } => {
+            println!("✔ Parry ({deflected} projectiles deflected)")
+        }
+        GameEvent::ParryMissed => println!("✘ Parry window missed!"),
+        GameEvent::GhostsDismissed { count } => println!("✔ Cancel Deployed, {count} ghost(s)!"),
+        GameEvent::FormationChanged { formation } => {
+            println!("Switched to formation: {formation:?}")
+        }
+        GameEvent::WeaponLevelChanged {
+            weapon,
+            level,
+            upgraded,
+        } => {
+            let name = &weapon.config(&state.config.weapons).name;
+            if *upgraded {
+                println!("✔ {name} upgraded to level {level}");
+            } else {
+                println!("✘ {name} dropped to level {level}");
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatch_runs_subscribers_and_keeps_events() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let pos = Position::new(100.0, 100.0);
+
+        emit(
+            &mut state,
+            GameEvent::EnemyKilled {
+                entity_type: EntityType(0),
+                pos,
+                killer: Some(ProjectileOwner::Player),
+            },
+        );
+        dispatch_events(&mut state);
+
+        // Shake + particle subscribers reacted, and the event is still readable
+        assert!(state.screen_shake_duration > 0.0);
+        assert!(!state.particles.is_empty());
+        assert_eq!(state.events.len(), 1);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::Modifiers;
+use crate::game::spatial::EntityKind;
 use crate::game::utils::calculate_formation_position;
 use crate::game::weapons::{FireWeaponParams, FiringDirection, fire_weapon};
 use crate::models::*;
@@ -10,8 +12,8 @@ pub fn update_ghosts(state: &mut GameState, delta: f32) {
     // Update each ghost (fire timers now embedded in Ghost struct!)
     let total_ghosts = state.ghosts.len();
     for ghost in state.ghosts.iter_mut() {
-        // Update fire timer
-        ghost.fire_timer = (ghost.fire_timer - delta).max(0.0);
+        // Update fire timer (slow/stun stretch it)
+        ghost.fire_timer = (ghost.fire_timer - delta * ghost.status.time_scale()).max(0.0);
     }
 
     // Second pass for movement (need index for formation position)
@@ -23,6 +25,7 @@ pub fn update_ghosts(state: &mut GameState, delta: f32) {
             total_ghosts,
             state.ghost_formation,
             &state.config.formation_spacing,
+            state.arena.x,
         );
 
         // Calculate distance to target
@@ -33,7 +36,7 @@ pub fn update_ghosts(state: &mut GameState, delta: f32) {
         if distance > 2.0 {
             // Smoothly interpolate to formation position (feels more natural than instant)
             let follow_speed = 3.0; // Smooth movement
-            ghost.pos += diff * follow_speed * delta;
+            ghost.pos += diff * follow_speed * delta * ghost.status.time_scale();
         }
     }
 
@@ -48,21 +51,22 @@ pub fn update_ghost_firing(state: &mut GameState) {
     let fire_interval = state.config.ghost_behavior.fire_interval;
 
     // Collect firing data first to avoid borrow issues
-    let mut fire_events: Vec<(Vec2, WeaponType, Vec2)> = Vec::new(); // (pos, weapon, target)
-
-    // Collect enemy positions for missile targeting
-    let enemy_positions: Vec<Vec2> = state.enemies.iter().map(|e| e.pos).collect();
+    let mut fire_events: Vec<(Vec2, EntityId, WeaponType, Vec2, Modifiers)> = Vec::new(); // (pos, id, weapon, target, affixes)
 
     for ghost in state.ghosts.iter_mut() {
-        if ghost.fire_timer <= 0.0 && !state.enemies.is_empty() {
+        if ghost.fire_timer <= 0.0 && !state.enemies.is_empty() && !ghost.status.is_stunned() {
             // Find nearest enemy
-            if let Some(target) = find_nearest_enemy(ghost.pos, &state.enemies) {
+            if let Some(target) = state.spatial.nearest(ghost.pos, EntityKind::Enemy) {
                 // Pick random weapon from arsenal
                 if !ghost.weapon_type.is_empty() {
-                    let random_idx = rand::gen_range(0, ghost.weapon_type.len());
+                    let random_idx = state.rng.gen_range(0, ghost.weapon_type.len());
                     let weapon = ghost.weapon_type[random_idx];
 
-                    fire_events.push((ghost.pos, weapon, target));
+                    let modifiers = ghost
+                        .entity_type
+                        .archetype(&state.config.archetypes)
+                        .ghost_modifiers;
+                    fire_events.push((ghost.pos, ghost.id, weapon, target.pos, modifiers));
 
                     // Reset fire timer
                     ghost.fire_timer = fire_interval;
@@ -72,26 +76,20 @@ pub fn update_ghost_firing(state: &mut GameState) {
     }
 
     // Execute firing using unified system
-    for (shooter_pos, weapon, target) in fire_events {
+    let damage_multiplier = state.config.damage.ghost_multiplier; // [damage] owner multiplier
+    for (shooter_pos, shooter, weapon, target, modifiers) in fire_events {
         fire_weapon(
             FireWeaponParams {
                 shooter_pos,
+                shooter: Some(shooter),
                 owner: ProjectileOwner::Ghost,
                 weapon,
                 direction: FiringDirection::AtTarget(target),
-                damage_multiplier: 0.5, // Ghosts deal 50% damage
-                enemies: Some(enemy_positions.clone()), // For missile targeting
+                damage_multiplier,
+                level: 1,
+                modifiers, // Archetype's ghost_modifiers
             },
             state,
         );
     }
 }
-
-/// Find the nearest enemy to a ghost
-fn find_nearest_enemy(ghost_pos: Position, enemies: &[Enemy]) -> Option<Position> {
-    enemies.iter().map(|e| e.pos).min_by(|a, b| {
-        let dist_a = (*a - ghost_pos).length_squared();
-        let dist_b = (*b - ghost_pos).length_squared();
-        dist_a.partial_cmp(&dist_b).unwrap()
-    })
-}
@@ -0,0 +1,74 @@
+//! Graze - enemy projectiles that pass close to the player without hitting refill energy
+//!
+//! A shot is marked when it enters `[graze] radius` and rewarded once it leaves again.
+//! Shots that hit were already removed by `check_projectile_collisions`, so they never count.
+
+use crate::game::events::{GameEvent, emit};
+use crate::models::*;
+
+/// Track enemy projectiles around the player and reward the ones that got away
+pub fn update_grazes(state: &mut GameState) {
+    let radius = state.config.graze.radius;
+    let player_pos = state.player.pos;
+    let mut grazed = Vec::new();
+
+    for projectile in &mut state.projectiles {
+        if projectile.owner != ProjectileOwner::Enemy {
+            continue;
+        }
+        let inside = projectile.pos.distance(player_pos) <= radius;
+        match projectile.graze {
+            Graze::Clear if inside => projectile.graze = Graze::Inside,
+            Graze::Inside if !inside => {
+                projectile.graze = Graze::Counted;
+                grazed.push(projectile.pos);
+            }
+            _ => {}
+        }
+    }
+
+    for pos in grazed {
+        state.grazes += 1;
+        state.player.energy =
+            (state.player.energy + state.config.graze.energy).min(state.player.max_energy);
+        emit(state, GameEvent::Graze { pos });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enemy_shot(state: &mut GameState, pos: Position) {
+        let bullet = WeaponType::from_name("Bullet", &state.config.weapons).unwrap();
+        let params = crate::game::FireWeaponParams {
+            shooter_pos: pos,
+            shooter: None,
+            owner: ProjectileOwner::Enemy,
+            weapon: bullet,
+            direction: crate::game::FiringDirection::Down,
+            damage_multiplier: 1.0,
+            level: 1,
+            modifiers: Default::default(),
+        };
+        crate::game::fire_weapon(params, state);
+    }
+
+    #[test]
+    fn test_near_miss_counts_once_after_leaving() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.player.pos = Position::new(400.0, 300.0);
+        state.player.energy = 0.0;
+        enemy_shot(&mut state, Position::new(425.0, 300.0)); // Inside the radius, off the hitbox
+
+        update_grazes(&mut state);
+        assert_eq!(state.grazes, 0); // Could still hit
+
+        state.projectiles[0].pos.y += 100.0;
+        update_grazes(&mut state);
+        update_grazes(&mut state);
+        assert_eq!(state.grazes, 1);
+        assert_eq!(state.player.energy, state.config.graze.energy);
+        assert!(matches!(state.events.as_slice(), [GameEvent::Graze { .. }]));
+    }
+}
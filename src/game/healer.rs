@@ -1,23 +1,31 @@
+use crate::game::spatial::EntityKind;
 use crate::models::*;
 
 /// Apply healing from healer enemies to other enemies in range
 pub fn update_healer_healing(state: &mut GameState, delta: f32) {
-    // Track healing info (healer_idx, enemy_idx, heal_amount)
-    let mut healing_events: Vec<(usize, usize, f32)> = Vec::new();
+    // Track healing info (healer, target, heal_amount) by handle, so removals can't retarget
+    let mut healing_events: Vec<(EntityId, EntityId, f32)> = Vec::new();
 
     // Check each healer enemy
-    for (healer_idx, healer) in state.enemies.iter().enumerate() {
-        if healer.entity_type != EntityType::Healer {
+    for healer in &state.enemies {
+        let Some(trait_cfg) = &healer
+            .entity_type
+            .archetype(&state.config.archetypes)
+            .healer
+        else {
             continue;
-        }
+        };
 
-        let heal_rate = state.config.entities.healer.heal_rate;
-        let heal_radius = state.config.entities.healer.heal_radius;
-        let heal_amount = heal_rate * delta;
+        let heal_radius = trait_cfg.heal_radius;
+        let heal_amount = trait_cfg.heal_rate * delta;
 
         // Find enemies in range (excluding self)
-        for (enemy_idx, enemy) in state.enemies.iter().enumerate() {
-            if healer_idx == enemy_idx {
+        for entry in state
+            .spatial
+            .query(healer.pos, heal_radius, EntityKind::Enemy)
+        {
+            let enemy = &state.enemies[entry.index];
+            if enemy.id == healer.id {
                 continue; // Don't heal self
             }
 
@@ -26,14 +34,15 @@ pub fn update_healer_healing(state: &mut GameState, delta: f32) {
             let distance = (dx * dx + dy * dy).sqrt();
 
             if distance <= heal_radius {
-                healing_events.push((healer_idx, enemy_idx, heal_amount));
+                healing_events.push((healer.id, enemy.id, heal_amount));
             }
         }
     }
 
     // Apply healing (don't exceed max health)
-    for (_healer_idx, enemy_idx, heal_amount) in healing_events {
-        if let Some(enemy) = state.enemies.get_mut(enemy_idx) {
+    for (_healer_id, target_id, heal_amount) in healing_events {
+        // Target may be gone - skip explicitly instead of healing whoever took its slot
+        if let Some(enemy) = state.enemies.iter_mut().find(|e| e.id == target_id) {
             enemy.stats.health = (enemy.stats.health + heal_amount).min(enemy.stats.max_health);
         }
     }
@@ -41,15 +50,13 @@ pub fn update_healer_healing(state: &mut GameState, delta: f32) {
 
 /// Apply healing from healer ghosts to player
 pub fn update_ghost_healer_healing(state: &mut GameState, delta: f32) {
-    let heal_rate = state.config.entities.healer.heal_rate;
-    let heal_radius = state.config.entities.healer.heal_radius;
-    let heal_amount = heal_rate * delta;
-
     // Check each ghost
     for ghost in &state.ghosts {
-        if ghost.entity_type != EntityType::Healer {
+        let Some(trait_cfg) = &ghost.entity_type.archetype(&state.config.archetypes).healer else {
             continue;
-        }
+        };
+        let heal_radius = trait_cfg.heal_radius;
+        let heal_amount = trait_cfg.heal_rate * delta;
 
         // Check if player is in range
         let dx = state.player.pos.x - ghost.pos.x;
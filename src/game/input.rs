@@ -1,139 +1,132 @@
+use crate::game::events::{GameEvent, emit};
 use crate::game::utils::calculate_formation_position;
-use crate::game::weapons;
-use crate::models::{EntityType, GameState, Ghost};
-use macroquad::input::*;
+use crate::game::{bombs, weapons};
+use crate::models::{EntityType, GameState, Ghost, GhostFormation};
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Everything the player can ask the game to do (keys are mapped to these via `[bindings]`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Dash,                         // Held together with a direction
+    FireSlot(usize),              // Weapon slot (0 = Bullets, 1 = Lasers, ..., 4 = Bombs)
+    SetFormation(GhostFormation), // Switch formation
+    SummonFormation,              // Summon full formation from the ghost queue
+    SummonType(EntityType),       // Summon a single ghost of this type
+    Parry,
+    CancelSummon,
+}
 
-/// Handle all player input
-pub fn handle_input(game_state: &mut GameState, _delta_time: f32) {
-    // Dash input (Shift + WASD) - Handle BEFORE normal movement!
-    handle_dash_input(game_state);
+/// One tick of player input (read from the keyboard or synthesized by bots/tests/replays)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputState {
+    pub held: BTreeSet<Action>,    // Actions whose key is down
+    pub pressed: BTreeSet<Action>, // Actions whose key went down since the last tick
+}
 
-    // Player movement input (only if not dashing)
-    // We now just calculate input direction, actual movement happens in player.rs with physics
-    if !game_state.player.is_dashing {
-        let mut input_direction = (0.0f32, 0.0f32);
+impl InputState {
+    /// Mark an action as held (movement, fire, dash)
+    pub fn hold(&mut self, action: Action) {
+        self.held.insert(action);
+    }
+
+    /// Mark a one-shot press (parry, summon, formation switch)
+    pub fn press(&mut self, action: Action) {
+        self.pressed.insert(action);
+    }
 
-        if is_key_down(KeyCode::Down) || is_key_down(KeyCode::S) {
-            input_direction.1 += 1.0f32;
+    pub fn is_held(&self, action: Action) -> bool {
+        self.held.contains(&action)
+    }
+
+    /// Normalized movement direction from the held move actions
+    pub fn move_direction(&self) -> Vec2 {
+        let mut direction = Vec2::ZERO;
+
+        if self.is_held(Action::MoveDown) {
+            direction.y += 1.0;
         }
-        if is_key_down(KeyCode::Up) || is_key_down(KeyCode::W) {
-            input_direction.1 -= 1.0f32;
+        if self.is_held(Action::MoveUp) {
+            direction.y -= 1.0;
         }
-        if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
-            input_direction.0 -= 1.0f32;
+        if self.is_held(Action::MoveLeft) {
+            direction.x -= 1.0;
         }
-        if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
-            input_direction.0 += 1.0f32;
+        if self.is_held(Action::MoveRight) {
+            direction.x += 1.0;
         }
 
         // Normalize diagonal movement
-        let magnitude =
-            (input_direction.0 * input_direction.0 + input_direction.1 * input_direction.1).sqrt();
-        if magnitude > 0.0 {
-            input_direction.0 /= magnitude;
-            input_direction.1 /= magnitude;
-        }
-
-        // Store input direction for physics-based movement
-        game_state.player.input_direction =
-            macroquad::math::Vec2::new(input_direction.0, input_direction.1);
-    } else {
-        // No input during dash
-        game_state.player.input_direction = macroquad::math::Vec2::ZERO;
+        direction.normalize_or_zero()
     }
 
-    // Fire Bullets
-    if is_key_down(KeyCode::H) {
-        weapons::player_fire_weapon(game_state, 0);
+    /// Merge a newer keyboard read into input that hasn't been simulated yet
+    /// Held actions take the latest state, presses are kept until a tick consumes them
+    pub fn accumulate(&mut self, latest: InputState) {
+        self.held = latest.held;
+        self.pressed.extend(latest.pressed);
     }
 
-    // Fire Lasers
-    if is_key_down(KeyCode::J) {
-        weapons::player_fire_weapon(game_state, 1);
-    }
-
-    // Fire Missiles
-    if is_key_down(KeyCode::K) {
-        weapons::player_fire_weapon(game_state, 2);
-    }
-    // Fire Plasma
-    if is_key_down(KeyCode::L) {
-        weapons::player_fire_weapon(game_state, 3);
-    }
-    // Fire Bombs
-    //if is_key_down(KeyCode::Semicolon) {
-    //    weapons::player_fire_weapon(game_state, 4);
-    //}
-
-    // Formation switching
-    change_formation(game_state);
-
-    if is_key_pressed(KeyCode::Space) {
-        spawn_formation(game_state);
+    /// Forget one-shot presses after a tick has handled them
+    pub fn clear_presses(&mut self) {
+        self.pressed.clear();
     }
+}
 
-    // Ghost spawning
-    spawn_ghost_from_queue(game_state);
+/// Handle all player input
+pub fn handle_input(game_state: &mut GameState, input: &InputState, _delta_time: f32) {
+    // Dash input (Shift + WASD) - Handle BEFORE normal movement!
+    handle_dash_input(game_state, input);
 
-    // Parry system
-    if is_key_pressed(KeyCode::X) {
-        crate::game::parry::attempt_parry(game_state);
+    // Player movement input (only if not dashing)
+    // We now just calculate input direction, actual movement happens in player.rs with physics
+    if !game_state.player.is_dashing {
+        // Store input direction for physics-based movement
+        game_state.player.input_direction = input.move_direction();
+    } else {
+        // No input during dash
+        game_state.player.input_direction = Vec2::ZERO;
     }
 
-    // Cancel summon
-    if is_key_pressed(KeyCode::C) {
-        crate::game::cancel_summon::cancel_summon(game_state);
+    // Fire weapons (Bullets, Lasers, Missiles, Plasma) - bomb slots act on press instead
+    for &action in &input.held {
+        if let Action::FireSlot(slot) = action
+            && bombs::bomb_slot_weapon(game_state, slot).is_none()
+        {
+            weapons::player_fire_weapon(game_state, slot);
+        }
     }
-}
-
-/// Spawn single ghost when F-keys pressed
-fn spawn_ghost_from_queue(state: &mut GameState) {
-    let desired_type = if is_key_pressed(KeyCode::F1) {
-        Some(EntityType::BasicFighter)
-    } else if is_key_pressed(KeyCode::F2) {
-        Some(EntityType::Sniper)
-    } else if is_key_pressed(KeyCode::F3) {
-        Some(EntityType::Tank)
-    } else if is_key_pressed(KeyCode::F4) {
-        Some(EntityType::Healer)
-    } else if is_key_pressed(KeyCode::F5) {
-        Some(EntityType::Splitter)
-    } else if is_key_pressed(KeyCode::F6) {
-        Some(EntityType::Elite)
-    } else {
-        None
-    };
 
-    if let Some(ghost_type) = desired_type {
-        try_spawn_ghost(state, ghost_type);
+    // One-shot actions (formation switching, summoning, parry, cancel)
+    for &action in &input.pressed {
+        match action {
+            Action::SetFormation(formation) => change_formation(game_state, formation),
+            Action::SummonFormation => spawn_formation(game_state),
+            Action::SummonType(ghost_type) => try_spawn_ghost(game_state, ghost_type),
+            Action::Parry => crate::game::parry::attempt_parry(game_state),
+            Action::CancelSummon => crate::game::cancel_summon::cancel_summon(game_state),
+            Action::FireSlot(slot) => bombs::player_bomb_press(game_state, slot), // Bomb slots only
+            _ => {}
+        }
     }
 }
 
-fn change_formation(state: &mut GameState) {
-    use crate::models::GhostFormation;
-
+fn change_formation(state: &mut GameState, formation: GhostFormation) {
     let current_ghost_count = state.player.available_ghosts.len();
-    let new_formation = if is_key_pressed(KeyCode::Key1) {
-        Some(GhostFormation::Line)
-    } else if is_key_pressed(KeyCode::Key2) {
-        Some(GhostFormation::Circle)
-    } else if is_key_pressed(KeyCode::Key3) {
-        Some(GhostFormation::VShape)
-    } else {
-        None
-    };
 
     // Validate formation can be used
-    if let Some(formation) = new_formation {
-        if formation.is_valid_for_count(current_ghost_count) {
-            state.ghost_formation = formation;
-            // TODO: Play formation switch sound
-            println!("Switched to formation: {formation:?}");
-        } else {
-            println!("Not enough ghosts to form : {formation:?}");
-            // TODO: Play error sound / show message
-            // Can't switch - not enough ghosts in queue
-        }
+    if formation.is_valid_for_count(current_ghost_count) {
+        state.ghost_formation = formation;
+        emit(state, GameEvent::FormationChanged { formation });
+    } else {
+        println!("Not enough ghosts to form : {formation:?}");
+        // TODO: Play error sound / show message
+        // Can't switch - not enough ghosts in queue
     }
 }
 
@@ -146,7 +139,7 @@ fn try_spawn_ghost(state: &mut GameState, desired_type: EntityType) {
         .iter()
         .position(|&t| t == desired_type)
     {
-        let energy_cost = desired_type.get_energy_cost(&state.config.entities);
+        let energy_cost = desired_type.get_energy_cost(&state.config.archetypes);
 
         // Check if player has enough energy
         if state.player.energy < energy_cost {
@@ -161,13 +154,22 @@ fn try_spawn_ghost(state: &mut GameState, desired_type: EntityType) {
             state.ghosts.len() + 1,
             state.ghost_formation,
             &state.config.formation_spacing,
+            state.arena.x,
         );
 
         // Create ghost directly from EntityType (no temp Enemy!)
-        let ghost = Ghost::from_entity_type(desired_type, spawn_pos, &state.config);
+        let id = state.entity_ids.next();
+        let ghost = Ghost::from_entity_type(id, desired_type, spawn_pos, &state.config);
 
         // All checks passed - spawn and deduct
         state.ghosts.push(ghost);
+        emit(
+            state,
+            GameEvent::GhostSummoned {
+                entity_type: desired_type,
+                pos: spawn_pos,
+            },
+        );
         state.player.available_ghosts.remove(index);
         state.player.energy -= energy_cost;
     }
@@ -191,7 +193,7 @@ fn spawn_formation(state: &mut GameState) {
     for i in 0..spawn_count {
         if i < state.player.available_ghosts.len() {
             let ghost_type = state.player.available_ghosts[i];
-            total_energy_cost += ghost_type.get_energy_cost(&state.config.entities);
+            total_energy_cost += ghost_type.get_energy_cost(&state.config.archetypes);
         }
     }
 
@@ -216,56 +218,48 @@ fn spawn_formation(state: &mut GameState) {
             state.ghosts.len() + (spawn_count - i),
             formation,
             &state.config.formation_spacing,
+            state.arena.x,
         );
 
         // Create ghost directly from EntityType (inherits weapons from config!)
-        let ghost = Ghost::from_entity_type(ghost_type, spawn_pos, &state.config);
+        let id = state.entity_ids.next();
+        let ghost = Ghost::from_entity_type(id, ghost_type, spawn_pos, &state.config);
         state.ghosts.push(ghost);
+        emit(
+            state,
+            GameEvent::GhostSummoned {
+                entity_type: ghost_type,
+                pos: spawn_pos,
+            },
+        );
     }
 }
 
 /// Handle dash input with 8-directional movement
-fn handle_dash_input(state: &mut GameState) {
+fn handle_dash_input(state: &mut GameState, input: &InputState) {
     let dash_cfg = &state.config.dash;
 
-    // Can't dash if disabled, already dashing, or on cooldown
-    if !dash_cfg.enabled || state.player.is_dashing || state.player.dash_cooldown_timer > 0.0 {
+    // Can't dash if disabled, already dashing, on cooldown or stunned
+    if !dash_cfg.enabled
+        || state.player.is_dashing
+        || state.player.dash_cooldown_timer > 0.0
+        || state.player.status.is_stunned()
+    {
         return;
     }
 
-    // Check if Shift is held
-    if !is_key_down(KeyCode::LeftShift) && !is_key_down(KeyCode::RightShift) {
+    // Check if the dash action is held
+    if !input.is_held(Action::Dash) {
         return;
     }
 
-    // Calculate dash direction from WASD input
-    let mut dir_x = 0.0f32;
-    let mut dir_y = 0.0f32;
-
-    if is_key_down(KeyCode::W) || is_key_down(KeyCode::Up) {
-        dir_y = -1.0f32;
-    }
-    if is_key_down(KeyCode::S) || is_key_down(KeyCode::Down) {
-        dir_y = 1.0f32;
-    }
-    if is_key_down(KeyCode::A) || is_key_down(KeyCode::Left) {
-        dir_x = -1.0f32;
-    }
-    if is_key_down(KeyCode::D) || is_key_down(KeyCode::Right) {
-        dir_x = 1.0f32;
-    }
-
+    // Dash direction comes from movement input (already normalized)
     // No direction = no dash
-    if dir_x == 0.0 && dir_y == 0.0 {
+    let direction = input.move_direction();
+    if direction == Vec2::ZERO {
         return;
     }
 
-    // Normalize diagonal directions
-    // Diagonal movement would be sqrt(1^2 + 1^2) = 1.414x faster without normalization
-    let magnitude = (dir_x * dir_x + dir_y * dir_y).sqrt();
-    dir_x /= magnitude;
-    dir_y /= magnitude;
-
     // Check energy requirement
     if state.player.energy < dash_cfg.energy_cost {
         return; // Not enough energy!
@@ -277,9 +271,16 @@ fn handle_dash_input(state: &mut GameState) {
     // Activate dash!
     state.player.is_dashing = true;
     state.player.dash_timer = dash_cfg.duration;
-    state.player.dash_direction.x = dir_x;
-    state.player.dash_direction.y = dir_y;
+    state.player.dash_direction = direction;
     state.player.i_frame_timer = dash_cfg.i_frame_duration;
     state.player.dash_cooldown_timer = dash_cfg.cooldown;
     state.player.dash_trail_timer = 0.0;
+
+    emit(
+        state,
+        GameEvent::DashStarted {
+            pos: state.player.pos,
+            direction,
+        },
+    );
 }
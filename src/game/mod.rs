@@ -1,20 +1,34 @@
+mod ai; // Behavior trees from [archetypes.ai]
 pub mod animation; // Easing functions and animation helpers (public for rendering)
+mod beams; // Continuous beam lasers (raycast hits)
+mod behaviors; // Lua enemy behaviors (steering/fire intents)
 mod bezier; // Bezier curve math for enemy paths
+mod bindings; // Key -> Action table from config
+mod bombs; // Fused explosives, detonation and shockwaves
 mod cancel_summon;
 mod collision;
 mod combat;
+mod damage; // Damage pipeline: crits, resistances, armor, hooks
 mod enemy;
 mod energy;
+pub mod events; // Gameplay event queue + subscribers (effects, logging)
 mod ghost;
 mod ghost_animation; // Ghost spawn/despawn animations
+mod graze; // Energy for enemy shots that barely miss
 mod healer; // Healing system for healer enemies/ghosts
 mod input;
 mod parry;
 mod particles;
 mod player;
+pub mod rng; // Seeded gameplay/cosmetic random streams
 mod screen_shake;
+mod script_commands; // Lua `game` API commands, applied after wave callbacks
+mod shields; // Directional regenerating shields (Tank, Elite)
+pub mod spatial; // Uniform-grid broadphase
 mod spawn;
 mod splitter; // Splitting system for splitter enemies/ghosts
+mod status; // Burn, slow, stun and energy drain
+mod upgrades; // Weapon levels: wave rewards, pickups, hit penalties
 mod utils;
 pub mod wave; // Public module for WaveManager
 mod weapons;
@@ -22,26 +36,43 @@ mod weapons;
 // Exports (some unused until features implemented)
 // pub use cancel_summon::*; // TODO: Enable when cancel summon UI added
 // pub use bezier::*; // Internal module, not re-exported
+pub use ai::AiMemory;
+pub use beams::*;
+pub use bindings::Bindings;
+pub use bombs::*;
 pub use collision::*;
 pub use combat::*;
 pub use enemy::*;
 pub use energy::*;
+pub use events::{GameEvent, emit};
 pub use ghost::*;
+pub use graze::*;
 pub use input::*;
 pub use parry::*;
 pub use particles::*;
 pub use player::*;
 pub use screen_shake::*;
+pub use shields::*;
 pub use spawn::*;
+pub use status::*;
+pub use upgrades::*;
 // pub use wave::*; // WaveManager accessed via game::wave::WaveManager
 pub use weapons::*;
 
 use crate::models::*;
 
-/// Main game loop
-pub fn update_all_systems(state: &mut GameState, delta: f32) {
+/// Main game loop (one fixed simulation tick)
+pub fn update_all_systems(state: &mut GameState, input: &InputState, delta: f32) {
+    // Remember where everything was for render interpolation
+    snapshot_positions(state);
+    state.events.clear();
+    state.time += delta;
+
+    // Last tick's cleanup/spawns invalidated the grid - player missiles need it during input
+    rebuild_spatial(state);
+
     // Handle input
-    handle_input(state, delta);
+    handle_input(state, input, delta);
 
     // Update parry system
     update_parry(state, delta);
@@ -52,22 +83,32 @@ pub fn update_all_systems(state: &mut GameState, delta: f32) {
     update_enemies(state, delta);
     update_ghosts(state, delta);
 
+    // Update ghost animations (spawn/despawn effects, removes finished ghosts)
+    ghost_animation::update_ghost_animations(&mut state.ghosts, delta, &state.config.animations);
+
+    // Positions and lists are final until cleanup - rebuild the broadphase
+    rebuild_spatial(state);
+
     // Ghost auto-fire (separate to avoid borrow issues)
     ghost::update_ghost_firing(state);
 
-    // Update ghost animations (spawn/despawn effects)
-    ghost_animation::update_ghost_animations(&mut state.ghosts, delta, &state.config.animations);
-
     // Update healing (healers heal allies, healer ghosts heal player)
     healer::update_healer_healing(state, delta);
     healer::update_ghost_healer_healing(state, delta);
 
     // Update weapons & projectiles
     update_weapons(state, delta);
+    bombs::update_bombs(state);
+    beams::update_beams(state, delta);
 
     // Check collisions
     check_projectile_collisions(state);
     check_entity_collisions(state);
+    update_grazes(state); // After hits, so shots that connected are gone
+
+    // Burn ticks, durations and energy drain (before dead entities are removed)
+    update_status_effects(state, delta);
+    update_shields(state, delta);
 
     // Manage resources
     manage_energy(state, delta);
@@ -76,11 +117,61 @@ pub fn update_all_systems(state: &mut GameState, delta: f32) {
     // Wave-based spawning (replaces spawn_enemies)
     update_wave_system(state, delta);
 
+    // Weapon levels (reads this tick's kills, hits and cleared waves)
+    update_upgrades(state, delta);
+
+    // Let effects and logging react to this tick's events
+    events::dispatch_events(state);
+
     // Update visual effects
     update_particles(state, delta);
+    update_shockwaves(state, delta);
     update_shake(state, delta);
 }
 
+/// Broadphase-heavy slice of a tick (stress benchmark - entities stay where they are)
+pub fn update_collision_systems(state: &mut GameState, delta: f32) {
+    rebuild_spatial(state);
+    ghost::update_ghost_firing(state);
+    healer::update_healer_healing(state, delta);
+    update_weapons(state, delta);
+    bombs::update_bombs(state);
+    beams::update_beams(state, delta);
+    check_projectile_collisions(state);
+}
+
+/// Rebuild the spatial grid from current player/enemy/ghost positions
+pub fn rebuild_spatial(state: &mut GameState) {
+    state.spatial.rebuild(
+        state.arena,
+        state.config.collision.spatial_cell_size,
+        &state.player,
+        &state.enemies,
+        &state.ghosts,
+    );
+}
+
+/// Store start-of-tick positions (rendering lerps between these and the new positions)
+fn snapshot_positions(state: &mut GameState) {
+    state.player.prev_pos = state.player.pos;
+
+    for enemy in &mut state.enemies {
+        enemy.prev_pos = enemy.pos;
+    }
+    for ghost in &mut state.ghosts {
+        ghost.prev_pos = ghost.pos;
+    }
+    for projectile in &mut state.projectiles {
+        projectile.prev_pos = projectile.pos;
+    }
+    for beam in &mut state.beams {
+        beam.prev_origin = beam.origin;
+    }
+    for pickup in &mut state.pickups {
+        pickup.prev_pos = pickup.pos;
+    }
+}
+
 /// Update wave system (replaces random spawning)
 fn update_wave_system(state: &mut GameState, delta: f32) {
     if state.config.spawning.wave_mode {
@@ -100,7 +191,9 @@ fn update_wave_system(state: &mut GameState, delta: f32) {
             wave_manager.start_next_wave(&config);
         }
 
-        // Update wave state
+        // Update wave state (callbacks see this tick's state through the `game` table)
+        wave_manager.scripting.refresh_view(state);
+        let previous_state = wave_manager.state;
         let enemies_alive = state.enemies.len();
         wave_manager.update_state(
             enemies_alive,
@@ -109,13 +202,39 @@ fn update_wave_system(state: &mut GameState, delta: f32) {
             delta,
         );
 
+        // Report wave transitions
+        let wave = wave_manager.current_wave_number;
+        match (previous_state, wave_manager.state) {
+            (WaveState::Preparing, WaveState::Active) => {
+                emit(state, GameEvent::WaveStarted { wave })
+            }
+            (WaveState::Active, WaveState::Complete) => {
+                emit(state, GameEvent::WaveCompleted { wave })
+            }
+            _ => {}
+        }
+
         // Spawn enemies for active wave
         wave_manager.spawn_for_wave(state, delta);
 
-        // Put wave_manager back
+        // Put wave_manager back, then apply what the callbacks asked for
+        let commands = wave_manager.scripting.take_commands();
         state.wave_manager = wave_manager;
+        script_commands::apply_script_commands(state, commands);
     } else {
         // Random enemy spawning for testing
         spawn_enemies(state, delta);
     }
+
+    // Lua event hooks (wave on_* fields, game.on handlers) - their commands apply right away
+    state.wave_manager.scripting.refresh_view(state);
+    let tick_interval = state.config.spawning.tick_hook_interval;
+    state.wave_manager.run_event_hooks(
+        &state.events,
+        &state.config.archetypes,
+        delta,
+        tick_interval,
+    );
+    let commands = state.wave_manager.scripting.take_commands();
+    script_commands::apply_script_commands(state, commands);
 }
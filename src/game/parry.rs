@@ -1,5 +1,4 @@
-use crate::game::particles::spawn_parry_effect;
-use crate::game::screen_shake::shake_on_parry;
+use crate::game::events::{GameEvent, emit};
 use crate::models::*;
 use macroquad::prelude::*;
 
@@ -44,7 +43,7 @@ pub fn update_parry(state: &mut GameState, delta: f32) {
             // Trigger failed parry animation (shrink + desaturation)
             state.player.parry_failed_timer = state.config.animations.parry_failed_duration;
 
-            println!("✘ Parry window missed!");
+            emit(state, GameEvent::ParryMissed);
         }
     }
 
@@ -76,9 +75,12 @@ pub fn check_parry_projectiles(state: &mut GameState) {
 
     // Check enemy projectiles near player
     for projectile in &mut state.projectiles {
-        // Only MISSILES can be parried (slow-moving, visible, high-skill reward)
+        // Only parryable weapons (missiles: slow-moving, visible, high-skill reward)
         if projectile.owner == ProjectileOwner::Enemy
-            && projectile.weapon_type == WeaponType::Missile
+            && projectile
+                .weapon_type
+                .config(&state.config.weapons)
+                .parryable
         {
             let dx = projectile.pos.x - state.player.pos.x;
             let dy = projectile.pos.y - state.player.pos.y;
@@ -90,9 +92,10 @@ pub fn check_parry_projectiles(state: &mut GameState) {
                 projectile.velocity.x *= -1.5; // Reverse and boost speed
                 projectile.velocity.y *= -1.5;
 
-                // Re-enable homing for parried missile (will track nearest enemy)
-                projectile.homing = true;
-                projectile.locked_target_index = None; // Find new target (nearest enemy)
+                // Homing shots switch to player tuning and retarget (nearest enemy)
+                let weapon = projectile.weapon_type.config(&state.config.weapons);
+                projectile.homing = crate::game::homing_for(weapon, ProjectileOwner::Player);
+                projectile.target = None; // Find new target (nearest enemy)
 
                 parried_count += 1;
             }
@@ -101,7 +104,6 @@ pub fn check_parry_projectiles(state: &mut GameState) {
 
     // If successful parry, deactivate and set cooldown
     if parried_count > 0 {
-        println!("✔ Parry ({parried_count} projectiles deflected)");
         state.player.parry_active = false;
         state.player.parry_cooldown = state.config.player.parry_cooldown;
 
@@ -114,8 +116,12 @@ pub fn check_parry_projectiles(state: &mut GameState) {
             state.player.parry_stance_glow_timer = burst_duration; // Extend to at least bounce duration
         }
 
-        shake_on_parry(state);
-        spawn_parry_effect(state, state.player.pos); // Particle burst!
-        // TODO: Sound effect
+        emit(
+            state,
+            GameEvent::ParrySuccess {
+                pos: state.player.pos,
+                deflected: parried_count,
+            },
+        );
     }
 }
@@ -1,14 +1,54 @@
+use crate::game::events::GameEvent;
 use crate::models::*;
 use macroquad::prelude::*;
 
+/// Event subscriber - bursts for deaths, hits, crits, parries, splits, chain arcs, shield breaks and grazes
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    // Splitter archetypes also get a split burst
+    let split_count = |state: &GameState, entity_type: EntityType| {
+        let archetype = entity_type.archetype(&state.config.archetypes);
+        archetype.splitter.as_ref().map(|s| s.split_count)
+    };
+
+    match *event {
+        GameEvent::EnemyKilled {
+            entity_type, pos, ..
+        } => {
+            spawn_death_explosion(state, pos);
+            if let Some(count) = split_count(state, entity_type) {
+                crate::game::splitter::spawn_split_particles(state, pos, count, false);
+            }
+        }
+        GameEvent::GhostKilled { entity_type, pos } => {
+            if let Some(count) = split_count(state, entity_type) {
+                crate::game::splitter::spawn_split_particles(state, pos, count, true);
+            }
+        }
+        GameEvent::PlayerHit { pos, .. } => spawn_player_hit_effect(state, pos),
+        GameEvent::EnemyHit { weapon, pos } | GameEvent::BombDetonated { weapon, pos, .. } => {
+            spawn_weapon_particles(state, pos, weapon)
+        }
+        GameEvent::CriticalHit { weapon, pos, .. } => spawn_crit_burst(state, pos, weapon),
+        GameEvent::ChainJump { weapon, from, to } => spawn_chain_arc(state, from, to, weapon),
+        GameEvent::ParrySuccess { pos, .. } => spawn_parry_effect(state, pos),
+        GameEvent::ShieldBroken { pos } => spawn_shield_break(state, pos),
+        GameEvent::Graze { pos } => spawn_graze_spark(state, pos),
+        _ => {}
+    }
+}
+
 /// Spawn explosion particles at position
 pub fn spawn_explosion(state: &mut GameState, pos: Position, count: usize, color: Color) {
     let cfg = &state.config.particles;
 
     for _ in 0..count {
-        let angle = rand::gen_range(0.0, std::f32::consts::PI * 2.0);
-        let speed = rand::gen_range(cfg.explosion_speed_min, cfg.explosion_speed_max);
-        let lifetime = rand::gen_range(cfg.explosion_lifetime_min, cfg.explosion_lifetime_max);
+        let angle = state.fx_rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+        let speed = state
+            .fx_rng
+            .gen_range(cfg.explosion_speed_min, cfg.explosion_speed_max);
+        let lifetime = state
+            .fx_rng
+            .gen_range(cfg.explosion_lifetime_min, cfg.explosion_lifetime_max);
 
         let particle = Particle {
             pos,
@@ -16,7 +56,9 @@ pub fn spawn_explosion(state: &mut GameState, pos: Position, count: usize, color
             lifetime,
             max_lifetime: cfg.explosion_lifetime_max,
             color,
-            size: rand::gen_range(cfg.explosion_size_min, cfg.explosion_size_max),
+            size: state
+                .fx_rng
+                .gen_range(cfg.explosion_size_min, cfg.explosion_size_max),
             size_decay: cfg.size_decay,
         };
 
@@ -25,24 +67,30 @@ pub fn spawn_explosion(state: &mut GameState, pos: Position, count: usize, color
 }
 
 /// Spawn hit sparks at position (directional)
-pub fn spawn_hit_sparks(state: &mut GameState, pos: Position, direction: Position) {
+pub fn spawn_hit_sparks(state: &mut GameState, pos: Position, direction: Position, color: Color) {
     let cfg = &state.config.particles;
 
     for _ in 0..cfg.spark_count {
         // Particles fly in reverse direction from impact
-        let angle_offset = rand::gen_range(-0.5, 0.5);
+        let angle_offset = state.fx_rng.gen_range(-0.5, 0.5);
         let base_angle = (-direction.y).atan2(-direction.x);
         let angle = base_angle + angle_offset;
-        let speed = rand::gen_range(cfg.spark_speed_min, cfg.spark_speed_max);
-        let lifetime = rand::gen_range(cfg.spark_lifetime_min, cfg.spark_lifetime_max);
+        let speed = state
+            .fx_rng
+            .gen_range(cfg.spark_speed_min, cfg.spark_speed_max);
+        let lifetime = state
+            .fx_rng
+            .gen_range(cfg.spark_lifetime_min, cfg.spark_lifetime_max);
 
         let particle = Particle {
             pos,
             velocity: Vec2::new(angle.cos() * speed, angle.sin() * speed),
             lifetime,
             max_lifetime: cfg.spark_lifetime_max,
-            color: YELLOW,
-            size: rand::gen_range(cfg.spark_size_min, cfg.spark_size_max),
+            color,
+            size: state
+                .fx_rng
+                .gen_range(cfg.spark_size_min, cfg.spark_size_max),
             size_decay: cfg.size_decay,
         };
 
@@ -50,39 +98,100 @@ pub fn spawn_hit_sparks(state: &mut GameState, pos: Position, direction: Positio
     }
 }
 
-/// Spawn weapon-specific particles
+/// Spawn weapon-specific impact particles
 pub fn spawn_weapon_particles(state: &mut GameState, pos: Position, weapon_type: WeaponType) {
+    let weapon = weapon_type.config(&state.config.weapons);
+    let [r, g, b] = weapon.hit_color;
+    let color = Color::new(r, g, b, 1.0);
+    let count = weapon.hit_particles;
+
+    if count == 0 {
+        // Small directional sparks
+        spawn_hit_sparks(state, pos, Vec2::new(0.0, -1.0), color);
+    } else {
+        spawn_explosion(state, pos, count, color);
+    }
+}
+
+/// Spawn a white-hot ring of sparks on a critical hit
+pub fn spawn_crit_burst(state: &mut GameState, pos: Position, weapon_type: WeaponType) {
+    let [r, g, b] = weapon_type.config(&state.config.weapons).hit_color;
+    let count = state.config.particles.spark_count * 2;
+    spawn_explosion(state, pos, count, WHITE);
+    spawn_explosion(state, pos, count, Color::new(r, g, b, 1.0));
+}
+
+/// Spawn a couple of faint sparks where a projectile grazed the player
+pub fn spawn_graze_spark(state: &mut GameState, pos: Position) {
+    let lifetime = state.config.particles.spark_lifetime_max * 0.5;
+    let size_decay = state.config.particles.size_decay;
+
+    for _ in 0..3 {
+        let angle = state.fx_rng.gen_range(0.0, std::f32::consts::TAU);
+        let speed = state.fx_rng.gen_range(30.0, 80.0);
+        state.particles.push(Particle {
+            pos,
+            velocity: Vec2::from_angle(angle) * speed,
+            lifetime,
+            max_lifetime: lifetime,
+            color: Color::new(0.7, 0.9, 1.0, 0.6),
+            size: 1.5,
+            size_decay,
+        });
+    }
+}
+
+/// Spawn a ring of shield shards flying outward
+pub fn spawn_shield_break(state: &mut GameState, pos: Position) {
     let cfg = &state.config.particles;
+    let (lifetime, size, size_decay) = (cfg.spark_lifetime_max, cfg.spark_size_max, cfg.size_decay);
+    let count = cfg.spark_count * 2;
 
-    // Extract config values before calling spawn functions (borrow checker)
-    let laser_count = cfg.laser_particle_count;
-    let missile_count = cfg.missile_particle_count;
-    let plasma_count = cfg.plasma_particle_count;
-    let bomb_red_count = cfg.bomb_red_particle_count;
-    let bomb_orange_count = cfg.bomb_orange_particle_count;
-
-    match weapon_type {
-        WeaponType::Bullet => {
-            // Small yellow sparks
-            spawn_hit_sparks(state, pos, Vec2::new(0.0, -1.0));
-        }
-        WeaponType::Laser => {
-            // Cyan energy burst
-            spawn_explosion(state, pos, laser_count, SKYBLUE);
-        }
-        WeaponType::Missile => {
-            // Orange explosion
-            spawn_explosion(state, pos, missile_count, ORANGE);
-        }
-        WeaponType::Plasma => {
-            // Purple energy
-            spawn_explosion(state, pos, plasma_count, PURPLE);
-        }
-        WeaponType::Bombs => {
-            // HUGE red/orange explosion
-            spawn_explosion(state, pos, bomb_red_count, RED);
-            spawn_explosion(state, pos, bomb_orange_count, ORANGE);
-        }
+    for i in 0..count {
+        let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+        let speed = state.fx_rng.gen_range(120.0, 220.0);
+        state.particles.push(Particle {
+            pos: pos + Vec2::from_angle(angle) * 22.0, // Start on the shield's rim
+            velocity: Vec2::from_angle(angle) * speed,
+            lifetime,
+            max_lifetime: lifetime,
+            color: Color::new(0.4, 0.8, 1.0, 1.0), // Shield blue
+            size,
+            size_decay,
+        });
+    }
+}
+
+/// Spawn a jagged line of sparks between two chained enemies
+pub fn spawn_chain_arc(
+    state: &mut GameState,
+    from: Position,
+    to: Position,
+    weapon_type: WeaponType,
+) {
+    let cfg = &state.config.particles;
+    let [r, g, b] = weapon_type.config(&state.config.weapons).hit_color;
+    let color = Color::new(r, g, b, 1.0);
+    let (lifetime, size, size_decay) = (cfg.spark_lifetime_max, cfg.spark_size_max, cfg.size_decay);
+
+    // One spark every ~8 px along the jump
+    let steps = ((from.distance(to) / 8.0) as usize).max(1);
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        let jitter = Vec2::new(
+            state.fx_rng.gen_range(-4.0, 4.0),
+            state.fx_rng.gen_range(-4.0, 4.0),
+        );
+
+        state.particles.push(Particle {
+            pos: from.lerp(to, t) + jitter,
+            velocity: jitter * 5.0,
+            lifetime,
+            max_lifetime: lifetime,
+            color,
+            size,
+            size_decay,
+        });
     }
 }
 
@@ -118,16 +227,16 @@ pub fn spawn_parry_effect(state: &mut GameState, pos: Position) {
 pub fn spawn_player_hit_effect(state: &mut GameState, pos: Position) {
     // Red impact burst (damage indication)
     for _ in 0..8 {
-        let angle = rand::gen_range(0.0, std::f32::consts::PI * 2.0);
-        let speed = rand::gen_range(50.0, 120.0);
+        let angle = state.fx_rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+        let speed = state.fx_rng.gen_range(50.0, 120.0);
 
         let particle = Particle {
             pos,
             velocity: Vec2::new(angle.cos() * speed, angle.sin() * speed),
-            lifetime: rand::gen_range(0.2, 0.4),
+            lifetime: state.fx_rng.gen_range(0.2, 0.4),
             max_lifetime: 0.4,
             color: RED,
-            size: rand::gen_range(4.0, 8.0),
+            size: state.fx_rng.gen_range(4.0, 8.0),
             size_decay: 0.9,
         };
 
@@ -136,16 +245,16 @@ pub fn spawn_player_hit_effect(state: &mut GameState, pos: Position) {
 
     // Orange warning particles
     for _ in 0..6 {
-        let angle = rand::gen_range(0.0, std::f32::consts::PI * 2.0);
-        let speed = rand::gen_range(70.0, 150.0);
+        let angle = state.fx_rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+        let speed = state.fx_rng.gen_range(70.0, 150.0);
 
         let particle = Particle {
             pos,
             velocity: Vec2::new(angle.cos() * speed, angle.sin() * speed),
-            lifetime: rand::gen_range(0.15, 0.35),
+            lifetime: state.fx_rng.gen_range(0.15, 0.35),
             max_lifetime: 0.35,
             color: ORANGE,
-            size: rand::gen_range(3.0, 6.0),
+            size: state.fx_rng.gen_range(3.0, 6.0),
             size_decay: 0.85,
         };
 
@@ -159,8 +268,8 @@ pub fn spawn_dash_trail(state: &mut GameState, pos: Position) {
 
     // Spawn multiple small blue particles as afterimage
     for _ in 0..dash_cfg.trail_particle_count {
-        let angle = macroquad::rand::gen_range(0.0, std::f32::consts::TAU);
-        let speed = macroquad::rand::gen_range(5.0, 15.0);
+        let angle = state.fx_rng.gen_range(0.0, std::f32::consts::TAU);
+        let speed = state.fx_rng.gen_range(5.0, 15.0);
 
         state.particles.push(Particle {
             pos,
@@ -176,7 +285,8 @@ pub fn spawn_dash_trail(state: &mut GameState, pos: Position) {
 
 /// Update all particles (movement, lifetime, cleanup)
 pub fn update_particles(state: &mut GameState, delta: f32) {
-    let friction = state.config.particles.friction;
+    // Friction is tuned per 60 Hz frame, scale it to the tick length
+    let friction = state.config.particles.friction.powf(delta * 60.0);
 
     // Update particles
     for particle in &mut state.particles {
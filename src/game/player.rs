@@ -23,14 +23,11 @@ pub fn update_player(state: &mut GameState, delta: f32) {
     }
 
     // Keep player on screen
-    state.player.pos.x = state.player.pos.x.clamp(15.0, screen_width() - 15.0);
+    let arena = state.arena;
+    state.player.pos.x = state.player.pos.x.clamp(15.0, arena.x - 15.0);
 
     // Player can only move in bottom half of screen
-    state.player.pos.y = state
-        .player
-        .pos
-        .y
-        .clamp(screen_height() / 2.0, screen_height() - 15.0);
+    state.player.pos.y = state.player.pos.y.clamp(arena.y / 2.0, arena.y - 15.0);
 
     // Update hit flash timer
     if state.player.hit_flash_timer > 0.0 {
@@ -49,8 +46,9 @@ fn apply_physics_movement(state: &mut GameState, delta: f32) {
     let cfg = &state.config.player;
     let input = &state.player.input_direction;
 
-    // Calculate target velocity based on input
-    let target_velocity = Vec2::new(input.x * cfg.movement_speed, input.y * cfg.movement_speed);
+    // Calculate target velocity based on input (slowed by status effects, zero when stunned)
+    let speed = cfg.movement_speed * state.player.status.time_scale();
+    let target_velocity = Vec2::new(input.x * speed, input.y * speed);
 
     // Apply acceleration force towards target velocity (responsiveness controls turn speed)
     let acceleration_force = Vec2::new(
@@ -62,9 +60,10 @@ fn apply_physics_movement(state: &mut GameState, delta: f32) {
     state.player.velocity.x += acceleration_force.x * delta;
     state.player.velocity.y += acceleration_force.y * delta;
 
-    // Apply friction (momentum decay)
-    state.player.velocity.x *= cfg.friction;
-    state.player.velocity.y *= cfg.friction;
+    // Apply friction (momentum decay, scaled so tick rate doesn't change feel)
+    let friction = cfg.friction.powf(delta * 60.0);
+    state.player.velocity.x *= friction;
+    state.player.velocity.y *= friction;
 
     // Move player by velocity
     state.player.pos.x += state.player.velocity.x * delta;
@@ -116,3 +115,31 @@ fn update_dash(state: &mut GameState, delta: f32) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hold right for `seconds` at `tick_rate`, return where the player ends up
+    fn run_right(tick_rate: f32, seconds: f32) -> Position {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.player.pos = Position::new(100.0, 450.0);
+        state.player.last_pos = state.player.pos;
+        state.player.input_direction = Vec2::X;
+
+        let delta = 1.0 / tick_rate;
+        for _ in 0..(seconds * tick_rate).round() as usize {
+            update_player(&mut state, delta);
+        }
+        state.player.pos
+    }
+
+    #[test]
+    fn test_movement_is_tick_rate_independent() {
+        let at_60 = run_right(60.0, 0.5);
+        let at_120 = run_right(120.0, 0.5);
+        assert!(at_60.x > 150.0); // Actually moved
+        assert!((at_60.x - at_120.x).abs() < 3.0, "{at_60} vs {at_120}");
+        assert_eq!(at_60.y, at_120.y);
+    }
+}
@@ -0,0 +1,140 @@
+//! Seeded random number generation
+//!
+//! Gameplay systems draw from `GameState::rng`, cosmetic systems (particles,
+//! screen shake) from `GameState::fx_rng`. Same seed + same inputs = same run.
+
+/// Small deterministic PRNG (SplitMix64)
+#[derive(Clone, Debug)]
+pub struct GameRng {
+    state: u64,
+}
+
+impl GameRng {
+    /// Create generator from a seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Derive an independent stream from a seed (e.g. cosmetic vs gameplay)
+    pub fn new_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Self::new(seed ^ stream.wrapping_mul(0xA24B_AED4_963E_E407));
+        rng.next_u64(); // Mix once so nearby seeds diverge immediately
+        rng
+    }
+
+    /// Seed from the system clock (used when no seed is configured)
+    pub fn seed_from_time() -> u64 {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64 & i64::MAX as u64) // Fits a TOML integer (replay files)
+            .unwrap_or(0x5EED)
+    }
+
+    /// Next raw 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        // Top 24 bits fit exactly in an f32 mantissa
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Random value in [low, high) - same contract as macroquad's `rand::gen_range`
+    pub fn gen_range<T: RngRange>(&mut self, low: T, high: T) -> T {
+        T::gen_range(self, low, high)
+    }
+}
+
+/// Types that can be sampled with `GameRng::gen_range`
+pub trait RngRange: Sized {
+    fn gen_range(rng: &mut GameRng, low: Self, high: Self) -> Self;
+}
+
+impl RngRange for f32 {
+    fn gen_range(rng: &mut GameRng, low: Self, high: Self) -> Self {
+        low + (high - low) * rng.next_f32()
+    }
+}
+
+impl RngRange for i32 {
+    fn gen_range(rng: &mut GameRng, low: Self, high: Self) -> Self {
+        if high <= low {
+            return low;
+        }
+        let span = (high as i64 - low as i64) as u64;
+        (low as i64 + (rng.next_u64() % span) as i64) as i32
+    }
+}
+
+impl RngRange for usize {
+    fn gen_range(rng: &mut GameRng, low: Self, high: Self) -> Self {
+        if high <= low {
+            return low;
+        }
+        low + (rng.next_u64() % (high - low) as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = GameRng::new(42);
+        let mut b = GameRng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let mut gameplay = GameRng::new_stream(42, 0);
+        let mut cosmetic = GameRng::new_stream(42, 1);
+
+        assert_ne!(gameplay.next_u64(), cosmetic.next_u64());
+    }
+
+    #[test]
+    fn test_gen_range_bounds() {
+        let mut rng = GameRng::new(7);
+
+        for _ in 0..1000 {
+            let f = rng.gen_range(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&f));
+
+            let i = rng.gen_range(0, 3);
+            assert!((0..3).contains(&i));
+
+            let u = rng.gen_range(2usize, 5usize);
+            assert!((2..5).contains(&u));
+        }
+    }
+
+    #[test]
+    fn test_config_seed_applies_on_reload() {
+        use crate::models::GameState;
+
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let mut config = state.config.clone();
+        config.simulation.seed = 42;
+        state.apply_config(&config);
+        assert_eq!(state.seed, 42);
+        assert_eq!(state.rng.next_u64(), GameRng::new_stream(42, 0).next_u64());
+
+        // Reloading the same seed leaves the running streams alone
+        let next = state.rng.clone().next_u64();
+        state.apply_config(&config);
+        assert_eq!(state.rng.next_u64(), next);
+    }
+}
@@ -1,5 +1,5 @@
+use crate::game::events::GameEvent;
 use crate::models::*;
-use macroquad::prelude::*;
 
 /// Trigger screen shake with specific duration and intensity
 pub fn trigger_shake(state: &mut GameState, duration: f32, intensity: f32) {
@@ -7,7 +7,7 @@ pub fn trigger_shake(state: &mut GameState, duration: f32, intensity: f32) {
     state.screen_shake_intensity = intensity;
 }
 
-/// Update screen shake (decay over time)
+/// Update screen shake (decay over time, pick this tick's offset)
 pub fn update_shake(state: &mut GameState, delta: f32) {
     if state.screen_shake_duration > 0.0 {
         state.screen_shake_duration -= delta;
@@ -16,19 +16,36 @@ pub fn update_shake(state: &mut GameState, delta: f32) {
             state.screen_shake_intensity = 0.0;
         }
     }
-}
 
-/// Get camera offset based on current shake
-pub fn get_shake_offset(state: &GameState) -> (f32, f32) {
     if state.screen_shake_duration <= 0.0 {
-        return (0.0, 0.0);
+        state.screen_shake_offset = (0.0, 0.0);
+        return;
     }
 
-    // Random offset within intensity range
-    let offset_x = rand::gen_range(-state.screen_shake_intensity, state.screen_shake_intensity);
-    let offset_y = rand::gen_range(-state.screen_shake_intensity, state.screen_shake_intensity);
+    // Random offset within intensity range (cosmetic stream)
+    let intensity = state.screen_shake_intensity;
+    let offset_x = state.fx_rng.gen_range(-intensity, intensity);
+    let offset_y = state.fx_rng.gen_range(-intensity, intensity);
+
+    state.screen_shake_offset = (offset_x, offset_y);
+}
+
+/// Get camera offset based on current shake
+pub fn get_shake_offset(state: &GameState) -> (f32, f32) {
+    state.screen_shake_offset
+}
 
-    (offset_x, offset_y)
+/// Event subscriber - shake on kills, hits and parries
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    match *event {
+        GameEvent::EnemyKilled { .. } => shake_on_enemy_death(state),
+        GameEvent::PlayerHit { .. } => shake_on_player_hit(state),
+        GameEvent::EnemyHit { weapon, .. } | GameEvent::BombDetonated { weapon, .. } => {
+            shake_on_weapon_hit(state, weapon)
+        }
+        GameEvent::ParrySuccess { .. } => shake_on_parry(state),
+        _ => {}
+    }
 }
 
 /// Trigger shake on enemy death
@@ -52,14 +69,7 @@ pub fn shake_on_player_hit(state: &mut GameState) {
 /// Trigger weapon-specific shake on hit (when player/ghost hits enemy)
 pub fn shake_on_weapon_hit(state: &mut GameState, weapon_type: WeaponType) {
     let cfg = &state.config.screen_shake;
-
-    let intensity = match weapon_type {
-        WeaponType::Bullet => cfg.bullet_hit_intensity,
-        WeaponType::Laser => cfg.laser_hit_intensity,
-        WeaponType::Missile => cfg.missile_hit_intensity,
-        WeaponType::Plasma => cfg.plasma_hit_intensity,
-        WeaponType::Bombs => cfg.bomb_hit_intensity,
-    };
+    let intensity = weapon_type.config(&state.config.weapons).shake_intensity;
 
     trigger_shake(state, cfg.weapon_hit_duration, intensity);
 }
@@ -0,0 +1,251 @@
+//! Lua `game` API - applies the commands scripts queued during a callback
+//!
+//! Scripts never touch `GameState` directly: queries read a snapshot (`ScriptView`) and
+//! actions are queued as `ScriptCommand`s, which run here once the callback has returned.
+
+use crate::game::events::{GameEvent, emit};
+use crate::game::screen_shake::trigger_shake;
+use crate::game::utils::biased_random_x;
+use crate::game::wave::spawn_enemy_on_path;
+use crate::models::*;
+use crate::scripting::ScriptCommand;
+use serde::de::{DeserializeOwned, IntoDeserializer, value};
+
+/// Run queued script commands against the live state (bad names are logged and skipped)
+pub fn apply_script_commands(state: &mut GameState, commands: Vec<ScriptCommand>) {
+    for command in commands {
+        match command {
+            ScriptCommand::SpawnEnemy {
+                enemy_type,
+                x,
+                path,
+            } => {
+                let Some(entity_type) =
+                    EntityType::from_name(&enemy_type, &state.config.archetypes)
+                else {
+                    println!("✘ game.spawn_enemy: unknown enemy type '{enemy_type}'");
+                    continue;
+                };
+                let entry_path = match path {
+                    Some(name) => match parse_name(&name) {
+                        Some(entry_path) => entry_path,
+                        None => {
+                            println!("✘ game.spawn_enemy: unknown path '{name}'");
+                            continue;
+                        }
+                    },
+                    None => entity_type.archetype(&state.config.archetypes).entry_path,
+                };
+                let x = match x {
+                    Some(x) => x.clamp(0.0, state.arena.x),
+                    None => biased_random_x(&mut state.rng, 50.0, state.arena.x - 50.0),
+                };
+                spawn_enemy_on_path(state, entity_type, x, entry_path);
+            }
+
+            ScriptCommand::SetFormation(name) => {
+                let Some(formation) = parse_name::<GhostFormation>(&name) else {
+                    println!("✘ game.set_formation: unknown formation '{name}'");
+                    continue;
+                };
+                // Same rule as the keyboard: the queued ghosts have to fill it
+                let ghost_count = state.player.available_ghosts.len();
+                if !formation.is_valid_for_count(ghost_count) {
+                    println!(
+                        "✘ game.set_formation: not enough ghosts for {formation:?} ({ghost_count})"
+                    );
+                    continue;
+                }
+                if state.ghost_formation != formation {
+                    state.ghost_formation = formation;
+                    emit(state, GameEvent::FormationChanged { formation });
+                }
+            }
+
+            ScriptCommand::GrantEnergy(amount) => {
+                let player = &mut state.player;
+                player.energy = (player.energy + amount).clamp(0.0, player.max_energy);
+            }
+
+            ScriptCommand::Shake {
+                duration,
+                intensity,
+            } => trigger_shake(state, duration, intensity),
+        }
+    }
+}
+
+/// Enum from its config/serde name ("Circle", "Swoop", ...)
+fn parse_name<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let deserializer: value::StrDeserializer<value::Error> = name.into_deserializer();
+    T::deserialize(deserializer).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripting::LuaScripting;
+
+    #[test]
+    fn test_lua_game_api_queues_and_applies_commands() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.player.energy = 10.0;
+        let scripting = LuaScripting::new().unwrap();
+        scripting.refresh_view(&state);
+
+        let (hp, count): (f32, usize) = scripting
+            .lua()
+            .load(
+                r#"
+                game.spawn_enemy("Tank", 200, "Heavy")
+                game.spawn_enemy("Nope", 100)
+                game.set_formation("Circle")
+                game.grant_energy(25)
+                game.shake(0.2, 5)
+                local hp = game.player_hp()
+                return hp, game.enemy_count()
+                "#,
+            )
+            .eval()
+            .unwrap();
+        assert_eq!(hp, state.player.stats.health);
+        assert_eq!(count, 0);
+        assert!(state.enemies.is_empty()); // Nothing happens until the queue is applied
+
+        apply_script_commands(&mut state, scripting.take_commands());
+        assert_eq!(state.enemies.len(), 1);
+        assert_eq!(state.ghost_formation, GhostFormation::Line); // No ghosts queued for a Circle
+        assert_eq!(state.player.energy, 35.0);
+        assert!(state.screen_shake_duration > 0.0);
+        assert!(scripting.take_commands().is_empty());
+
+        let basic = EntityType::from_name("BasicFighter", &state.config.archetypes).unwrap();
+        let needed = GhostFormation::Circle.min_ghost_count();
+        state.player.available_ghosts = vec![basic; needed];
+        scripting
+            .lua()
+            .load(r#"game.set_formation("Circle")"#)
+            .exec()
+            .unwrap();
+        apply_script_commands(&mut state, scripting.take_commands());
+        assert_eq!(state.ghost_formation, GhostFormation::Circle);
+    }
+
+    #[test]
+    fn test_timeline_waits_runs_parallel_and_waits_for_clear() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let scripting = LuaScripting::new().unwrap();
+        let run: mlua::Function = scripting
+            .lua()
+            .load(
+                r#"
+                function()
+                    spawn("BasicFighter", 100)
+                    wait(1.0)
+                    parallel {
+                        function() spawn("Tank") end,
+                        function() wait(0.5) spawn("Sniper") end,
+                    }
+                    wait_until_clear()
+                    spawn("Elite", 400)
+                end
+                "#,
+            )
+            .eval()
+            .unwrap();
+        let mut timeline = scripting.start_timeline(run).unwrap();
+
+        // One tick the way the wave manager drives it
+        let mut tick = |state: &mut GameState| {
+            scripting.refresh_view(state);
+            let done = scripting.resume_timeline(&mut timeline, 0.25).unwrap();
+            apply_script_commands(state, scripting.take_commands());
+            done
+        };
+
+        for _ in 0..4 {
+            assert!(!tick(&mut state));
+        }
+        assert_eq!(state.enemies.len(), 1); // Still waiting out the first second
+        assert!(!tick(&mut state));
+        assert_eq!(state.enemies.len(), 2); // Tank right away...
+        for _ in 0..5 {
+            assert!(!tick(&mut state));
+        }
+        assert_eq!(state.enemies.len(), 3); // ...Sniper half a second later, then waits for clear
+
+        state.enemies.clear();
+        assert!(tick(&mut state));
+        let elite = EntityType::from_name("Elite", &state.config.archetypes).unwrap();
+        assert_eq!(state.enemies.len(), 1);
+        assert_eq!(state.enemies[0].entity_type, elite);
+    }
+
+    #[test]
+    fn test_crossfire_timeline_wave_runs_to_completion() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.config.spawning.wave_mode = true;
+        state.wave_manager.current_wave_number = 5;
+        assert!(state.wave_manager.start_next_wave(&state.config));
+
+        // Every spawn is shot down on the next tick
+        let mut spawned = 0;
+        let input = crate::game::InputState::default();
+        for _ in 0..120 * 60 {
+            spawned += state.enemies.len();
+            state.enemies.clear();
+            crate::game::update_all_systems(&mut state, &input, 1.0 / 120.0);
+            if state.wave_manager.waves_cleared == 1 {
+                break;
+            }
+        }
+        assert_eq!(state.wave_manager.waves_cleared, 1);
+        assert_eq!(spawned, 3 + 8 + 2 + 3 + 3 + 1); // Snipers, the parallel groups, Tanks, Elite
+    }
+
+    #[test]
+    fn test_event_hooks_run_and_script_errors_are_isolated() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.player.energy = 0.0;
+        let lua = state.wave_manager.scripting.lua().clone();
+        lua.load(
+            r#"
+            kills = {}
+            game.on("enemy_killed", function(kind, x, y, killer)
+                table.insert(kills, kind .. ":" .. tostring(killer))
+                game.grant_energy(5)
+            end)
+            game.on("enemy_killed", function() error("boom") end)
+            ticks = 0
+            game.on("tick", function(dt) ticks = ticks + 1 end)
+            "#,
+        )
+        .exec()
+        .unwrap();
+
+        let basic = EntityType::from_name("BasicFighter", &state.config.archetypes).unwrap();
+        let killed = |killer| GameEvent::EnemyKilled {
+            entity_type: basic,
+            pos: Position::ZERO,
+            killer,
+        };
+        let events = [killed(Some(ProjectileOwner::Ghost)), killed(None)];
+        for _ in 0..4 {
+            state
+                .wave_manager
+                .run_event_hooks(&events[..1], &state.config.archetypes, 0.125, 0.25);
+        }
+        state
+            .wave_manager
+            .run_event_hooks(&events[1..], &state.config.archetypes, 0.125, 0.25);
+        let commands = state.wave_manager.scripting.take_commands();
+        apply_script_commands(&mut state, commands);
+
+        let kills: Vec<String> = lua.globals().get("kills").unwrap();
+        assert_eq!(kills.len(), 5);
+        assert_eq!(kills[0], "BasicFighter:Ghost");
+        assert_eq!(kills[4], "BasicFighter:nil");
+        assert_eq!(state.player.energy, 25.0);
+        assert_eq!(lua.globals().get::<u32>("ticks").unwrap(), 2); // Every 0.25s of 0.625s
+    }
+}
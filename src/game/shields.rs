@@ -0,0 +1,198 @@
+//! Shields - a regenerating layer over the front of Tanks and Elites
+//!
+//! Hits arriving inside the archetype's arc drain the shield before health (`absorb`,
+//! called from the damage pipeline). Enemies face the player and ghosts face up, so
+//! shots from the side - ghosts in the Circle formation - get around it. A broken
+//! shield staggers its owner and recharges after `recharge_delay` without hits.
+
+use crate::config::{Stacking, StatusKind, StatusTrait};
+use crate::game::damage::{DamageInfo, DamageTarget, target_pos, target_stats};
+use crate::game::events::{GameEvent, emit};
+use crate::models::*;
+
+/// Ghost shields point up, toward the enemies
+pub const GHOST_SHIELD_FACING: Position = Position::NEG_Y;
+
+/// Enemy shields point at the player
+pub fn enemy_shield_facing(enemy_pos: Position, player_pos: Position) -> Position {
+    (player_pos - enemy_pos)
+        .try_normalize()
+        .unwrap_or(Position::Y)
+}
+
+fn shield_facing(state: &GameState, target: DamageTarget) -> Position {
+    match target {
+        DamageTarget::Enemy(index) => {
+            enemy_shield_facing(state.enemies[index].pos, state.player.pos)
+        }
+        DamageTarget::Player | DamageTarget::Ghost(_) => GHOST_SHIELD_FACING,
+    }
+}
+
+/// Whether a hit travelling along `direction` comes in through the shield's arc
+pub fn covers(facing: Position, direction: Position, arc: f32) -> bool {
+    if arc >= 360.0 || direction == Position::ZERO {
+        return true;
+    }
+    facing.angle_between(-direction).abs() <= arc.to_radians() / 2.0
+}
+
+/// Soak as much of a hit as the shield can (returns what goes on to health)
+pub fn absorb(state: &mut GameState, target: DamageTarget, info: &DamageInfo, amount: f32) -> f32 {
+    // Hits without a direction (burn ticks) go straight through
+    let Some(direction) = info.direction else {
+        return amount;
+    };
+    let entity_type = match target {
+        DamageTarget::Player => return amount, // The player has no shield
+        DamageTarget::Enemy(index) => state.enemies[index].entity_type,
+        DamageTarget::Ghost(index) => state.ghosts[index].entity_type,
+    };
+    let Some(cfg) = entity_type.archetype(&state.config.archetypes).shield else {
+        return amount;
+    };
+    if !covers(shield_facing(state, target), direction, cfg.arc) {
+        return amount;
+    }
+
+    let Some(shield) = target_stats(state, target).shield.as_mut() else {
+        return amount;
+    };
+    if shield.current <= 0.0 {
+        return amount; // Broken - still recharging
+    }
+    let absorbed = amount.min(shield.current);
+    shield.current -= absorbed;
+    shield.recharge_timer = cfg.recharge_delay;
+
+    if shield.current <= 0.0 {
+        let stagger = StatusTrait {
+            kind: StatusKind::Stun,
+            duration: cfg.break_stagger,
+            magnitude: 0.0,
+            stacking: Stacking::Refresh,
+            max_stacks: 1,
+        };
+        match target {
+            DamageTarget::Player => {}
+            DamageTarget::Enemy(index) => {
+                state.enemies[index]
+                    .status
+                    .apply(&stagger, info.weapon, info.source)
+            }
+            DamageTarget::Ghost(index) => {
+                state.ghosts[index]
+                    .status
+                    .apply(&stagger, info.weapon, info.source)
+            }
+        }
+        let pos = target_pos(state, target);
+        emit(state, GameEvent::ShieldBroken { pos });
+    }
+
+    amount - absorbed
+}
+
+/// Recharge delay and regeneration for every shielded enemy and ghost
+pub fn update_shields(state: &mut GameState, delta: f32) {
+    let archetypes = &state.config.archetypes;
+    let enemies = state
+        .enemies
+        .iter_mut()
+        .map(|e| (e.entity_type, &mut e.stats));
+    let ghosts = state
+        .ghosts
+        .iter_mut()
+        .map(|g| (g.entity_type, &mut g.stats));
+
+    for (entity_type, stats) in enemies.chain(ghosts) {
+        let Some(shield) = stats.shield.as_mut() else {
+            continue;
+        };
+        let Some(cfg) = &entity_type.archetype(archetypes).shield else {
+            continue;
+        };
+
+        if shield.recharge_timer > 0.0 {
+            shield.recharge_timer -= delta;
+            continue;
+        }
+        shield.current = (shield.current + cfg.regen_rate * delta).min(shield.max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::damage::apply_damage;
+
+    /// Unarmored Tank at `pos`, facing a player straight below it
+    fn spawn_tank(state: &mut GameState, pos: Position) -> usize {
+        let tank = EntityType::from_name("Tank", &state.config.archetypes).unwrap();
+        state.config.archetypes[tank.0 as usize].armor = 0.0;
+        state.player.pos = pos + Position::new(0.0, 400.0);
+        let id = state.entity_ids.next();
+        let enemy = Enemy::from_entity_type(id, tank, pos, &state.config);
+        state.enemies.push(enemy);
+        state.enemies.len() - 1
+    }
+
+    fn shot(state: &GameState, amount: f32, direction: Position) -> DamageInfo {
+        let laser = WeaponType::from_name("Laser", &state.config.weapons).unwrap();
+        DamageInfo::new(amount, laser, ProjectileOwner::Player).with_direction(direction)
+    }
+
+    #[test]
+    fn test_front_hits_are_absorbed_and_flank_hits_are_not() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let tank = spawn_tank(&mut state, Position::new(400.0, 100.0));
+        let health = state.enemies[tank].stats.health;
+
+        // Player shot travelling up hits the front
+        let hit = shot(&state, 30.0, Position::NEG_Y);
+        let dealt = apply_damage(&mut state, DamageTarget::Enemy(tank), hit);
+        assert_eq!(dealt, 0.0);
+        assert_eq!(state.enemies[tank].stats.health, health);
+        assert_eq!(state.enemies[tank].stats.shield.unwrap().current, 50.0);
+
+        // Ghost shot from the side gets around it
+        let hit = shot(&state, 30.0, Position::X);
+        let dealt = apply_damage(&mut state, DamageTarget::Enemy(tank), hit);
+        assert_eq!(dealt, 30.0);
+        assert_eq!(state.enemies[tank].stats.shield.unwrap().current, 50.0);
+    }
+
+    #[test]
+    fn test_break_staggers_then_recharges() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let tank = spawn_tank(&mut state, Position::new(400.0, 100.0));
+        let cfg = state.enemies[tank]
+            .entity_type
+            .archetype(&state.config.archetypes)
+            .shield
+            .unwrap();
+        let health = state.enemies[tank].stats.health;
+
+        // Overflow goes on to health
+        let hit = shot(&state, 100.0, Position::NEG_Y);
+        let dealt = apply_damage(&mut state, DamageTarget::Enemy(tank), hit);
+        assert_eq!(dealt, 100.0 - cfg.capacity);
+        assert_eq!(state.enemies[tank].stats.health, health - dealt);
+        assert!(state.enemies[tank].status.is_stunned());
+        assert!(matches!(
+            state.events.as_slice(),
+            [GameEvent::ShieldBroken { .. }]
+        ));
+
+        // Nothing until the recharge delay runs out, then regen up to capacity
+        update_shields(&mut state, cfg.recharge_delay * 0.5);
+        assert_eq!(state.enemies[tank].stats.shield.unwrap().current, 0.0);
+        for _ in 0..100 {
+            update_shields(&mut state, 0.5);
+        }
+        assert_eq!(
+            state.enemies[tank].stats.shield.unwrap().current,
+            cfg.capacity
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Uniform-grid spatial hash (broadphase for collisions, range and nearest queries)
+//!
+//! Rebuilt once per tick after entities move. Entries carry the Vec index of the
+//! entity for that tick, so the grid is stale once anything is removed (cleanup).
+
+use crate::models::{Enemy, EntityId, Ghost, Player, Position};
+use macroquad::math::Vec2;
+use std::collections::HashMap;
+
+/// Which list a grid entry points into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityKind {
+    Player,
+    Enemy,
+    Ghost,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GridEntry {
+    pub kind: EntityKind,
+    pub index: usize, // Index into state.enemies / state.ghosts (0 for the player)
+    pub id: EntityId,
+    pub pos: Position,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SpatialGrid {
+    cell_size: f32, // 0 = single cell (brute force)
+    cols: usize,
+    rows: usize,
+    cells: Vec<Vec<GridEntry>>,
+    by_id: HashMap<EntityId, GridEntry>,
+}
+
+impl SpatialGrid {
+    /// Rebuild from scratch (entities outside the arena are clamped into edge cells)
+    pub fn rebuild(
+        &mut self,
+        arena: Vec2,
+        cell_size: f32,
+        player: &Player,
+        enemies: &[Enemy],
+        ghosts: &[Ghost],
+    ) {
+        self.cell_size = cell_size;
+        if cell_size > 0.0 {
+            self.cols = ((arena.x / cell_size).ceil() as usize).max(1);
+            self.rows = ((arena.y / cell_size).ceil() as usize).max(1);
+        } else {
+            self.cols = 1;
+            self.rows = 1;
+        }
+
+        // Keep cell allocations between ticks
+        self.cells.resize_with(self.cols * self.rows, Vec::new);
+        self.cells.truncate(self.cols * self.rows);
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        self.by_id.clear();
+
+        self.insert(GridEntry {
+            kind: EntityKind::Player,
+            index: 0,
+            id: player.id,
+            pos: player.pos,
+        });
+        for (index, enemy) in enemies.iter().enumerate() {
+            self.insert(GridEntry {
+                kind: EntityKind::Enemy,
+                index,
+                id: enemy.id,
+                pos: enemy.pos,
+            });
+        }
+        for (index, ghost) in ghosts.iter().enumerate() {
+            self.insert(GridEntry {
+                kind: EntityKind::Ghost,
+                index,
+                id: ghost.id,
+                pos: ghost.pos,
+            });
+        }
+    }
+
+    fn insert(&mut self, entry: GridEntry) {
+        let (col, row) = self.cell_coords(entry.pos);
+        self.cells[row * self.cols + col].push(entry);
+        self.by_id.insert(entry.id, entry);
+    }
+
+    /// Cell containing a position (clamped to the grid)
+    fn cell_coords(&self, pos: Position) -> (usize, usize) {
+        if self.cell_size <= 0.0 {
+            return (0, 0);
+        }
+
+        let col = (pos.x / self.cell_size).floor().max(0.0) as usize;
+        let row = (pos.y / self.cell_size).floor().max(0.0) as usize;
+        (col.min(self.cols - 1), row.min(self.rows - 1))
+    }
+
+    /// Entry for a live entity (None if it wasn't alive at rebuild time)
+    pub fn find(&self, id: EntityId) -> Option<GridEntry> {
+        self.by_id.get(&id).copied()
+    }
+
+    /// Broadphase: every entry of `kind` whose cell overlaps the circle, in Vec order
+    /// Callers still do the exact distance test
+    pub fn query(&self, center: Position, radius: f32, kind: EntityKind) -> Vec<GridEntry> {
+        let mut found = Vec::new();
+        if self.cells.is_empty() {
+            return found;
+        }
+
+        let (min_col, min_row) = self.cell_coords(center - Vec2::splat(radius));
+        let (max_col, max_row) = self.cell_coords(center + Vec2::splat(radius));
+
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                found.extend(
+                    self.cells[row * self.cols + col]
+                        .iter()
+                        .filter(|entry| entry.kind == kind),
+                );
+            }
+        }
+
+        // Same order as iterating the Vec directly (keeps results identical to brute force)
+        found.sort_unstable_by_key(|entry| entry.index);
+        found
+    }
+
+    /// Nearest entry of `kind` (ties go to the lower Vec index)
+    pub fn nearest(&self, center: Position, kind: EntityKind) -> Option<GridEntry> {
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        let (center_col, center_row) = self.cell_coords(center);
+        let max_ring = self.cols.max(self.rows);
+        let mut best: Option<(f32, GridEntry)> = None;
+
+        // Search rings of cells outward from the center cell
+        for ring in 0..=max_ring {
+            for row in center_row.saturating_sub(ring)..=(center_row + ring).min(self.rows - 1) {
+                for col in center_col.saturating_sub(ring)..=(center_col + ring).min(self.cols - 1)
+                {
+                    // Only the ring's border cells are new
+                    if row.abs_diff(center_row) != ring && col.abs_diff(center_col) != ring {
+                        continue;
+                    }
+
+                    for entry in &self.cells[row * self.cols + col] {
+                        if entry.kind != kind {
+                            continue;
+                        }
+
+                        let dist = (entry.pos - center).length_squared();
+                        let better = match best {
+                            None => true,
+                            Some((best_dist, best_entry)) => {
+                                dist < best_dist
+                                    || (dist == best_dist && entry.index < best_entry.index)
+                            }
+                        };
+                        if better {
+                            best = Some((dist, *entry));
+                        }
+                    }
+                }
+            }
+
+            // Anything in the next ring is at least `ring * cell_size` away
+            if let Some((best_dist, _)) = best {
+                let reach = ring as f32 * self.cell_size;
+                if best_dist < reach * reach {
+                    break;
+                }
+            }
+        }
+
+        best.map(|(_, entry)| entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GameState;
+
+    fn state_with_enemies(positions: &[(f32, f32)]) -> GameState {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        for &(x, y) in positions {
+            let id = state.entity_ids.next();
+            let enemy = Enemy::from_entity_type(
+                id,
+                crate::models::EntityType(0),
+                Vec2::new(x, y),
+                &state.config,
+            );
+            state.enemies.push(enemy);
+        }
+        state
+    }
+
+    fn grid(state: &GameState, cell_size: f32) -> SpatialGrid {
+        let mut grid = SpatialGrid::default();
+        grid.rebuild(
+            state.arena,
+            cell_size,
+            &state.player,
+            &state.enemies,
+            &state.ghosts,
+        );
+        grid
+    }
+
+    #[test]
+    fn test_query_matches_brute_force() {
+        let state =
+            state_with_enemies(&[(10.0, 10.0), (100.0, 100.0), (400.0, 300.0), (-40.0, 5.0)]);
+        let grid = grid(&state, 64.0);
+
+        let hits: Vec<usize> = grid
+            .query(Vec2::new(20.0, 20.0), 20.0, EntityKind::Enemy)
+            .iter()
+            .filter(|e| (e.pos - Vec2::new(20.0, 20.0)).length() < 20.0)
+            .map(|e| e.index)
+            .collect();
+        assert_eq!(hits, vec![0]);
+
+        // Off-arena entities are clamped into edge cells, not lost
+        let edge = grid.query(Vec2::new(0.0, 0.0), 50.0, EntityKind::Enemy);
+        assert!(edge.iter().any(|e| e.index == 3));
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let state = state_with_enemies(&[(700.0, 500.0), (120.0, 90.0), (300.0, 40.0)]);
+
+        for cell_size in [0.0, 32.0, 64.0, 500.0] {
+            let grid = grid(&state, cell_size);
+            let nearest = grid.nearest(Vec2::new(100.0, 100.0), EntityKind::Enemy);
+            assert_eq!(nearest.map(|e| e.index), Some(1), "cell size {cell_size}");
+        }
+    }
+}
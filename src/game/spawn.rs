@@ -1,38 +1,49 @@
+use crate::config::EntryPath;
+use crate::game::rng::GameRng;
 use crate::game::utils::biased_random_x;
 use crate::models::*;
 use macroquad::prelude::*;
-use macroquad::rand::gen_range;
 
-/// Create a Bezier entry path based on enemy type
-pub fn create_wave_enemy_path(entity_type: EntityType, spawn_x: f32) -> EnemyMovementState {
-    let screen_w = screen_width();
-    let _screen_h = screen_height();
+/// Create a Bezier entry path for an archetype's entry style
+pub fn create_wave_enemy_path(
+    rng: &mut GameRng,
+    entry_path: EntryPath,
+    spawn_x: f32,
+    arena_width: f32,
+) -> EnemyMovementState {
+    let screen_w = arena_width;
 
-    match entity_type {
-        EntityType::BasicFighter => {
+    match entry_path {
+        EntryPath::Swoop => {
             // Random variant
-            let variant = gen_range(0, 3);
+            let variant = rng.gen_range(0, 3);
             let path = match variant {
                 0 => {
                     // Gentle Curve
                     BezierPath {
-                        p0: Vec2::new(spawn_x, gen_range(-30.0, -10.0)),
-                        p1: Vec2::new(spawn_x + gen_range(-80.0, 80.0), gen_range(30.0, 45.0)),
-                        p2: Vec2::new(spawn_x + gen_range(-40.0, 40.0), gen_range(90.0, 120.0)),
-                        p3: Vec2::new(spawn_x, gen_range(100.0, 140.0)),
-                        duration: gen_range(1.5, 2.0),
+                        p0: Vec2::new(spawn_x, rng.gen_range(-30.0, -10.0)),
+                        p1: Vec2::new(
+                            spawn_x + rng.gen_range(-80.0, 80.0),
+                            rng.gen_range(30.0, 45.0),
+                        ),
+                        p2: Vec2::new(
+                            spawn_x + rng.gen_range(-40.0, 40.0),
+                            rng.gen_range(90.0, 120.0),
+                        ),
+                        p3: Vec2::new(spawn_x, rng.gen_range(100.0, 140.0)),
+                        duration: rng.gen_range(1.5, 2.0),
                         use_cubic: true,
                     }
                 }
                 1 => {
                     // Sharp Swoop
-                    let swoop_dir = if gen_range(0, 2) == 0 { 1.0 } else { -1.0 };
+                    let swoop_dir = if rng.gen_range(0, 2) == 0 { 1.0 } else { -1.0 };
                     BezierPath {
                         p0: Vec2::new(spawn_x + swoop_dir * 150.0, -30.0),
                         p1: Vec2::new(spawn_x + swoop_dir * 120.0, 30.0),
                         p2: Vec2::new(spawn_x - swoop_dir * 30.0, 90.0),
                         p3: Vec2::new(spawn_x, 130.0),
-                        duration: gen_range(1.0, 1.5),
+                        duration: rng.gen_range(1.0, 1.5),
                         use_cubic: true,
                     }
                 }
@@ -55,31 +66,31 @@ pub fn create_wave_enemy_path(entity_type: EntityType, spawn_x: f32) -> EnemyMov
                 elapsed_time: 0.0,
             }
         }
-        EntityType::Sniper => {
-            // Snipers ALWAYS come from sides
-            let side = gen_range(0, 2); // 0 = left, 1 = right
-            let entry_style = gen_range(0, 2); // Different curve styles
+        EntryPath::SideSweep => {
+            // ALWAYS come from sides
+            let side = rng.gen_range(0, 2); // 0 = left, 1 = right
+            let entry_style = rng.gen_range(0, 2); // Different curve styles
 
             let path = if side == 0 {
                 // From LEFT
                 if entry_style == 0 {
                     // Fast sweep
                     BezierPath {
-                        p0: Vec2::new(-50.0, gen_range(60.0, 120.0)),
-                        p1: Vec2::new(80.0, gen_range(40.0, 80.0)),
-                        p2: Vec2::new(140.0, gen_range(100.0, 140.0)),
-                        p3: Vec2::new(110.0, gen_range(130.0, 160.0)),
-                        duration: gen_range(1.5, 2.0),
+                        p0: Vec2::new(-50.0, rng.gen_range(60.0, 120.0)),
+                        p1: Vec2::new(80.0, rng.gen_range(40.0, 80.0)),
+                        p2: Vec2::new(140.0, rng.gen_range(100.0, 140.0)),
+                        p3: Vec2::new(110.0, rng.gen_range(130.0, 160.0)),
+                        duration: rng.gen_range(1.5, 2.0),
                         use_cubic: true,
                     }
                 } else {
                     // Curved approach
                     BezierPath {
-                        p0: Vec2::new(-50.0, gen_range(40.0, 80.0)),
-                        p1: Vec2::new(120.0, gen_range(20.0, 60.0)),
-                        p2: Vec2::new(100.0, gen_range(120.0, 160.0)),
-                        p3: Vec2::new(100.0, gen_range(140.0, 170.0)),
-                        duration: gen_range(1.5, 2.5),
+                        p0: Vec2::new(-50.0, rng.gen_range(40.0, 80.0)),
+                        p1: Vec2::new(120.0, rng.gen_range(20.0, 60.0)),
+                        p2: Vec2::new(100.0, rng.gen_range(120.0, 160.0)),
+                        p3: Vec2::new(100.0, rng.gen_range(140.0, 170.0)),
+                        duration: rng.gen_range(1.5, 2.5),
                         use_cubic: true,
                     }
                 }
@@ -88,21 +99,21 @@ pub fn create_wave_enemy_path(entity_type: EntityType, spawn_x: f32) -> EnemyMov
                 if entry_style == 0 {
                     // Fast sweep
                     BezierPath {
-                        p0: Vec2::new(screen_w + 50.0, gen_range(60.0, 120.0)),
-                        p1: Vec2::new(screen_w - 80.0, gen_range(40.0, 80.0)),
-                        p2: Vec2::new(screen_w - 140.0, gen_range(100.0, 140.0)),
-                        p3: Vec2::new(screen_w - 110.0, gen_range(130.0, 160.0)),
-                        duration: gen_range(1.5, 2.0),
+                        p0: Vec2::new(screen_w + 50.0, rng.gen_range(60.0, 120.0)),
+                        p1: Vec2::new(screen_w - 80.0, rng.gen_range(40.0, 80.0)),
+                        p2: Vec2::new(screen_w - 140.0, rng.gen_range(100.0, 140.0)),
+                        p3: Vec2::new(screen_w - 110.0, rng.gen_range(130.0, 160.0)),
+                        duration: rng.gen_range(1.5, 2.0),
                         use_cubic: true,
                     }
                 } else {
                     // Curved approach
                     BezierPath {
-                        p0: Vec2::new(screen_w + 50.0, gen_range(40.0, 80.0)),
-                        p1: Vec2::new(screen_w - 120.0, gen_range(20.0, 60.0)),
-                        p2: Vec2::new(screen_w - 100.0, gen_range(120.0, 160.0)),
-                        p3: Vec2::new(screen_w - 100.0, gen_range(140.0, 170.0)),
-                        duration: gen_range(1.5, 2.5),
+                        p0: Vec2::new(screen_w + 50.0, rng.gen_range(40.0, 80.0)),
+                        p1: Vec2::new(screen_w - 120.0, rng.gen_range(20.0, 60.0)),
+                        p2: Vec2::new(screen_w - 100.0, rng.gen_range(120.0, 160.0)),
+                        p3: Vec2::new(screen_w - 100.0, rng.gen_range(140.0, 170.0)),
+                        duration: rng.gen_range(1.5, 2.5),
                         use_cubic: true,
                     }
                 }
@@ -114,19 +125,19 @@ pub fn create_wave_enemy_path(entity_type: EntityType, spawn_x: f32) -> EnemyMov
                 elapsed_time: 0.0,
             }
         }
-        EntityType::Tank => {
-            // Tanks: Heavy dramatic entries
-            let variant = gen_range(0, 2);
+        EntryPath::Heavy => {
+            // Heavy dramatic entries
+            let variant = rng.gen_range(0, 2);
             let path = if variant == 0 {
                 // Diagonal Slam - Top corner to opposite bottom
-                let from_right = gen_range(0, 2) == 0;
+                let from_right = rng.gen_range(0, 2) == 0;
                 if from_right {
                     BezierPath {
                         p0: Vec2::new(screen_w + 50.0, -50.0),
                         p1: Vec2::new(screen_w * 0.7, 60.0),
                         p2: Vec2::new(screen_w * 0.3, 140.0),
-                        p3: Vec2::new(gen_range(100.0, 200.0), gen_range(160.0, 200.0)),
-                        duration: gen_range(2.0, 3.0),
+                        p3: Vec2::new(rng.gen_range(100.0, 200.0), rng.gen_range(160.0, 200.0)),
+                        duration: rng.gen_range(2.0, 3.0),
                         use_cubic: true,
                     }
                 } else {
@@ -135,21 +146,21 @@ pub fn create_wave_enemy_path(entity_type: EntityType, spawn_x: f32) -> EnemyMov
                         p1: Vec2::new(screen_w * 0.3, 60.0),
                         p2: Vec2::new(screen_w * 0.7, 140.0),
                         p3: Vec2::new(
-                            gen_range(screen_w - 200.0, screen_w - 100.0),
-                            gen_range(160.0, 200.0),
+                            rng.gen_range(screen_w - 200.0, screen_w - 100.0),
+                            rng.gen_range(160.0, 200.0),
                         ),
-                        duration: gen_range(2.5, 3.0),
+                        duration: rng.gen_range(2.5, 3.0),
                         use_cubic: true,
                     }
                 }
             } else {
                 // Overhead Arc
                 BezierPath {
-                    p0: Vec2::new(gen_range(0.0, screen_w), -50.0),
-                    p1: Vec2::new(screen_w / 2.0 + gen_range(-100.0, 100.0), 30.0),
-                    p2: Vec2::new(spawn_x + gen_range(-80.0, 80.0), 120.0),
-                    p3: Vec2::new(spawn_x, gen_range(150.0, 180.0)),
-                    duration: gen_range(2.0, 2.5),
+                    p0: Vec2::new(rng.gen_range(0.0, screen_w), -50.0),
+                    p1: Vec2::new(screen_w / 2.0 + rng.gen_range(-100.0, 100.0), 30.0),
+                    p2: Vec2::new(spawn_x + rng.gen_range(-80.0, 80.0), 120.0),
+                    p3: Vec2::new(spawn_x, rng.gen_range(150.0, 180.0)),
+                    duration: rng.gen_range(2.0, 2.5),
                     use_cubic: true,
                 }
             };
@@ -160,9 +171,9 @@ pub fn create_wave_enemy_path(entity_type: EntityType, spawn_x: f32) -> EnemyMov
                 elapsed_time: 0.0,
             }
         }
-        EntityType::Elite => {
-            // Elite: Multiple dramatic entrance styles
-            let variant = gen_range(0, 3);
+        EntryPath::Showcase => {
+            // Multiple dramatic entrance styles
+            let variant = rng.gen_range(0, 3);
             let path = match variant {
                 0 => {
                     // Figure-8 Entry
@@ -205,20 +216,23 @@ pub fn create_wave_enemy_path(entity_type: EntityType, spawn_x: f32) -> EnemyMov
                 elapsed_time: 0.0,
             }
         }
-        EntityType::Healer | EntityType::Splitter => {
+        EntryPath::Gentle => {
             // Support units: Gentle randomized entries
-            let curve_strength = gen_range(30.0, 80.0);
-            let curve_dir = if gen_range(0, 2) == 0 { 1.0 } else { -1.0 };
+            let curve_strength = rng.gen_range(30.0, 80.0);
+            let curve_dir = if rng.gen_range(0, 2) == 0 { 1.0 } else { -1.0 };
 
             let path = BezierPath {
                 p0: Vec2::new(spawn_x, -30.0),
-                p1: Vec2::new(spawn_x + curve_dir * curve_strength, gen_range(50.0, 90.0)),
+                p1: Vec2::new(
+                    spawn_x + curve_dir * curve_strength,
+                    rng.gen_range(50.0, 90.0),
+                ),
                 p2: Vec2::new(
                     spawn_x - curve_dir * (curve_strength * 0.5),
-                    gen_range(100.0, 130.0),
+                    rng.gen_range(100.0, 130.0),
                 ),
-                p3: Vec2::new(spawn_x, gen_range(120.0, 150.0)),
-                duration: gen_range(1.0, 1.5),
+                p3: Vec2::new(spawn_x, rng.gen_range(120.0, 150.0)),
+                duration: rng.gen_range(1.0, 1.5),
                 use_cubic: true,
             };
             EnemyMovementState::FollowingPath {
@@ -238,51 +252,37 @@ pub fn spawn_enemies(state: &mut GameState, delta: f32) {
         // Reset timer
         state.spawn_timer = state.config.spawning.enemy_spawn_interval;
 
-        //  Spawn pool:
-        let enemy_types = [
-            EntityType::BasicFighter,
-            EntityType::BasicFighter,
-            EntityType::Splitter,
-            EntityType::Splitter,
-            EntityType::Sniper,
-            EntityType::Sniper,
-            EntityType::Tank,
-            EntityType::Tank,
-            EntityType::Elite,
-        ];
-
-        let random_idx = rand::gen_range(0, enemy_types.len());
-        let entity_type = enemy_types[random_idx];
-
-        // Get entity stats from config
-        let entity_stats = entity_type.get_stats(&state.config.entities);
+        // Weighted pick from the archetypes' random_spawn_weight
+        let archetypes = &state.config.archetypes;
+        let total_weight: u32 = archetypes.iter().map(|a| a.random_spawn_weight).sum();
+        if total_weight == 0 {
+            return; // Nothing is allowed to spawn randomly
+        }
 
-        // Get weapons list
-        let weapons_list = match entity_type {
-            EntityType::BasicFighter => &state.config.entities.basic_fighter.weapons,
-            EntityType::Sniper => &state.config.entities.sniper.weapons,
-            EntityType::Tank => &state.config.entities.tank.weapons,
-            EntityType::Elite => &state.config.entities.elite.weapons,
-            EntityType::Healer => &state.config.entities.healer.weapons,
-            EntityType::Splitter => &state.config.entities.splitter.weapons,
+        let mut roll = state.rng.gen_range(0, total_weight as usize) as u32;
+        let Some(entity_type) = EntityType::all(archetypes).find(|t| {
+            let weight = t.archetype(archetypes).random_spawn_weight;
+            if roll < weight {
+                return true;
+            }
+            roll -= weight;
+            false
+        }) else {
+            return;
         };
+        let archetype = entity_type.archetype(archetypes);
 
-        // Parse weapons from config
-        let weapons: Vec<WeaponType> = weapons_list
-            .iter()
-            .filter_map(|w| WeaponType::from_string(w))
-            .collect();
+        // Get entity stats from config
+        let entity_stats = entity_type.get_stats(archetypes);
 
-        // Fallback to Bullet if no valid weapons configured
-        let final_weapons = if weapons.is_empty() {
-            vec![WeaponType::Bullet]
-        } else {
-            weapons
-        };
+        // Get weapons from config
+        let final_weapons = entity_type.get_weapons(&state.config);
+        let entry_path = archetype.entry_path;
 
         // Generate spawn position and entry path
-        let spawn_x = biased_random_x(50.0, screen_width() - 50.0);
-        let movement_state = create_wave_enemy_path(entity_type, spawn_x);
+        let spawn_x = biased_random_x(&mut state.rng, 50.0, state.arena.x - 50.0);
+        let movement_state =
+            create_wave_enemy_path(&mut state.rng, entry_path, spawn_x, state.arena.x);
 
         // Get starting position from path
         let start_pos = match &movement_state {
@@ -292,13 +292,18 @@ pub fn spawn_enemies(state: &mut GameState, delta: f32) {
 
         // Create enemy with Bezier entry path
         let enemy = Enemy {
+            id: state.entity_ids.next(),
             pos: start_pos,
+            prev_pos: start_pos,
             stats: entity_stats,
             entity_type,
             weapon: final_weapons,
             anim: EntityAnimState::default(),
             movement_state,
-            fire_timer: rand::gen_range(1.0, 3.0), // Random initial delay
+            fire_timer: state.rng.gen_range(1.0, 3.0), // Random initial delay
+            last_hit_by: None,
+            status: Default::default(),
+            ai: Default::default(),
         };
 
         state.enemies.push(enemy);
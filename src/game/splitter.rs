@@ -1,3 +1,4 @@
+use crate::game::rng::GameRng;
 use crate::models::*;
 use macroquad::prelude::*;
 
@@ -6,17 +7,19 @@ use macroquad::prelude::*;
 pub fn handle_enemy_splits(
     dead_enemies: &[Enemy],
     config: &crate::config::GameConfig,
+    rng: &mut GameRng,
+    ids: &mut EntityIds,
 ) -> Vec<Enemy> {
     let mut new_enemies = Vec::new();
 
     for enemy in dead_enemies {
         // Only splitters split when killed
-        if enemy.entity_type != EntityType::Splitter {
+        let Some((splitter, split_type)) = split_trait(enemy.entity_type, config) else {
             continue;
-        }
+        };
 
-        let split_count = config.entities.splitter.split_count;
-        let split_health_ratio = config.entities.splitter.split_health_ratio;
+        let split_count = splitter.split_count;
+        let split_health_ratio = splitter.split_health_ratio;
         let original_max_hp = enemy.stats.max_health;
         let split_hp = original_max_hp * split_health_ratio;
 
@@ -37,11 +40,11 @@ pub fn handle_enemy_splits(
                 // Spread outward: left splits go left, right splits go right
                 let normalized_pos =
                     (i as f32 - (split_count as f32 - 1.0) / 2.0) / (split_count as f32 / 2.0);
-                normalized_pos * 80.0 + rand::gen_range(-20.0, 20.0) // ±80px base + randomness
+                normalized_pos * 80.0 + rng.gen_range(-20.0, 20.0) // ±80px base + randomness
             };
 
             let start_x = enemy.pos.x + x_offset;
-            let start_y = enemy.pos.y + rand::gen_range(-10.0, 10.0);
+            let start_y = enemy.pos.y + rng.gen_range(-10.0, 10.0);
 
             // Create short Bezier path for spread effect
             let spread_path = BezierPath {
@@ -49,26 +52,32 @@ pub fn handle_enemy_splits(
                 p1: Vec2::new(start_x + spread_x * 0.5, start_y + 40.0),
                 p2: Vec2::new(start_x + spread_x, start_y + 80.0),
                 p3: Vec2::new(start_x + spread_x, start_y + 100.0),
-                duration: rand::gen_range(1.25, 2.0), // Quick spread
+                duration: rng.gen_range(1.25, 2.0), // Quick spread
                 use_cubic: true,
             };
 
             let split_enemy = Enemy {
+                id: ids.next(),
                 pos: Vec2::new(start_x, start_y),
+                prev_pos: Vec2::new(start_x, start_y),
                 stats: Stats {
                     health: split_hp,
                     max_health: split_hp,
                     damage: enemy.stats.damage, // Same damage as parent
+                    shield: None,
                 },
-                weapon: enemy.weapon.clone(),          // Inherit weapons
-                entity_type: EntityType::BasicFighter, // Splits become basic fighters (don't split again!)
-                anim: EntityAnimState::default(),      // Default animation state for splits
+                weapon: enemy.weapon.clone(),     // Inherit weapons
+                entity_type: split_type,          // Splits don't split again
+                anim: EntityAnimState::default(), // Default animation state for splits
                 movement_state: EnemyMovementState::FollowingPath {
                     path: spread_path,
                     progress: 0.0,
                     elapsed_time: 0.0,
                 }, // Reuse Bezier system for spread!
-                fire_timer: rand::gen_range(0.5, 1.5), // Random initial delay for splits
+                fire_timer: rng.gen_range(0.5, 1.5), // Random initial delay for splits
+                last_hit_by: None,
+                status: Default::default(),
+                ai: Default::default(),
             };
 
             new_enemies.push(split_enemy);
@@ -84,6 +93,19 @@ pub fn handle_enemy_splits(
     new_enemies
 }
 
+/// Splitter trait of an archetype plus the archetype its splits become
+fn split_trait(
+    entity_type: EntityType,
+    config: &crate::config::GameConfig,
+) -> Option<(&crate::config::SplitterTrait, EntityType)> {
+    let splitter = entity_type
+        .archetype(&config.archetypes)
+        .splitter
+        .as_ref()?;
+    let split_type = EntityType::from_name(&splitter.split_into, &config.archetypes)?;
+    Some((splitter, split_type))
+}
+
 /// Spawn visual split effect particles (shows splitting animation)
 pub fn spawn_split_particles(
     state: &mut GameState,
@@ -109,11 +131,14 @@ pub fn spawn_split_particles(
 
             let particle = Particle {
                 pos,
-                velocity: Vec2::new((target_x - pos.x) * 3.0, rand::gen_range(-20.0, 20.0)),
+                velocity: Vec2::new(
+                    (target_x - pos.x) * 3.0,
+                    state.fx_rng.gen_range(-20.0, 20.0),
+                ),
                 lifetime: 0.5 + progress * 0.3, // Staggered lifetime
                 max_lifetime: 0.8,
                 color,
-                size: rand::gen_range(3.0, 6.0),
+                size: state.fx_rng.gen_range(3.0, 6.0),
                 size_decay: 0.95,
             };
 
@@ -123,16 +148,16 @@ pub fn spawn_split_particles(
 
     // Central burst showing the split happening
     for _ in 0..15 {
-        let angle = rand::gen_range(0.0, std::f32::consts::PI * 2.0);
-        let speed = rand::gen_range(50.0, 120.0);
+        let angle = state.fx_rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+        let speed = state.fx_rng.gen_range(50.0, 120.0);
 
         let particle = Particle {
             pos,
             velocity: Vec2::new(angle.cos() * speed, angle.sin() * speed),
-            lifetime: rand::gen_range(0.3, 0.6),
+            lifetime: state.fx_rng.gen_range(0.3, 0.6),
             max_lifetime: 0.6,
             color,
-            size: rand::gen_range(4.0, 8.0),
+            size: state.fx_rng.gen_range(4.0, 8.0),
             size_decay: 0.92,
         };
 
@@ -146,34 +171,43 @@ pub fn spawn_split_particles(
 #[allow(dead_code)]
 pub fn handle_ghost_splitter_damage(
     ghost: &Ghost,
-    _config: &crate::config::GameConfig, // Reserved for future config options
+    config: &crate::config::GameConfig,
+    rng: &mut GameRng,
+    ids: &mut EntityIds,
 ) -> Option<Ghost> {
     // Only splitter ghosts create clones
-    if ghost.entity_type != EntityType::Splitter {
-        return None;
-    }
+    ghost
+        .entity_type
+        .archetype(&config.archetypes)
+        .splitter
+        .as_ref()?;
 
     // 20% chance to spawn clone when taking damage
-    if rand::gen_range(0.0, 1.0) > 0.2 {
+    if rng.gen_range(0.0, 1.0) > 0.2 {
         return None;
     }
 
     // Create temporary clone with reduced stats
+    let clone_pos = Vec2::new(
+        ghost.pos.x + rng.gen_range(-40.0, 40.0),
+        ghost.pos.y + rng.gen_range(-40.0, 40.0),
+    );
     let clone = Ghost {
-        pos: Vec2::new(
-            ghost.pos.x + rand::gen_range(-40.0, 40.0),
-            ghost.pos.y + rand::gen_range(-40.0, 40.0),
-        ),
+        id: ids.next(),
+        pos: clone_pos,
+        prev_pos: clone_pos,
         stats: Stats {
             health: ghost.stats.health * 0.5, // Clone has 50% HP
             max_health: ghost.stats.max_health * 0.5,
             damage: ghost.stats.damage,
+            shield: ghost.stats.shield,
         },
         weapon_type: ghost.weapon_type.clone(),
-        entity_type: EntityType::Splitter,
+        entity_type: ghost.entity_type,
         energy_drain_per_sec: 0.0, // Clones don't drain energy!
         anim: EntityAnimState::new_spawning(0.3), // Quick spawn animation for clones
         fire_timer: 0.0,
+        status: Default::default(), // Clones start clean
     };
 
     println!("✓ Ghost splitter created a clone!");
@@ -185,17 +219,19 @@ pub fn handle_ghost_splitter_damage(
 pub fn handle_ghost_splits(
     dead_ghosts: &[Ghost],
     config: &crate::config::GameConfig,
+    rng: &mut GameRng,
+    ids: &mut EntityIds,
 ) -> Vec<Ghost> {
     let mut new_ghosts = Vec::new();
 
     for ghost in dead_ghosts {
         // Only splitter ghosts split when killed
-        if ghost.entity_type != EntityType::Splitter {
+        let Some((splitter, split_type)) = split_trait(ghost.entity_type, config) else {
             continue;
-        }
+        };
 
-        let split_count = config.entities.splitter.split_count;
-        let split_health_ratio = config.entities.splitter.split_health_ratio;
+        let split_count = splitter.split_count;
+        let split_health_ratio = splitter.split_health_ratio;
         let original_max_hp = ghost.stats.max_health;
         let split_hp = original_max_hp * split_health_ratio;
 
@@ -209,18 +245,23 @@ pub fn handle_ghost_splits(
                 (i as f32 - (split_count as f32 - 1.0) / 2.0) * spacing
             };
 
+            let split_pos = Vec2::new(ghost.pos.x + x_offset, ghost.pos.y);
             let split_ghost = Ghost {
-                pos: Vec2::new(ghost.pos.x + x_offset, ghost.pos.y),
+                id: ids.next(),
+                pos: split_pos,
+                prev_pos: split_pos,
                 stats: Stats {
                     health: split_hp,
                     max_health: split_hp,
                     damage: ghost.stats.damage, // Same damage as parent
+                    shield: None,
                 },
                 weapon_type: ghost.weapon_type.clone(), // Inherit weapons
-                entity_type: EntityType::BasicFighter,  // Splits become basic fighters
+                entity_type: split_type,                // Splits don't split again
                 energy_drain_per_sec: ghost.energy_drain_per_sec * 0.0, // Doesn't drain energy
                 anim: EntityAnimState::new_spawning(0.4), // Spawn animation for splits
-                fire_timer: rand::gen_range(0.5, 1.5),  // Random initial delay
+                fire_timer: rng.gen_range(0.5, 1.5),    // Random initial delay
+                status: Default::default(),
             };
 
             new_ghosts.push(split_ghost);
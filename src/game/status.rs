@@ -0,0 +1,244 @@
+//! Status effects - burn, slow, stun and energy drain on the player, enemies and ghosts
+//!
+//! Weapons apply them through `[[weapons.status]]` (an on-damage hook in damage.rs).
+//! Movement and fire timers scale by `StatusEffects::time_scale`; burn ticks go back
+//! through the damage pipeline.
+
+use crate::config::{Stacking, StatusKind, StatusTrait};
+use crate::game::damage::{DamageInfo, DamageTarget, apply_damage};
+use crate::models::*;
+
+/// One running effect
+#[derive(Clone, Copy, Debug)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub remaining: f32, // Seconds left
+    pub magnitude: f32, // Per stack (see StatusTrait)
+    pub stacks: u32,
+    pub weapon: WeaponType,      // Burn ticks are credited to this weapon...
+    pub source: ProjectileOwner, // ...and owner
+}
+
+/// Effects on one entity
+#[derive(Clone, Debug, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+    pub tick_timer: f32, // Time until the next burn tick
+}
+
+impl StatusEffects {
+    /// Add an effect (or stack/refresh/extend the running one of the same kind)
+    pub fn apply(&mut self, status: &StatusTrait, weapon: WeaponType, source: ProjectileOwner) {
+        let Some(effect) = self.effects.iter_mut().find(|e| e.kind == status.kind) else {
+            self.effects.push(StatusEffect {
+                kind: status.kind,
+                remaining: status.duration,
+                magnitude: status.magnitude,
+                stacks: 1,
+                weapon,
+                source,
+            });
+            return;
+        };
+
+        match status.stacking {
+            Stacking::Refresh => effect.remaining = effect.remaining.max(status.duration),
+            Stacking::Extend => {
+                let cap = status.duration * status.max_stacks as f32;
+                effect.remaining = (effect.remaining + status.duration).min(cap);
+            }
+            Stacking::Stack => {
+                effect.stacks = (effect.stacks + 1).min(status.max_stacks);
+                effect.remaining = status.duration;
+            }
+        }
+        effect.magnitude = effect.magnitude.max(status.magnitude);
+        effect.weapon = weapon;
+        effect.source = source;
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects.iter().any(|e| e.kind == kind)
+    }
+
+    /// Summed magnitude of every stack of a kind
+    pub fn total(&self, kind: StatusKind) -> f32 {
+        self.effects
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.magnitude * e.stacks as f32)
+            .sum()
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusKind::Stun)
+    }
+
+    /// Speed of movement and fire timers (1.0 = normal, 0.0 = stunned)
+    pub fn time_scale(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.0;
+        }
+        (1.0 - self.total(StatusKind::Slow)).clamp(0.0, 1.0)
+    }
+
+    /// Effect shown on the entity (stun > burn > slow > drain)
+    pub fn strongest(&self) -> Option<StatusKind> {
+        [
+            StatusKind::Stun,
+            StatusKind::Burn,
+            StatusKind::Slow,
+            StatusKind::EnergyDrain,
+        ]
+        .into_iter()
+        .find(|&kind| self.has(kind))
+    }
+}
+
+/// Tick durations, burn damage and energy drain (before `cleanup_dead_entities`)
+pub fn update_status_effects(state: &mut GameState, delta: f32) {
+    let tick_interval = state.config.status.tick_interval;
+    let mut burns = Vec::new();
+
+    let player = std::iter::once((DamageTarget::Player, &mut state.player.status));
+    let enemies = state
+        .enemies
+        .iter_mut()
+        .enumerate()
+        .map(|(index, enemy)| (DamageTarget::Enemy(index), &mut enemy.status));
+    let ghosts = state
+        .ghosts
+        .iter_mut()
+        .enumerate()
+        .map(|(index, ghost)| (DamageTarget::Ghost(index), &mut ghost.status));
+
+    for (target, status) in player.chain(enemies).chain(ghosts) {
+        if status.effects.is_empty() {
+            continue;
+        }
+
+        // Burn damage in chunks (one crit-free hit per tick_interval)
+        status.tick_timer -= delta;
+        if status.tick_timer <= 0.0 {
+            status.tick_timer += tick_interval;
+            for effect in &status.effects {
+                if effect.kind == StatusKind::Burn {
+                    let amount = effect.magnitude * effect.stacks as f32 * tick_interval;
+                    let info = DamageInfo {
+                        periodic: true,
+                        ..DamageInfo::new(amount, effect.weapon, effect.source)
+                    };
+                    burns.push((target, info));
+                }
+            }
+        }
+
+        for effect in &mut status.effects {
+            effect.remaining -= delta;
+        }
+        status.effects.retain(|e| e.remaining > 0.0);
+        if status.effects.is_empty() {
+            status.tick_timer = 0.0; // Next effect ticks right away
+        }
+    }
+
+    // Energy drain works on the player only
+    let drain = state.player.status.total(StatusKind::EnergyDrain);
+    state.player.energy = (state.player.energy - drain * delta).max(0.0);
+
+    for (target, info) in burns {
+        apply_damage(state, target, info);
+    }
+}
+
+/// Damage hook: put the weapon's status effects on whatever it hit
+pub fn apply_weapon_status(state: &mut GameState, target: DamageTarget, info: &DamageInfo) {
+    if info.periodic {
+        return; // Burn ticks don't re-apply themselves
+    }
+    let weapon = info.weapon.config(&state.config.weapons);
+    if weapon.status.is_empty() {
+        return;
+    }
+
+    let status = match target {
+        DamageTarget::Player => &mut state.player.status,
+        DamageTarget::Enemy(index) => &mut state.enemies[index].status,
+        DamageTarget::Ghost(index) => &mut state.ghosts[index].status,
+    };
+    for effect in &weapon.status {
+        if effect.kind == StatusKind::EnergyDrain && target != DamageTarget::Player {
+            continue; // Only the player has energy
+        }
+        status.apply(effect, info.weapon, info.source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weapon(state: &GameState, name: &str) -> WeaponType {
+        WeaponType::from_name(name, &state.config.weapons).unwrap()
+    }
+
+    #[test]
+    fn test_stacking_rules_and_time_scale() {
+        let state = GameState::with_arena(800.0, 600.0);
+        let plasma = weapon(&state, "Plasma");
+        let mut status = StatusEffects::default();
+        let slow = StatusTrait {
+            kind: StatusKind::Slow,
+            duration: 2.0,
+            magnitude: 0.25,
+            stacking: Stacking::Stack,
+            max_stacks: 3,
+        };
+
+        for _ in 0..5 {
+            status.apply(&slow, plasma, ProjectileOwner::Player);
+        }
+        assert_eq!(status.effects.len(), 1);
+        assert_eq!(status.effects[0].stacks, 3);
+        assert_eq!(status.time_scale(), 0.25);
+
+        let stun = StatusTrait {
+            kind: StatusKind::Stun,
+            stacking: Stacking::Extend,
+            ..slow
+        };
+        status.apply(&stun, plasma, ProjectileOwner::Player);
+        status.apply(&stun, plasma, ProjectileOwner::Player);
+        assert_eq!(status.time_scale(), 0.0);
+        assert_eq!(status.effects[1].remaining, 4.0);
+        assert_eq!(status.strongest(), Some(StatusKind::Stun));
+    }
+
+    #[test]
+    fn test_plasma_hit_burns_until_it_expires() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let id = state.entity_ids.next();
+        let pos = Position::new(400.0, 200.0);
+        let enemy = Enemy::from_entity_type(id, EntityType(0), pos, &state.config);
+        state.enemies.push(enemy);
+
+        let plasma = weapon(&state, "Plasma");
+        let hit = DamageInfo::new(1.0, plasma, ProjectileOwner::Player);
+        apply_damage(&mut state, DamageTarget::Enemy(0), hit);
+        assert!(state.enemies[0].status.has(StatusKind::Burn));
+
+        let burn = plasma.config(&state.config.weapons).status[0];
+        let health = state.enemies[0].stats.health;
+        let interval = state.config.status.tick_interval;
+        for _ in 0..(burn.duration / interval) as usize * 2 {
+            update_status_effects(&mut state, interval);
+        }
+
+        let burned = health - state.enemies[0].stats.health;
+        assert!(
+            (burned - burn.magnitude * burn.duration).abs() < 1e-3,
+            "{burned}"
+        );
+        assert!(state.enemies[0].status.effects.is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+//! Weapon upgrades - player slots level up (1-5) from cleared waves and pickups,
+//! and the highest slot loses levels when the player is hit
+//!
+//! Works off this tick's events (kills, hits, cleared waves), so it runs after the
+//! gameplay systems and before `dispatch_events`.
+
+use crate::game::events::{GameEvent, emit};
+use crate::models::*;
+
+/// Upgrade pickup dropped by a killed enemy (drifts down, collected on touch)
+#[derive(Clone, Copy)]
+pub struct Pickup {
+    pub pos: Position,
+    pub prev_pos: Position, // Position at start of tick (render interpolation)
+    pub lifetime: f32,      // Seconds left before it vanishes
+}
+
+/// Drop, move and collect pickups, then apply wave rewards and hit penalties
+pub fn update_upgrades(state: &mut GameState, delta: f32) {
+    let cfg = state.config.upgrades.clone();
+    let player = &mut state.player;
+    player.downgrade_grace_timer = (player.downgrade_grace_timer - delta).max(0.0);
+
+    // What happened this tick
+    let mut kills = Vec::new();
+    let mut player_hit = false;
+    let mut waves_cleared = 0;
+    for event in &state.events {
+        match *event {
+            GameEvent::EnemyKilled {
+                pos,
+                killer: Some(ProjectileOwner::Player | ProjectileOwner::Ghost),
+                ..
+            } => kills.push(pos),
+            GameEvent::PlayerHit { .. } => player_hit = true,
+            GameEvent::WaveCompleted { .. } => waves_cleared += 1,
+            _ => {}
+        }
+    }
+
+    for pos in kills {
+        if state.rng.gen_range(0.0, 1.0) < cfg.pickup_drop_chance {
+            state.pickups.push(Pickup {
+                pos,
+                prev_pos: pos,
+                lifetime: cfg.pickup_lifetime,
+            });
+        }
+    }
+
+    // Drift down, expire and collect
+    let player_pos = state.player.pos;
+    let bottom = state.arena.y + cfg.pickup_radius;
+    let mut collected = 0;
+    state.pickups.retain_mut(|pickup| {
+        pickup.pos.y += cfg.pickup_fall_speed * delta;
+        pickup.lifetime -= delta;
+
+        if pickup.pos.distance(player_pos) <= cfg.pickup_radius {
+            collected += 1;
+            return false;
+        }
+        pickup.lifetime > 0.0 && pickup.pos.y < bottom
+    });
+
+    upgrade_weapons(state, collected + waves_cleared * cfg.levels_per_wave);
+
+    // Grace period keeps one burst of damage from stripping several levels
+    if player_hit
+        && state.player.downgrade_grace_timer <= 0.0
+        && downgrade_weapon(state, cfg.levels_lost_on_hit)
+    {
+        state.player.downgrade_grace_timer = cfg.downgrade_grace;
+    }
+}
+
+/// Raise the lowest upgradable slot one level at a time
+pub fn upgrade_weapons(state: &mut GameState, levels: usize) {
+    for _ in 0..levels {
+        let weapons = &state.config.weapons;
+        let player = &state.player;
+        let Some(index) = player
+            .slots
+            .iter()
+            .zip(&player.weapon)
+            .enumerate()
+            .filter(|(_, (slot, weapon))| slot.level < weapon.config(weapons).max_level())
+            .min_by_key(|(_, (slot, _))| slot.level)
+            .map(|(index, _)| index)
+        else {
+            return; // Everything maxed
+        };
+
+        state.player.slots[index].level += 1;
+        emit_level_change(state, index, true);
+    }
+}
+
+/// Take levels from the highest slot (returns false if nothing was lost)
+pub fn downgrade_weapon(state: &mut GameState, levels: usize) -> bool {
+    if levels == 0 {
+        return false;
+    }
+    let Some(index) = state
+        .player
+        .slots
+        .iter()
+        .enumerate()
+        .filter(|(_, slot)| slot.level > 1)
+        .max_by_key(|(_, slot)| slot.level)
+        .map(|(index, _)| index)
+    else {
+        return false;
+    };
+
+    let slot = &mut state.player.slots[index];
+    slot.level = slot.level.saturating_sub(levels).max(1);
+    emit_level_change(state, index, false);
+    true
+}
+
+fn emit_level_change(state: &mut GameState, slot: usize, upgraded: bool) {
+    let weapon = state.player.weapon[slot];
+    let level = state.player.slots[slot].level;
+    emit(
+        state,
+        GameEvent::WeaponLevelChanged {
+            weapon,
+            level,
+            upgraded,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot_of(state: &GameState, name: &str) -> usize {
+        let weapon = WeaponType::from_name(name, &state.config.weapons).unwrap();
+        state
+            .player
+            .weapon
+            .iter()
+            .position(|&w| w == weapon)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_wave_reward_levels_lowest_slot_and_hit_takes_it_back() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let bullet = slot_of(&state, "Bullet");
+
+        upgrade_weapons(&mut state, 2);
+        assert_eq!(state.player.slots[bullet].level, 2);
+        let upgraded: usize = state.player.slots.iter().map(|slot| slot.level - 1).sum();
+        assert_eq!(upgraded, 2);
+
+        // Bombs have no tiers and never level up
+        upgrade_weapons(&mut state, 100);
+        assert_eq!(state.player.slots[slot_of(&state, "Bombs")].level, 1);
+        assert_eq!(state.player.slots[bullet].level, 5);
+
+        state.events.clear();
+        let pos = state.player.pos;
+        emit(&mut state, GameEvent::PlayerHit { pos, damage: 10.0 });
+        update_upgrades(&mut state, 0.0);
+        let levels: usize = state.player.slots.iter().map(|slot| slot.level).sum();
+        assert_eq!(levels, 4 * 5 + 1 - state.config.upgrades.levels_lost_on_hit);
+
+        // Grace period: a second hit right away costs nothing
+        update_upgrades(&mut state, 0.0);
+        let after: usize = state.player.slots.iter().map(|slot| slot.level).sum();
+        assert_eq!(after, levels);
+    }
+
+    #[test]
+    fn test_level_changes_fire_pattern() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let bullet = slot_of(&state, "Bullet");
+        state.player.slots[bullet].level = 5;
+
+        crate::game::rebuild_spatial(&mut state);
+        crate::game::player_fire_weapon(&mut state, bullet);
+        assert_eq!(state.projectiles.len(), 5);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::game::rng::GameRng;
 use crate::models::{GhostFormation, Position};
 use macroquad::math::Vec2;
 
@@ -8,6 +9,27 @@ pub fn circle_collision(pos1: Position, pos2: Position, radius1: f32, radius2: f
     distance_sq < radii_sum * radii_sum
 }
 
+/// Segment-to-circle collision detection (beams)
+pub fn segment_circle_collision(
+    start: Position,
+    end: Position,
+    center: Position,
+    radius: f32,
+) -> bool {
+    let segment = end - start;
+    let length_sq = segment.length_squared();
+
+    // Closest point on the segment to the circle center
+    let t = if length_sq > 0.0 {
+        ((center - start).dot(segment) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = start + segment * t;
+
+    (center - closest).length_squared() < radius * radius
+}
+
 /// Calculate squared distance between two positions (faster than sqrt)
 #[allow(dead_code)]
 pub fn distance_sq(a: Position, b: Position) -> f32 {
@@ -82,9 +104,6 @@ pub fn calculate_lead_velocity(
     calculate_velocity(from, intercept, projectile_speed)
 }
 
-use macroquad::prelude::rand;
-use macroquad::prelude::screen_width;
-
 /// Calculate ghost spawn position based on formation
 pub fn calculate_formation_position(
     player_pos: Position,
@@ -92,13 +111,14 @@ pub fn calculate_formation_position(
     total_ghosts: usize,
     formation: GhostFormation,
     config: &crate::config::FormationSpacingConfig,
+    arena_width: f32,
 ) -> Position {
     match formation {
         GhostFormation::VShape => {
             calculate_v_formation(player_pos, ghost_index, total_ghosts, config)
         }
         GhostFormation::Line => {
-            calculate_line_formation(player_pos, ghost_index, total_ghosts, config)
+            calculate_line_formation(player_pos, ghost_index, total_ghosts, config, arena_width)
         }
         GhostFormation::Circle => {
             calculate_circle_formation(player_pos, ghost_index, total_ghosts, config)
@@ -108,11 +128,11 @@ pub fn calculate_formation_position(
 
 /// Generate a center-biased random X position for enemy spawning
 /// Uses a triangular distribution to favor center positions
-pub fn biased_random_x(min: f32, max: f32) -> f32 {
+pub fn biased_random_x(rng: &mut GameRng, min: f32, max: f32) -> f32 {
     // Generate two random numbers and average them
     // This creates a triangular distribution that peaks at the center
-    let r1 = rand::gen_range(min, max);
-    let r2 = rand::gen_range(min, max);
+    let r1 = rng.gen_range(min, max);
+    let r2 = rng.gen_range(min, max);
     (r1 + r2) / 2.0
 }
 
@@ -139,6 +159,7 @@ fn calculate_line_formation(
     index: usize,
     total: usize,
     config: &crate::config::FormationSpacingConfig,
+    arena_width: f32,
 ) -> Position {
     let spacing = config.line_spacing;
     let center_offset = (total as f32 - 1.0) / 2.0;
@@ -146,7 +167,7 @@ fn calculate_line_formation(
     let padding = config.screen_edge_padding;
 
     Vec2::new(
-        (player_pos.x + x_offset).clamp(padding, screen_width() - padding),
+        (player_pos.x + x_offset).clamp(padding, arena_width - padding),
         player_pos.y - config.line_height_offset,
     )
 }
@@ -1,6 +1,8 @@
+use crate::config::EntryPath;
+use crate::game::events::GameEvent;
 use crate::game::utils::biased_random_x;
 use crate::models::*;
-use crate::scripting::LuaScripting;
+use crate::scripting::{EventHooks, HookKind, LuaScripting, Timeline};
 use macroquad::prelude::*;
 use mlua::prelude::*;
 
@@ -13,10 +15,19 @@ pub struct WaveManager {
     pub prep_timer: f32,       // Countdown before wave starts
     pub transition_timer: f32, // Pause between waves
     pub total_waves: usize,    // How many waves in total
+    pub waves_cleared: usize,  // Waves completed this run
 
     // Lua callbacks (stored for execution)
     on_start_callback: Option<LuaFunction>,
     on_complete_callback: Option<LuaFunction>,
+    run_callback: Option<LuaFunction>,
+
+    // The wave's run() coroutine while it's going (the wave isn't done until it returns)
+    timeline: Option<Timeline>,
+
+    // The current wave's on_* event handlers (global game.on handlers live in LuaScripting)
+    hooks: EventHooks,
+    tick_hook_elapsed: f32, // Time since on_tick last ran
 }
 
 impl WaveManager {
@@ -32,16 +43,20 @@ impl WaveManager {
             prep_timer: 0.0,
             transition_timer: 0.0,
             total_waves,
+            waves_cleared: 0,
             on_start_callback: None,
             on_complete_callback: None,
+            run_callback: None,
+            timeline: None,
+            hooks: EventHooks::default(),
+            tick_hook_elapsed: 0.0,
         }
     }
 
     /// Create a dummy WaveManager for mem::replace (doesn't initialize Lua)
     pub fn new_dummy() -> Self {
-        // Use Lua::new() instead of LuaScripting::new() to avoid loading init.lua
-        let lua = mlua::Lua::new();
-        let scripting = LuaScripting { lua };
+        // Bare runtime instead of LuaScripting::new() to avoid loading init.lua
+        let scripting = LuaScripting::bare();
 
         Self {
             scripting,
@@ -51,8 +66,13 @@ impl WaveManager {
             prep_timer: 0.0,
             transition_timer: 0.0,
             total_waves: 0,
+            waves_cleared: 0,
             on_start_callback: None,
             on_complete_callback: None,
+            run_callback: None,
+            timeline: None,
+            hooks: EventHooks::default(),
+            tick_hook_elapsed: 0.0,
         }
     }
 
@@ -71,12 +91,16 @@ impl WaveManager {
                 // Store callbacks BEFORE converting (to avoid partial move)
                 let on_start = lua_wave.on_start.clone();
                 let on_complete = lua_wave.on_complete.clone();
+                let run = lua_wave.run.clone();
+                let hooks = lua_wave.hooks.clone();
 
                 // Convert to WaveDefinition
                 match WaveDefinition::from_lua(lua_wave, config) {
                     Some(wave_def) => {
                         self.on_start_callback = on_start;
                         self.on_complete_callback = on_complete;
+                        self.run_callback = run;
+                        self.hooks = hooks;
                         self.prep_timer = wave_def.prep_time;
                         self.current_wave = Some(wave_def);
                         self.state = WaveState::Preparing;
@@ -122,6 +146,14 @@ impl WaveManager {
                         println!("✘ Wave start callback error: {e}");
                     }
 
+                    // Start the timeline (resumed in spawn_for_wave from this tick on)
+                    if let Some(run) = self.run_callback.take() {
+                        match self.scripting.start_timeline(run) {
+                            Ok(timeline) => self.timeline = Some(timeline),
+                            Err(e) => println!("✘ Wave timeline error: {e}"),
+                        }
+                    }
+
                     self.state = WaveState::Active;
                     println!(" Wave {} Active!", self.current_wave_number);
                 }
@@ -131,13 +163,14 @@ impl WaveManager {
                 // Check if wave complete (spawning handled separately)
                 let wave_complete = if let Some(wave) = &self.current_wave {
                     let all_spawned = wave.spawns.iter().all(|s| s.spawned >= s.count);
-                    all_spawned && enemies_alive == 0
+                    all_spawned && enemies_alive == 0 && self.timeline.is_none()
                 } else {
                     false
                 };
 
                 if wave_complete {
                     self.state = WaveState::Complete;
+                    self.waves_cleared += 1;
 
                     if let Some(wave) = &self.current_wave {
                         println!("✓ Wave {} Complete: {}", wave.wave_number, wave.name);
@@ -153,6 +186,9 @@ impl WaveManager {
                     println!("✘ Wave complete callback error: {e}");
                 }
 
+                // The wave's event handlers end with it
+                self.hooks = EventHooks::default();
+
                 // Transition to next wave
                 self.transition_timer = 3.0; // 3 second pause
                 self.state = WaveState::Transition;
@@ -177,6 +213,23 @@ impl WaveManager {
         }
 
         self.spawn_wave_enemies(game_state, delta);
+        self.resume_timeline(delta);
+    }
+
+    /// Run the wave's timeline up to its next wait (dropped once it returns or fails)
+    fn resume_timeline(&mut self, delta: f32) {
+        let Some(timeline) = &mut self.timeline else {
+            return;
+        };
+
+        match self.scripting.resume_timeline(timeline, delta) {
+            Ok(false) => {}
+            Ok(true) => self.timeline = None,
+            Err(e) => {
+                println!("✘ Wave timeline error: {e}");
+                self.timeline = None;
+            }
+        }
     }
 
     /// Spawn enemies according to wave definition
@@ -196,65 +249,83 @@ impl WaveManager {
 
             // Spawn enemy when timer reaches 0
             if spawn.timer <= 0.0 {
-                // Get entity stats from config
-                let entity_stats = spawn.enemy_type.get_stats(&game_state.config.entities);
-
-                // Get weapons list
-                let weapons_list = match spawn.enemy_type {
-                    EntityType::BasicFighter => &game_state.config.entities.basic_fighter.weapons,
-                    EntityType::Sniper => &game_state.config.entities.sniper.weapons,
-                    EntityType::Tank => &game_state.config.entities.tank.weapons,
-                    EntityType::Elite => &game_state.config.entities.elite.weapons,
-                    EntityType::Healer => &game_state.config.entities.healer.weapons,
-                    EntityType::Splitter => &game_state.config.entities.splitter.weapons,
-                };
-
-                let weapons: Vec<WeaponType> = weapons_list
-                    .iter()
-                    .filter_map(|w| WeaponType::from_string(w))
-                    .collect();
-
-                let final_weapons = if weapons.is_empty() {
-                    vec![WeaponType::Bullet]
-                } else {
-                    weapons
-                };
-
-                // Generate spawn position and entry path
-                let spawn_x = biased_random_x(50.0, screen_width() - 50.0);
-                let movement_state =
-                    crate::game::spawn::create_wave_enemy_path(spawn.enemy_type, spawn_x);
-
-                // Get starting position from path
-                let start_pos = match &movement_state {
-                    EnemyMovementState::FollowingPath { path, .. } => path.p0,
-                    EnemyMovementState::FreeMovement => Vec2::new(spawn_x, -25.0),
-                };
-
-                // Create enemy with Bezier entry path
-                let enemy = Enemy {
-                    pos: start_pos,
-                    stats: entity_stats,
-                    entity_type: spawn.enemy_type,
-                    weapon: final_weapons,
-                    anim: EntityAnimState::default(),
-                    movement_state,
-                    fire_timer: 0.0, // Ready to fire
-                };
+                let archetypes = &game_state.config.archetypes;
+                let entry_path = spawn.enemy_type.archetype(archetypes).entry_path;
 
-                game_state.enemies.push(enemy);
+                // Generate spawn position, then the entry path from there
+                let arena_width = game_state.arena.x;
+                let spawn_x = biased_random_x(&mut game_state.rng, 50.0, arena_width - 50.0);
+                spawn_enemy_on_path(game_state, spawn.enemy_type, spawn_x, entry_path);
 
                 spawn.spawned += 1;
                 spawn.timer = spawn.interval; // Reset timer
 
                 println!(
-                    "  Spawned {:?} ({}/{})",
-                    spawn.enemy_type, spawn.spawned, spawn.count
+                    "  Spawned {} ({}/{})",
+                    spawn.enemy_type.name(&game_state.config.archetypes),
+                    spawn.spawned,
+                    spawn.count
                 );
             }
         }
     }
 
+    /// Pass this tick's events to the Lua handlers, plus a throttled on_tick
+    /// (script errors are logged, never fatal)
+    pub fn run_event_hooks(
+        &mut self,
+        events: &[GameEvent],
+        archetypes: &[crate::config::ArchetypeConfig],
+        delta: f32,
+        tick_interval: f32,
+    ) {
+        for event in events {
+            let result = match *event {
+                GameEvent::EnemyKilled {
+                    entity_type,
+                    pos,
+                    killer,
+                } => {
+                    let name = entity_type.name(archetypes).to_string();
+                    let killer = killer.map(|owner| format!("{owner:?}"));
+                    let args = (name, pos.x, pos.y, killer);
+                    self.scripting
+                        .call_hooks(&mut self.hooks, HookKind::EnemyKilled, args)
+                }
+                GameEvent::PlayerHit { pos, damage } => {
+                    let args = (damage, pos.x, pos.y);
+                    self.scripting
+                        .call_hooks(&mut self.hooks, HookKind::PlayerHit, args)
+                }
+                GameEvent::ParrySuccess { pos, deflected } => {
+                    let args = (deflected, pos.x, pos.y);
+                    self.scripting
+                        .call_hooks(&mut self.hooks, HookKind::Parry, args)
+                }
+                GameEvent::GhostSummoned { entity_type, pos } => {
+                    let args = (entity_type.name(archetypes).to_string(), pos.x, pos.y);
+                    self.scripting
+                        .call_hooks(&mut self.hooks, HookKind::GhostSummoned, args)
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                println!("✘ Lua event hook error: {e}");
+            }
+        }
+
+        self.tick_hook_elapsed += delta;
+        if self.tick_hook_elapsed >= tick_interval {
+            let elapsed = std::mem::take(&mut self.tick_hook_elapsed);
+            if let Err(e) = self
+                .scripting
+                .call_hooks(&mut self.hooks, HookKind::Tick, elapsed)
+            {
+                println!("✘ Lua event hook error: {e}");
+            }
+        }
+    }
+
     // UI Helpers
 
     /// Get current wave info for UI
@@ -279,12 +350,55 @@ impl WaveManager {
     }
 
     /// Check if all waves complete
-    #[allow(dead_code)] // Will be used in Wave UI
     pub fn is_game_complete(&self) -> bool {
-        self.current_wave_number >= self.total_waves && self.state == WaveState::Transition
+        self.waves_cleared >= self.total_waves
     }
 }
 
+/// Spawn an enemy of `enemy_type` entering the arena along an `entry_path` at column `spawn_x`
+pub fn spawn_enemy_on_path(
+    game_state: &mut GameState,
+    enemy_type: EntityType,
+    spawn_x: f32,
+    entry_path: EntryPath,
+) {
+    // Get entity stats and weapons from config
+    let entity_stats = enemy_type.get_stats(&game_state.config.archetypes);
+    let final_weapons = enemy_type.get_weapons(&game_state.config);
+
+    let arena_width = game_state.arena.x;
+    let movement_state = crate::game::spawn::create_wave_enemy_path(
+        &mut game_state.rng,
+        entry_path,
+        spawn_x,
+        arena_width,
+    );
+
+    // Get starting position from path
+    let start_pos = match &movement_state {
+        EnemyMovementState::FollowingPath { path, .. } => path.p0,
+        EnemyMovementState::FreeMovement => Vec2::new(spawn_x, -25.0),
+    };
+
+    // Create enemy with Bezier entry path
+    let enemy = Enemy {
+        id: game_state.entity_ids.next(),
+        pos: start_pos,
+        prev_pos: start_pos,
+        stats: entity_stats,
+        entity_type: enemy_type,
+        weapon: final_weapons,
+        anim: EntityAnimState::default(),
+        movement_state,
+        fire_timer: 0.0, // Ready to fire
+        last_hit_by: None,
+        status: Default::default(),
+        ai: Default::default(),
+    };
+
+    game_state.enemies.push(enemy);
+}
+
 /// Wave info for UI display
 #[allow(dead_code)] // Wave UI Helper
 pub struct WaveInfo {
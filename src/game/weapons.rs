@@ -1,3 +1,5 @@
+use crate::config::{HomingTrait, Modifiers, WeaponConfig};
+use crate::game::spatial::EntityKind;
 use crate::models::*;
 use macroquad::prelude::*;
 
@@ -17,19 +19,49 @@ pub enum FiringDirection {
 }
 
 /// Configuration for a single weapon firing event
+#[derive(Clone, Copy)]
 pub struct FireWeaponParams {
     pub shooter_pos: Vec2,
+    pub shooter: Option<EntityId>, // Bursts follow this entity (None = fire from shooter_pos)
     pub owner: ProjectileOwner,
     pub weapon: WeaponType,
     pub direction: FiringDirection,
     pub damage_multiplier: f32,
-    pub enemies: Option<Vec<Vec2>>, // Enemy positions for missile targeting (None for enemy shooters)
+    pub level: usize, // Upgrade level of the pattern (1 = base, enemies/ghosts)
+    pub modifiers: Modifiers, // Affixes on top of the weapon's own (player/ghost config)
+}
+
+/// A queued follow-up shot of a burst
+pub struct PendingShot {
+    pub params: FireWeaponParams,
+    pub delay: f32, // Seconds until it fires
 }
 
 /// Unified weapon firing function - ALL entities use this!
-pub fn fire_weapon(params: FireWeaponParams, state: &mut GameState) {
-    let weapon_stats = params.weapon.get_weapon_stats(&state.config.weapons);
-    let speed = weapon_stats.projectile_speed;
+pub fn fire_weapon(mut params: FireWeaponParams, state: &mut GameState) {
+    let weapon = params.weapon.config(&state.config.weapons);
+    let pattern = weapon.level(params.level);
+    params.damage_multiplier *= pattern.damage_multiplier;
+
+    // Queue the rest of the burst
+    for shot in 1..pattern.burst_count {
+        state.pending_shots.push(PendingShot {
+            params,
+            delay: shot as f32 * weapon.burst_interval,
+        });
+    }
+
+    fire_shot(&params, state);
+}
+
+/// Fire one shot of a weapon (its whole projectile pattern)
+fn fire_shot(params: &FireWeaponParams, state: &mut GameState) {
+    let weapon = params.weapon.config(&state.config.weapons);
+    if weapon.beam.is_some() {
+        crate::game::fire_beam(params, state);
+        return;
+    }
+    let speed = weapon.projectile_speed;
 
     // Calculate base velocity based on direction strategy
     let base_velocity = match params.direction {
@@ -57,139 +89,56 @@ pub fn fire_weapon(params: FireWeaponParams, state: &mut GameState) {
         FiringDirection::LeadTarget { target_pos, .. } => target_pos,
     };
 
-    let final_damage = weapon_stats.damage * params.damage_multiplier;
-
-    match params.weapon {
-        WeaponType::Bullet => {
-            state.projectiles.push(create_projectile(
-                params.shooter_pos,
-                base_velocity,
-                final_damage,
-                params.weapon,
-                params.owner,
-                false, // piercing
-                false, // homing
-                0.0,   // explosion_radius
-                None,  // locked_target
-            ));
-        }
-
-        WeaponType::Laser => {
-            state.projectiles.push(create_projectile(
-                params.shooter_pos,
-                base_velocity,
-                final_damage,
-                params.weapon,
-                params.owner,
-                true,  // piercing - lasers go through targets
-                false, // homing
-                0.0,   // explosion_radius
-                None,  // locked_target
-            ));
-        }
-
-        WeaponType::Missile => {
-            // Find target for homing
-            let locked_target = match params.owner {
-                ProjectileOwner::Player | ProjectileOwner::Ghost => {
-                    // Lock onto nearest enemy
-                    params.enemies.as_ref().and_then(|enemies| {
-                        find_nearest_position_index(params.shooter_pos, enemies)
-                    })
-                }
-                ProjectileOwner::Enemy => {
-                    // Enemy missiles track player (index 0 convention)
-                    Some(0)
-                }
-            };
-
-            state.projectiles.push(create_projectile(
-                params.shooter_pos,
-                base_velocity,
-                final_damage,
-                params.weapon,
-                params.owner,
-                false,         // piercing
-                true,          // homing
-                0.0,           // explosion_radius
-                locked_target, // locked_target
-            ));
-        }
-
-        WeaponType::Plasma => {
-            // 3-projectile spread pattern
-            let spread_angle = 15.0_f32.to_radians();
-            let angles = [-spread_angle, 0.0, spread_angle];
-
-            for &angle in &angles {
-                let spread_velocity =
-                    calculate_spread_velocity(params.shooter_pos, target_pos, speed, angle);
-
-                state.projectiles.push(create_projectile(
-                    params.shooter_pos,
-                    spread_velocity,
-                    final_damage,
-                    params.weapon,
-                    params.owner,
-                    false, // piercing
-                    false, // homing
-                    0.0,   // explosion_radius
-                    None,  // locked_target
-                ));
-            }
-        }
-
-        WeaponType::Bombs => {
-            // AOE radius varies by owner
-            let explosion_radius = match params.owner {
-                ProjectileOwner::Player => 80.0,
-                ProjectileOwner::Ghost => 70.0,
-                ProjectileOwner::Enemy => 60.0,
-            };
-
-            state.projectiles.push(create_projectile(
-                params.shooter_pos,
-                base_velocity,
-                final_damage,
-                params.weapon,
-                params.owner,
-                false,            // piercing
-                false,            // homing
-                explosion_radius, // explosion_radius
-                None,             // locked_target
-            ));
-        }
-    }
-}
+    // Homing shots lock onto a target inside their cone when fired
+    let homing = homing_for(weapon, params.owner);
+    let locked_target = homing.and_then(|homing| {
+        acquire_target(
+            state,
+            params.shooter_pos,
+            base_velocity,
+            &homing,
+            params.owner,
+        )
+        .map(|(id, _)| id)
+    });
 
-/// Helper: Create a projectile with all fields
-fn create_projectile(
-    pos: Vec2,
-    velocity: Vec2,
-    damage: f32,
-    weapon_type: WeaponType,
-    owner: ProjectileOwner,
-    piercing: bool,
-    homing: bool,
-    explosion_radius: f32,
-    locked_target_index: Option<usize>,
-) -> Projectile {
-    Projectile {
-        pos,
-        velocity,
-        damage,
-        weapon_type,
-        owner,
-        piercing,
+    let projectile = Projectile {
+        pos: params.shooter_pos,
+        prev_pos: params.shooter_pos,
+        velocity: base_velocity,
+        damage: weapon.damage * params.damage_multiplier,
+        weapon_type: params.weapon,
+        owner: params.owner,
+        modifiers: weapon.modifiers.combine(params.modifiers),
         homing,
-        explosion_radius,
-        locked_target_index,
+        explosion_radius: weapon.explosion_radius,
+        detonate: false,
+        target: locked_target,
         lifetime: 0.0,
         trail_timer: 0.0,
+        last_hit: None,
+        graze: Graze::Clear,
+    };
+
+    let pattern = weapon.level(params.level);
+    if pattern.projectile_count == 1 {
+        state.projectiles.push(projectile);
+        return;
+    }
+
+    // Fan the projectiles evenly around the aim direction
+    let spread_angle = pattern.spread_angle.to_radians();
+    let center = (pattern.projectile_count - 1) as f32 / 2.0;
+    for i in 0..pattern.projectile_count {
+        let angle = (i as f32 - center) * spread_angle;
+        state.projectiles.push(Projectile {
+            velocity: calculate_spread_velocity(params.shooter_pos, target_pos, speed, angle),
+            ..projectile
+        });
     }
 }
 
-/// Helper: Calculate velocity with angular spread (for plasma)
+/// Helper: Calculate velocity with angular spread (for multi-projectile patterns)
 fn calculate_spread_velocity(from: Vec2, to: Vec2, speed: f32, angle: f32) -> Vec2 {
     let dir = to - from;
     let distance = dir.length();
@@ -207,34 +156,47 @@ fn calculate_spread_velocity(from: Vec2, to: Vec2, speed: f32, angle: f32) -> Ve
     Vec2::new(rotated_x * speed, rotated_y * speed)
 }
 
-/// Helper: Find nearest position in a list and return its index
-fn find_nearest_position_index(from: Vec2, positions: &[Vec2]) -> Option<usize> {
-    if positions.is_empty() {
-        return None;
-    }
-
-    positions
-        .iter()
-        .enumerate()
-        .min_by(|(_, a), (_, b)| {
-            let dist_a = (**a - from).length_squared();
-            let dist_b = (**b - from).length_squared();
-            dist_a.partial_cmp(&dist_b).unwrap()
-        })
-        .map(|(idx, _)| idx)
-}
-
 // PLAYER WEAPON INTERFACE (uses unified system)
 
 /// Update all weapon timers and projectiles
 pub fn update_weapons(state: &mut GameState, delta: f32) {
-    // Countdown fire cooldown
-    state.player_fire_timer = (state.player_fire_timer - delta).max(0.0);
+    // Player slot cooldowns, heat and ammo
+    update_weapon_slots(state, delta);
+
+    // Fire queued burst shots
+    update_pending_shots(state, delta);
 
     // Update all projectiles
     update_projectiles(state, delta);
 }
 
+/// Fire burst shots whose delay ran out (dropped if the shooter died mid-burst)
+fn update_pending_shots(state: &mut GameState, delta: f32) {
+    if state.pending_shots.is_empty() {
+        return;
+    }
+
+    let mut ready = Vec::new();
+    state.pending_shots.retain_mut(|shot| {
+        shot.delay -= delta;
+        if shot.delay <= 0.0 {
+            ready.push(shot.params);
+            return false;
+        }
+        true
+    });
+
+    for mut params in ready {
+        if let Some(id) = params.shooter {
+            let Some(pos) = state.entity_pos(id) else {
+                continue;
+            };
+            params.shooter_pos = pos;
+        }
+        fire_shot(&params, state);
+    }
+}
+
 /// Fire player weapon if cooldown allows
 pub fn player_fire_weapon(state: &mut GameState, weapon_index: usize) {
     if weapon_index >= state.player.weapon.len() {
@@ -243,31 +205,111 @@ pub fn player_fire_weapon(state: &mut GameState, weapon_index: usize) {
 
     let weapon = state.player.weapon[weapon_index];
 
-    // Check cooldown
-    if state.player_fire_timer > 0.0 {
+    // A live beam keeps firing while the key is held
+    if crate::game::hold_player_beam(state, weapon) {
         return;
     }
 
-    let weapon_stats = weapon.get_weapon_stats(&state.config.weapons);
-    state.player_fire_timer = weapon_stats.fire_rate;
-
-    // Collect enemy positions for missile targeting
-    let enemy_positions: Vec<Vec2> = state.enemies.iter().map(|e| e.pos).collect();
+    // Check this slot's cooldown, heat and ammo
+    if !use_weapon_slot(state, weapon_index) {
+        return;
+    }
+    let level = state.player.slots[weapon_index].level;
+    let modifiers = player_modifiers(state, weapon);
 
     // Use unified weapon system!
     fire_weapon(
         FireWeaponParams {
             shooter_pos: state.player.pos,
+            shooter: Some(EntityId::PLAYER),
             owner: ProjectileOwner::Player,
             weapon,
             direction: FiringDirection::Up, // Player shoots up
             damage_multiplier: 1.0,         // Full damage
-            enemies: Some(enemy_positions),
+            level,
+            modifiers,
         },
         state,
     );
 }
 
+/// Affixes the player config adds to a weapon (`[player.weapon_modifiers]`)
+pub fn player_modifiers(state: &GameState, weapon: WeaponType) -> Modifiers {
+    let name = &weapon.config(&state.config.weapons).name;
+    state
+        .config
+        .player
+        .weapon_modifiers
+        .get(name)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Tick every player slot: cooldown, cooling and ammo regen
+fn update_weapon_slots(state: &mut GameState, delta: f32) {
+    let weapons = &state.config.weapons;
+    let player = &mut state.player;
+    let time_scale = player.status.time_scale(); // Slow/stun stretch the cooldowns
+
+    for (slot, weapon) in player.slots.iter_mut().zip(&player.weapon) {
+        let config = weapon.config(weapons);
+        slot.cooldown = (slot.cooldown - delta * time_scale).max(0.0);
+        slot.level = slot.level.clamp(1, config.max_level()); // Levels may shrink on config reload
+
+        if let Some(heat) = config.heat {
+            slot.heat = (slot.heat - heat.cooling * delta).max(0.0);
+            if slot.overheated && slot.heat <= heat.recover_at {
+                slot.overheated = false;
+            }
+        }
+
+        if let Some(ammo) = config.ammo {
+            slot.ammo = slot.ammo.min(ammo.max);
+            if slot.ammo >= ammo.max || ammo.regen_time <= 0.0 {
+                slot.regen_timer = 0.0;
+                continue;
+            }
+
+            slot.regen_timer += delta;
+            if slot.regen_timer >= ammo.regen_time {
+                slot.regen_timer = 0.0;
+                slot.ammo += 1;
+            }
+        }
+    }
+}
+
+/// Can the slot fire right now? (cooldown, overheat, ammo)
+pub fn slot_ready(slot: &WeaponSlot, weapon: &WeaponConfig) -> bool {
+    slot.cooldown <= 0.0 && !slot.overheated && (weapon.ammo.is_none() || slot.ammo > 0)
+}
+
+/// Spend one trigger pull of a player slot (false = not ready, nothing spent)
+pub fn use_weapon_slot(state: &mut GameState, slot_index: usize) -> bool {
+    let Some(weapon) = state.player.weapon.get(slot_index) else {
+        return false;
+    };
+    let config = weapon.config(&state.config.weapons);
+    let stunned = state.player.status.is_stunned();
+    let Some(slot) = state.player.slots.get_mut(slot_index) else {
+        return false;
+    };
+
+    if stunned || !slot_ready(slot, config) {
+        return false;
+    }
+
+    slot.cooldown = config.fire_rate;
+    if let Some(heat) = config.heat {
+        slot.heat = (slot.heat + heat.per_shot).min(1.0);
+        slot.overheated = slot.heat >= 1.0;
+    }
+    if config.ammo.is_some() {
+        slot.ammo -= 1;
+    }
+    true
+}
+
 /// Update all projectile positions and remove off-screen ones
 fn update_projectiles(state: &mut GameState, delta: f32) {
     // Update lifetimes and trail timers
@@ -281,43 +323,50 @@ fn update_projectiles(state: &mut GameState, delta: f32) {
         spawn_projectile_trails(state, delta);
     }
 
-    // Update homing missiles with proper target tracking
-    for projectile in &mut state.projectiles {
-        if projectile.homing {
-            let target_pos = match projectile.owner {
-                ProjectileOwner::Player | ProjectileOwner::Ghost => {
-                    // Player/Ghost missiles track enemies
-                    if let Some(target_idx) = projectile.locked_target_index {
-                        // Validate target index
-                        if target_idx < state.enemies.len() {
-                            Some(state.enemies[target_idx].pos)
-                        } else {
-                            // Target was destroyed, find new one
-                            projectile.locked_target_index =
-                                find_nearest_enemy_index(projectile.pos, &state.enemies);
-                            projectile
-                                .locked_target_index
-                                .map(|idx| state.enemies[idx].pos)
-                        }
-                    } else {
-                        // No target locked, find one
-                        projectile.locked_target_index =
-                            find_nearest_enemy_index(projectile.pos, &state.enemies);
-                        projectile
-                            .locked_target_index
-                            .map(|idx| state.enemies[idx].pos)
-                    }
+    // Keep locks whose target is alive and inside the cone, otherwise (re)acquire one
+    let homing_targets: Vec<Option<(EntityId, Vec2)>> = state
+        .projectiles
+        .iter()
+        .map(|projectile| {
+            let homing = projectile.homing?;
+
+            if let Some(id) = projectile.target {
+                if let Some(pos) = state.entity_pos(id)
+                    && in_lock_cone(projectile.pos, projectile.velocity, pos, &homing)
+                {
+                    return Some((id, pos));
                 }
-                ProjectileOwner::Enemy => {
-                    // Enemy missiles track the player
-                    Some(state.player.pos)
+                if !homing.reacquire {
+                    return None; // Lock broken
                 }
-            };
+            }
 
-            // If we have a target, steer towards it
-            if let Some(target) = target_pos {
-                homing_behavior(projectile, target, delta);
+            acquire_target(
+                state,
+                projectile.pos,
+                projectile.velocity,
+                &homing,
+                projectile.owner,
+            )
+        })
+        .collect();
+
+    for (projectile, homing_target) in state.projectiles.iter_mut().zip(homing_targets) {
+        if let Some(homing) = projectile.homing {
+            match homing_target {
+                Some((id, _)) => projectile.target = Some(id),
+                // Lock broken without reacquire: fly straight from here on
+                None if projectile.target.is_some() && !homing.reacquire => {
+                    projectile.homing = None;
+                }
+                None => projectile.target = None,
             }
+            homing_behavior(
+                projectile,
+                &homing,
+                homing_target.map(|(_, pos)| pos),
+                delta,
+            );
         }
 
         // Normal movement for all projectiles
@@ -325,24 +374,53 @@ fn update_projectiles(state: &mut GameState, delta: f32) {
         projectile.pos.y += projectile.velocity.y * delta;
     }
 
+    // Ricochet: bounce off the screen edges while bounces are left
+    let arena = state.arena;
+    for projectile in &mut state.projectiles {
+        if projectile.modifiers.ricochet > 0 {
+            ricochet(projectile, arena);
+        }
+    }
+
     // Remove projectiles that are:
     // Off-screen
-    // Exceeded max lifetime (5 seconds - prevents stuck missiles)
+    // Exceeded their weapon's lifetime (also prevents stuck missiles)
     let padding = state.config.projectile_bounds.off_screen_padding;
-    let max_lifetime = 5.0;
+    let weapons = &state.config.weapons;
 
     state.projectiles.retain(|p| {
         let in_bounds = p.pos.y > -padding
-            && p.pos.y < screen_height() + padding
+            && p.pos.y < arena.y + padding
             && p.pos.x > -padding
-            && p.pos.x < screen_width() + padding;
+            && p.pos.x < arena.x + padding;
 
-        let alive = p.lifetime < max_lifetime;
+        let alive = p.lifetime < p.weapon_type.config(weapons).lifetime;
 
         in_bounds && alive
     });
 }
 
+/// Reflect a projectile that left the arena back inside (uses up one bounce)
+fn ricochet(projectile: &mut Projectile, arena: Vec2) {
+    let pos = &mut projectile.pos;
+    let velocity = &mut projectile.velocity;
+
+    let bounced_x = (pos.x < 0.0 && velocity.x < 0.0) || (pos.x > arena.x && velocity.x > 0.0);
+    let bounced_y = (pos.y < 0.0 && velocity.y < 0.0) || (pos.y > arena.y && velocity.y > 0.0);
+    if bounced_x {
+        velocity.x = -velocity.x;
+        pos.x = pos.x.clamp(0.0, arena.x);
+    }
+    if bounced_y {
+        velocity.y = -velocity.y;
+        pos.y = pos.y.clamp(0.0, arena.y);
+    }
+    if bounced_x || bounced_y {
+        projectile.modifiers.ricochet -= 1;
+        projectile.target = None; // Homing shots pick a new target after a bounce
+    }
+}
+
 /// Spawn trail particles behind moving projectiles
 fn spawn_projectile_trails(state: &mut GameState, _delta: f32) {
     use macroquad::prelude::*;
@@ -354,66 +432,52 @@ fn spawn_projectile_trails(state: &mut GameState, _delta: f32) {
     let mut trails_to_spawn = Vec::new();
 
     for projectile in &mut state.projectiles {
-        let interval = match projectile.weapon_type {
-            WeaponType::Missile => base_interval * 0.5,
-            WeaponType::Laser => base_interval * 0.01,
-            WeaponType::Plasma => base_interval * 0.75,
-            WeaponType::Bombs => base_interval * 1.0,
-            WeaponType::Bullet => base_interval * 0.5,
-        };
-
-        // Determine how many particles to spawn per interval
-        let particle_count = match projectile.weapon_type {
-            WeaponType::Laser => 20,
-            WeaponType::Missile => 3,
-            WeaponType::Plasma => 3,
-            WeaponType::Bombs => 3,
-            WeaponType::Bullet => 2,
-        };
+        let weapon = projectile.weapon_type.config(&state.config.weapons);
+        let interval = base_interval * weapon.trail_interval;
 
         // Check if it's time to spawn a trail particle
         if projectile.trail_timer >= interval {
             projectile.trail_timer = 0.0; // Reset timer
 
-            // Get trail color based on weapon type and owner
-            let trail_color = match projectile.weapon_type {
-                WeaponType::Missile => match projectile.owner {
-                    ProjectileOwner::Player | ProjectileOwner::Ghost => {
-                        Color::new(1.0, 0.6, 0.2, 0.8)
-                    } // Orange smoke
-                    ProjectileOwner::Enemy => Color::new(0.9, 0.2, 0.2, 0.8), // Red smoke
-                },
-                WeaponType::Laser => Color::new(0.2, 0.8, 1.0, 0.9), // Cyan glow
-                WeaponType::Plasma => Color::new(0.8, 0.2, 1.0, 0.8), // Purple energy
-                WeaponType::Bombs => Color::new(1.0, 0.3, 0.0, 0.7), // Fiery red-orange
-                WeaponType::Bullet => Color::new(1.0, 1.0, 0.6, 0.6), // Faint yellow
+            // Get trail color based on weapon and owner
+            let [r, g, b, a] = match (projectile.owner, weapon.enemy_trail_color) {
+                (ProjectileOwner::Enemy, Some(color)) => color,
+                _ => weapon.trail_color,
             };
+            let trail_color = Color::new(r, g, b, a);
 
-            // Spawn multiple particles for this projectile
-            for _ in 0..particle_count {
-                trails_to_spawn.push((projectile.pos, trail_color, projectile.weapon_type));
+            // Spawn multiple particles for this projectile (style follows the weapon, not stacked affixes)
+            let beam = weapon.modifiers.pierce > 0;
+            for _ in 0..weapon.trail_particles {
+                trails_to_spawn.push((projectile.pos, trail_color, beam));
             }
         }
     }
 
     // Spawn trail particles
-    for (pos, color, weapon_type) in trails_to_spawn {
-        // Add positional spread for lasers to create beam width (prevent overlap)
-        let (spawn_pos, velocity) = if weapon_type == WeaponType::Laser {
+    for (pos, color, beam) in trails_to_spawn {
+        // Add positional spread for piercing beams to create beam width (prevent overlap)
+        let (spawn_pos, velocity) = if beam {
             // Spawn particles in a perpendicular spread to create beam width
             let spread = 5.5; // Beam width radius
-            let offset_x = rand::gen_range(-spread, spread);
-            let offset_y = rand::gen_range(-spread, spread);
+            let offset_x = state.fx_rng.gen_range(-spread, spread);
+            let offset_y = state.fx_rng.gen_range(-spread, spread);
 
             (
                 Vec2::new(pos.x + offset_x, pos.y + offset_y),
-                Vec2::new(rand::gen_range(-5.0, 5.0), rand::gen_range(-5.0, 5.0)),
+                Vec2::new(
+                    state.fx_rng.gen_range(-5.0, 5.0),
+                    state.fx_rng.gen_range(-5.0, 5.0),
+                ),
             )
         } else {
             // Other weapons use normal random drift
             (
                 pos,
-                Vec2::new(rand::gen_range(-10.0, 10.0), rand::gen_range(-10.0, 10.0)),
+                Vec2::new(
+                    state.fx_rng.gen_range(-10.0, 10.0),
+                    state.fx_rng.gen_range(-10.0, 10.0),
+                ),
             )
         };
 
@@ -423,11 +487,11 @@ fn spawn_projectile_trails(state: &mut GameState, _delta: f32) {
             lifetime: cfg.trail_lifetime,
             max_lifetime: cfg.trail_lifetime,
             color,
-            size: if weapon_type == WeaponType::Laser {
+            size: if beam {
                 cfg.trail_size * 0.8
             } else {
                 cfg.trail_size
-            }, // Slightly smaller laser particles
+            }, // Slightly smaller beam particles
             size_decay: cfg.size_decay * 0.5, // Slower decay for trails
         };
 
@@ -435,59 +499,314 @@ fn spawn_projectile_trails(state: &mut GameState, _delta: f32) {
     }
 }
 
-/// Homing behavior: Smoothly steer projectile towards target
-fn homing_behavior(projectile: &mut Projectile, target: Position, delta: f32) {
-    // Calculate direction to target
-    let dx = target.x - projectile.pos.x;
-    let dy = target.y - projectile.pos.y;
-    let distance = (dx * dx + dy * dy).sqrt();
-
-    if distance < 1.0 {
-        return; // Already on target
+/// Homing behavior: turn towards the target (if any) and accelerate to cruise speed
+fn homing_behavior(
+    projectile: &mut Projectile,
+    homing: &HomingTrait,
+    target: Option<Position>,
+    delta: f32,
+) {
+    let speed = projectile.velocity.length();
+    let mut heading = projectile
+        .velocity
+        .try_normalize()
+        .unwrap_or(Vec2::new(0.0, -1.0));
+
+    // Turn towards the target, at most turn_rate degrees/second
+    if let Some(target) = target {
+        let to_target = target - projectile.pos;
+        if to_target.length_squared() >= 1.0 {
+            let max_turn = homing.turn_rate.to_radians() * delta;
+            let turn = heading.angle_between(to_target).clamp(-max_turn, max_turn);
+            heading = Vec2::from_angle(turn).rotate(heading);
+        }
     }
 
-    // Desired velocity (pointing at target)
-    let desired_speed = 300.0; // Constant homing speed (CONFIG HERE)
-    let desired_vel_x = (dx / distance) * desired_speed;
-    let desired_vel_y = (dy / distance) * desired_speed;
+    // Speed up (or slow down) towards cruise speed
+    let max_change = homing.acceleration * delta;
+    let speed = speed + (homing.max_speed - speed).clamp(-max_change, max_change);
 
-    // Smoothly interpolate current velocity towards desired velocity
-    let turn_speed = 8.0; // How fast missile can turn (higher = sharper turns)
+    projectile.velocity = heading * speed;
+}
 
-    projectile.velocity.x += (desired_vel_x - projectile.velocity.x) * turn_speed * delta;
-    projectile.velocity.y += (desired_vel_y - projectile.velocity.y) * turn_speed * delta;
+/// Homing tuning for a shot (enemy-owned shots use `enemy_homing` if set)
+pub fn homing_for(weapon: &WeaponConfig, owner: ProjectileOwner) -> Option<HomingTrait> {
+    match owner {
+        ProjectileOwner::Player | ProjectileOwner::Ghost => weapon.homing,
+        ProjectileOwner::Enemy => weapon.enemy_homing.or(weapon.homing),
+    }
+}
 
-    // Clamp speed to prevent over-acceleration
-    let current_speed = (projectile.velocity.x * projectile.velocity.x
-        + projectile.velocity.y * projectile.velocity.y)
-        .sqrt();
+/// Is the target within the lock cone around the heading?
+fn in_lock_cone(pos: Position, heading: Vec2, target: Position, homing: &HomingTrait) -> bool {
+    let to_target = target - pos;
+    if homing.lock_cone >= 180.0 || to_target.length_squared() < 1.0 {
+        return true;
+    }
+    heading.angle_between(to_target).abs() <= homing.lock_cone.to_radians()
+}
 
-    if current_speed > desired_speed * 1.5 {
-        let scale = (desired_speed * 1.5) / current_speed;
-        projectile.velocity.x *= scale;
-        projectile.velocity.y *= scale;
+/// Pick a target inside the lock cone (nearest enemy, or the player for enemy shots)
+fn acquire_target(
+    state: &GameState,
+    pos: Position,
+    heading: Vec2,
+    homing: &HomingTrait,
+    owner: ProjectileOwner,
+) -> Option<(EntityId, Position)> {
+    match owner {
+        ProjectileOwner::Player | ProjectileOwner::Ghost => {
+            // Broadphase shortcut, unless the nearest one was just killed
+            if homing.lock_cone >= 180.0
+                && let Some(entry) = state.spatial.nearest(pos, EntityKind::Enemy)
+                && state.entity_pos(entry.id).is_some()
+            {
+                return Some((entry.id, entry.pos));
+            }
+            state
+                .enemies
+                .iter()
+                .filter(|enemy| enemy.stats.health > 0.0)
+                .filter(|enemy| in_lock_cone(pos, heading, enemy.pos, homing))
+                .min_by(|a, b| {
+                    let a = a.pos.distance_squared(pos);
+                    let b = b.pos.distance_squared(pos);
+                    a.total_cmp(&b)
+                })
+                .map(|enemy| (enemy.id, enemy.pos))
+        }
+        ProjectileOwner::Enemy => in_lock_cone(pos, heading, state.player.pos, homing)
+            .then_some((EntityId::PLAYER, state.player.pos)),
     }
 }
 
-/// Find the nearest enemy to a given position and return its index
-fn find_nearest_enemy_index(pos: Position, enemies: &[crate::models::Enemy]) -> Option<usize> {
-    if enemies.is_empty() {
-        return None;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AmmoTrait, HeatTrait};
+
+    fn spawn_enemy(state: &mut GameState, pos: Position) -> EntityId {
+        let basic = EntityType::from_name("BasicFighter", &state.config.archetypes).unwrap();
+        let id = state.entity_ids.next();
+        let enemy = Enemy::from_entity_type(id, basic, pos, &state.config);
+        state.enemies.push(enemy);
+        id
     }
 
-    enemies
-        .iter()
-        .enumerate()
-        .min_by(|(_, a), (_, b)| {
-            let dist_a = (a.pos.x - pos.x).powi(2) + (a.pos.y - pos.y).powi(2);
-            let dist_b = (b.pos.x - pos.x).powi(2) + (b.pos.y - pos.y).powi(2);
-            dist_a.partial_cmp(&dist_b).unwrap()
-        })
-        .map(|(idx, _)| idx)
-}
+    /// Fire one player shot of `weapon` straight up from `pos`
+    fn player_shot(state: &mut GameState, weapon: &str, pos: Position) {
+        let weapon = WeaponType::from_name(weapon, &state.config.weapons).unwrap();
+        crate::game::rebuild_spatial(state);
+        let params = FireWeaponParams {
+            shooter_pos: pos,
+            shooter: None,
+            owner: ProjectileOwner::Player,
+            weapon,
+            direction: FiringDirection::Up,
+            damage_multiplier: 1.0,
+            level: 1,
+            modifiers: Modifiers::default(),
+        };
+        fire_weapon(params, state);
+    }
 
-/// Find the nearest enemy to a given position (for backward compatibility)
-#[allow(dead_code)] // May be used for future weapon targeting improvements
-fn find_nearest_enemy(pos: Position, enemies: &[crate::models::Enemy]) -> Option<Position> {
-    find_nearest_enemy_index(pos, enemies).map(|idx| enemies[idx].pos)
+    /// Player with a single `weapon` slot tuned by `tune`
+    fn player_with(weapon: &str, tune: impl FnOnce(&mut WeaponConfig)) -> GameState {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let weapon = WeaponType::from_name(weapon, &state.config.weapons).unwrap();
+        let config = &mut state.config.weapons[weapon.0 as usize];
+        tune(config);
+        let slot = WeaponSlot::new(config);
+        state.player.weapon = vec![weapon];
+        state.player.slots = vec![slot];
+        state
+    }
+
+    fn missile_homing(state: &GameState) -> HomingTrait {
+        let missile = WeaponType::from_name("Missile", &state.config.weapons).unwrap();
+        missile.config(&state.config.weapons).homing.unwrap()
+    }
+
+    #[test]
+    fn test_homing_turn_rate_is_limited() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        player_shot(&mut state, "Missile", Position::new(400.0, 500.0));
+        let homing = HomingTrait {
+            turn_rate: 90.0,
+            acceleration: 0.0,
+            max_speed: 100.0,
+            ..missile_homing(&state)
+        };
+        let projectile = &mut state.projectiles[0];
+        projectile.pos = Position::ZERO;
+        projectile.velocity = Vec2::new(0.0, -100.0);
+
+        // Target straight to the right: only 9° of the 90° turn in 0.1s
+        homing_behavior(projectile, &homing, Some(Position::new(500.0, 0.0)), 0.1);
+        let turned = Vec2::NEG_Y.angle_between(projectile.velocity).to_degrees();
+        assert!((turned - 9.0).abs() < 0.01, "turned {turned}°");
+        assert!((projectile.velocity.length() - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_lock_cone() {
+        let state = GameState::with_arena(800.0, 600.0);
+        let homing = HomingTrait {
+            lock_cone: 45.0,
+            ..missile_homing(&state)
+        };
+        let up = Vec2::NEG_Y;
+        assert!(in_lock_cone(
+            Position::ZERO,
+            up,
+            Position::new(30.0, -100.0),
+            &homing
+        ));
+        assert!(!in_lock_cone(
+            Position::ZERO,
+            up,
+            Position::new(100.0, 0.0),
+            &homing
+        ));
+        assert!(!in_lock_cone(
+            Position::ZERO,
+            up,
+            Position::new(0.0, 100.0),
+            &homing
+        ));
+
+        let anywhere = HomingTrait {
+            lock_cone: 180.0,
+            ..homing
+        };
+        assert!(in_lock_cone(
+            Position::ZERO,
+            up,
+            Position::new(0.0, 100.0),
+            &anywhere
+        ));
+    }
+
+    #[test]
+    fn test_homing_reacquires_when_target_dies() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let near = spawn_enemy(&mut state, Position::new(400.0, 300.0));
+        let far = spawn_enemy(&mut state, Position::new(450.0, 150.0));
+        player_shot(&mut state, "Missile", Position::new(400.0, 500.0));
+        assert_eq!(state.projectiles[0].target, Some(near));
+
+        // Killed but not cleaned up yet - the lock moves on this tick
+        state.enemies[0].stats.health = 0.0;
+        update_projectiles(&mut state, 0.01);
+        assert_eq!(state.projectiles[0].target, Some(far));
+
+        // Without reacquire the shot gives up and flies straight
+        state.projectiles[0].homing.as_mut().unwrap().reacquire = false;
+        state.enemies[1].stats.health = 0.0;
+        update_projectiles(&mut state, 0.01);
+        assert!(state.projectiles[0].homing.is_none());
+    }
+
+    #[test]
+    fn test_proximity_fuse_widens_hits() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        let missile = WeaponType::from_name("Missile", &state.config.weapons).unwrap();
+        state.config.weapons[missile.0 as usize]
+            .homing
+            .as_mut()
+            .unwrap()
+            .proximity_radius = 60.0;
+        spawn_enemy(&mut state, Position::new(450.0, 300.0));
+        let health = state.enemies[0].stats.health;
+
+        // 50px off to the side - a contact-only missile would fly past
+        player_shot(&mut state, "Missile", Position::new(400.0, 300.0));
+        crate::game::check_projectile_collisions(&mut state);
+        assert!(state.enemies[0].stats.health < health);
+        assert!(state.projectiles.is_empty());
+    }
+
+    #[test]
+    fn test_heat_overheats_and_cools_down() {
+        let mut state = player_with("Laser", |config| {
+            config.fire_rate = 0.1;
+            config.ammo = None;
+            config.heat = Some(HeatTrait {
+                per_shot: 0.4,
+                cooling: 0.5,
+                recover_at: 0.3,
+            });
+        });
+
+        // Three pulls: 0.4, 0.8, then capped at 1.0 and locked
+        for _ in 0..3 {
+            assert!(use_weapon_slot(&mut state, 0));
+            update_weapon_slots(&mut state, 0.1);
+        }
+        assert!(state.player.slots[0].overheated);
+        assert!(!use_weapon_slot(&mut state, 0));
+
+        // Still locked above recover_at even though the cooldown is long over
+        update_weapon_slots(&mut state, 1.0);
+        assert!(state.player.slots[0].overheated);
+        assert!(!use_weapon_slot(&mut state, 0));
+
+        update_weapon_slots(&mut state, 0.5);
+        assert!(!state.player.slots[0].overheated);
+        assert!(use_weapon_slot(&mut state, 0));
+    }
+
+    #[test]
+    fn test_ammo_runs_dry_and_regenerates() {
+        let mut state = player_with("Missile", |config| {
+            config.fire_rate = 0.1;
+            config.heat = None;
+            config.ammo = Some(AmmoTrait {
+                max: 2,
+                regen_time: 1.0,
+            });
+        });
+
+        for _ in 0..2 {
+            assert!(use_weapon_slot(&mut state, 0));
+            update_weapon_slots(&mut state, 0.1);
+        }
+        assert_eq!(state.player.slots[0].ammo, 0);
+        assert!(!use_weapon_slot(&mut state, 0));
+
+        // One round per regen_time, never past max
+        update_weapon_slots(&mut state, 0.5);
+        assert_eq!(state.player.slots[0].ammo, 0);
+        update_weapon_slots(&mut state, 0.5);
+        assert_eq!(state.player.slots[0].ammo, 1);
+        for _ in 0..5 {
+            update_weapon_slots(&mut state, 1.0);
+        }
+        assert_eq!(state.player.slots[0].ammo, 2);
+        assert!(use_weapon_slot(&mut state, 0));
+    }
+
+    #[test]
+    fn test_ricochet_reflects_and_uses_up_bounces() {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        player_shot(&mut state, "Bullet", Position::new(400.0, 5.0));
+        let projectile = &mut state.projectiles[0];
+        projectile.modifiers.ricochet = 1;
+        let speed = projectile.velocity.y;
+        assert!(speed < 0.0);
+
+        // Crosses the top edge: flipped back down, clamped inside, one bounce spent
+        update_projectiles(&mut state, 0.1);
+        let projectile = &state.projectiles[0];
+        assert_eq!(projectile.velocity.y, -speed);
+        assert_eq!(projectile.pos.y, 0.0);
+        assert_eq!(projectile.modifiers.ricochet, 0);
+
+        // Out of bounces: the next edge is the end of it
+        state.projectiles[0].pos.y = 590.0;
+        for _ in 0..20 {
+            update_projectiles(&mut state, 0.1);
+        }
+        assert!(state.projectiles.is_empty());
+    }
 }
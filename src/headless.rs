@@ -0,0 +1,408 @@
+//! Headless simulation - steps GameState without a window (balance runs, batch jobs)
+
+use crate::config::Modifiers;
+use crate::game::{
+    Action, FireWeaponParams, FiringDirection, InputState, fire_weapon, update_all_systems,
+    update_collision_systems,
+};
+use crate::models::{Enemy, EntityType, GameState, Ghost, ProjectileOwner, WeaponType};
+use crate::replay::{Replay, ReplayPlayer, ReplayRecorder, state_checksum};
+use macroquad::math::Vec2;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Projectiles kept alive by `--stress` when no count is given
+const DEFAULT_STRESS_PROJECTILES: usize = 2000;
+
+/// Command line options for a headless run
+pub struct HeadlessOptions {
+    pub arena_width: f32,
+    pub arena_height: f32,
+    pub tick_rate: Option<f32>, // Override simulation tick rate from config
+    pub max_time: f32,          // Give up after this many simulated seconds
+    pub waves: Option<usize>,   // Override wave count from config
+    pub seed: Option<u64>,      // Override RNG seed from config
+    pub record: Option<PathBuf>, // Save the autopilot's run as a replay
+    pub replay: Option<PathBuf>, // Verify a replay instead of running the autopilot
+    pub stress: Option<usize>,  // Benchmark the broadphase with this many projectiles
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            arena_width: 800.0,
+            arena_height: 600.0,
+            tick_rate: None,
+            max_time: 900.0, // 15 minutes of game time
+            waves: None,
+            seed: None,
+            record: None,
+            replay: None,
+            stress: None,
+        }
+    }
+}
+
+impl HeadlessOptions {
+    /// Parse `--max-time <secs>`, `--waves <n>`, `--tick-rate <hz>`, `--seed <n>`,
+    /// `--record <file>`, `--replay <file>` and `--stress [projectiles]` (unknown flags are ignored)
+    pub fn from_args(args: &[String]) -> Self {
+        let mut options = Self::default();
+        let mut iter = args.iter().peekable();
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--max-time" => {
+                    if let Some(value) = iter.next().and_then(|v| v.parse().ok()) {
+                        options.max_time = value;
+                    }
+                }
+                "--waves" => {
+                    options.waves = iter.next().and_then(|v| v.parse().ok());
+                }
+                "--tick-rate" => {
+                    let rate = iter.next().and_then(|v| v.parse::<f32>().ok());
+                    match rate {
+                        Some(rate) if rate.is_finite() && rate > 0.0 => {
+                            options.tick_rate = Some(rate)
+                        }
+                        _ => println!("✗ --tick-rate needs a positive number - using the config's"),
+                    }
+                }
+                "--seed" => {
+                    options.seed = iter.next().and_then(|v| v.parse().ok());
+                }
+                "--record" => {
+                    options.record = iter.next().map(PathBuf::from);
+                }
+                "--replay" => {
+                    options.replay = iter.next().map(PathBuf::from);
+                }
+                "--stress" => {
+                    // Count is optional
+                    let count = iter.peek().and_then(|v| v.parse().ok());
+                    if count.is_some() {
+                        iter.next();
+                    }
+                    options.stress = Some(count.unwrap_or(DEFAULT_STRESS_PROJECTILES));
+                }
+                _ => {}
+            }
+        }
+
+        options
+    }
+}
+
+/// Outcome of a headless run
+pub struct RunSummary {
+    pub waves_cleared: usize,
+    pub total_waves: usize,
+    pub player_health: f32,
+    pub player_max_health: f32,
+    pub kills: usize,
+    pub grazes: usize,
+    pub ticks: u64,
+    pub sim_time: f32,
+    pub seed: u64,
+}
+
+impl RunSummary {
+    fn from_state(state: &GameState, ticks: u64, sim_time: f32) -> Self {
+        Self {
+            waves_cleared: state.wave_manager.waves_cleared,
+            total_waves: state.wave_manager.total_waves,
+            player_health: state.player.stats.health.max(0.0),
+            player_max_health: state.player.stats.max_health,
+            kills: state.enemies_killed,
+            grazes: state.grazes,
+            ticks,
+            sim_time,
+            seed: state.seed,
+        }
+    }
+
+    pub fn print(&self) {
+        println!("─── Headless run summary ───");
+        println!("Waves cleared: {}/{}", self.waves_cleared, self.total_waves);
+        println!(
+            "Player HP:     {:.0}/{:.0}",
+            self.player_health, self.player_max_health
+        );
+        println!("Kills:         {}", self.kills);
+        println!("Grazes:        {}", self.grazes);
+        println!("Seed:          {}", self.seed);
+        println!(
+            "Sim time:      {:.1}s ({} ticks)",
+            self.sim_time, self.ticks
+        );
+    }
+}
+
+/// Run the Lua wave system to completion with a fixed arena and the autopilot
+pub fn run(options: HeadlessOptions) -> RunSummary {
+    if let Some(path) = &options.replay {
+        return run_replay(path);
+    }
+    if let Some(projectiles) = options.stress {
+        return run_stress(&options, projectiles);
+    }
+
+    let mut state = GameState::with_arena(options.arena_width, options.arena_height);
+    if let Some(seed) = options.seed {
+        state.reseed(seed);
+    }
+
+    // Headless runs are always wave-based
+    state.config.spawning.wave_mode = true;
+    if let Some(waves) = options.waves {
+        state.config.spawning.wave_count = waves; // Keep config in sync for replay snapshots
+        state.wave_manager.total_waves = waves;
+    }
+
+    // Same fixed step as the windowed loop
+    let tick_rate = options
+        .tick_rate
+        .unwrap_or(state.config.simulation.tick_rate);
+    let delta = 1.0 / tick_rate;
+    state.config.simulation.tick_rate = tick_rate;
+    let mut ticks: u64 = 0;
+    let mut sim_time = 0.0;
+
+    let mut recorder = options.record.as_ref().map(|_| ReplayRecorder::new(&state));
+
+    while sim_time < options.max_time {
+        let input = autopilot(&state);
+        update_all_systems(&mut state, &input, delta);
+        if let Some(recorder) = &mut recorder {
+            recorder.record_tick(&input, &state);
+        }
+
+        ticks += 1;
+        sim_time += delta;
+
+        if state.player.stats.health <= 0.0 {
+            println!("✘ Player destroyed at {sim_time:.1}s");
+            break;
+        }
+
+        if state.wave_manager.is_game_complete() {
+            break;
+        }
+    }
+
+    if sim_time >= options.max_time {
+        println!("✘ Time limit reached ({:.0}s)", options.max_time);
+    }
+
+    if let (Some(recorder), Some(path)) = (&recorder, &options.record) {
+        recorder.save(path);
+    }
+
+    let summary = RunSummary::from_state(&state, ticks, sim_time);
+    summary.print();
+    summary
+}
+
+/// Play a replay file back as fast as possible and report divergence
+fn run_replay(path: &std::path::Path) -> RunSummary {
+    let replay = match Replay::load(path) {
+        Ok(replay) => replay,
+        Err(e) => {
+            println!("✗ Failed to load replay {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    };
+
+    let mut player = ReplayPlayer::new(replay);
+    let mut state = player.new_state();
+    let delta = 1.0 / state.config.simulation.tick_rate;
+
+    while !player.is_finished() {
+        player.step(&mut state);
+    }
+    player.report();
+
+    let summary = RunSummary::from_state(&state, player.tick(), player.tick() as f32 * delta);
+    summary.print();
+    summary
+}
+
+/// Enemies/ghosts in the stress arena (projectiles are the variable load)
+const STRESS_ENEMIES: usize = 300;
+const STRESS_GHOSTS: usize = 60;
+const STRESS_TICKS: u64 = 600;
+
+/// Time the broadphase against brute force (cell size 0) on the same scenario
+fn run_stress(options: &HeadlessOptions, projectiles: usize) -> RunSummary {
+    println!(
+        "─── Stress test: {projectiles} projectiles, {STRESS_ENEMIES} enemies, {STRESS_GHOSTS} ghosts, {STRESS_TICKS} ticks ───"
+    );
+
+    let (brute_ms, brute_sum, _) = stress_pass(options, projectiles, true);
+    let (grid_ms, grid_sum, state) = stress_pass(options, projectiles, false);
+
+    println!("Brute force:   {brute_ms:.3} ms/tick");
+    println!(
+        "Spatial grid:  {grid_ms:.3} ms/tick ({}px cells)",
+        state.config.collision.spatial_cell_size
+    );
+    println!(
+        "Speedup:       {:.1}x",
+        brute_ms / grid_ms.max(f64::EPSILON)
+    );
+    if brute_sum == grid_sum {
+        println!("✔ Results identical (checksum {grid_sum:08x})");
+    } else {
+        println!("✘ Results differ (brute force {brute_sum:08x}, grid {grid_sum:08x})");
+    }
+
+    let delta = 1.0 / state.config.simulation.tick_rate;
+    RunSummary::from_state(&state, STRESS_TICKS, STRESS_TICKS as f32 * delta)
+}
+
+/// One timed pass - returns (ms per tick, final state checksum, state)
+fn stress_pass(
+    options: &HeadlessOptions,
+    projectiles: usize,
+    brute_force: bool,
+) -> (f64, u32, GameState) {
+    let mut state = GameState::with_arena(options.arena_width, options.arena_height);
+    state.reseed(options.seed.unwrap_or(1));
+    if brute_force {
+        state.config.collision.spatial_cell_size = 0.0;
+    }
+    let tick_rate = options
+        .tick_rate
+        .unwrap_or(state.config.simulation.tick_rate);
+    state.config.simulation.tick_rate = tick_rate;
+    let delta = 1.0 / tick_rate;
+    populate_stress_arena(&mut state);
+
+    let mut elapsed = 0.0;
+    for _ in 0..STRESS_TICKS {
+        refill_stress_projectiles(&mut state, projectiles);
+
+        let start = Instant::now();
+        update_collision_systems(&mut state, delta);
+        elapsed += start.elapsed().as_secs_f64();
+    }
+
+    let ms_per_tick = elapsed * 1000.0 / STRESS_TICKS as f64;
+    (ms_per_tick, state_checksum(&state), state)
+}
+
+/// Scatter unkillable enemies (top) and ghosts (bottom) across the arena
+fn populate_stress_arena(state: &mut GameState) {
+    let types: Vec<EntityType> = EntityType::all(&state.config.archetypes).collect();
+    let arena = state.arena;
+
+    for i in 0..STRESS_ENEMIES {
+        let pos = Vec2::new(
+            state.rng.gen_range(0.0, arena.x),
+            state.rng.gen_range(0.0, arena.y * 0.6),
+        );
+        let id = state.entity_ids.next();
+        let mut enemy = Enemy::from_entity_type(id, types[i % types.len()], pos, &state.config);
+        enemy.stats.health = 1.0e9;
+        enemy.stats.max_health = 2.0e9; // Leave room for healers
+        state.enemies.push(enemy);
+    }
+
+    for i in 0..STRESS_GHOSTS {
+        let pos = Vec2::new(
+            state.rng.gen_range(0.0, arena.x),
+            state.rng.gen_range(arena.y * 0.6, arena.y),
+        );
+        let id = state.entity_ids.next();
+        let mut ghost = Ghost::from_entity_type(id, types[i % types.len()], pos, &state.config);
+        ghost.stats.health = 1.0e9;
+        state.ghosts.push(ghost);
+    }
+
+    state.player.stats.health = 1.0e9;
+}
+
+/// Top the projectile count back up with a mix of player, ghost and enemy shots
+fn refill_stress_projectiles(state: &mut GameState, count: usize) {
+    const WEAPONS: [&str; 4] = ["Bullet", "Plasma", "Missile", "Bombs"];
+    let weapons: Vec<WeaponType> = WEAPONS
+        .iter()
+        .filter_map(|name| WeaponType::from_name(name, &state.config.weapons))
+        .collect();
+    let arena = state.arena;
+
+    while state.projectiles.len() < count {
+        let shooter_pos = Vec2::new(
+            state.rng.gen_range(0.0, arena.x),
+            state.rng.gen_range(0.0, arena.y),
+        );
+        let weapon = weapons[state.rng.gen_range(0, weapons.len())];
+        let (owner, direction) = match state.rng.gen_range(0, 3) {
+            0 => (ProjectileOwner::Player, FiringDirection::Up),
+            1 => (ProjectileOwner::Ghost, FiringDirection::Up),
+            _ => (ProjectileOwner::Enemy, FiringDirection::Down),
+        };
+
+        fire_weapon(
+            FireWeaponParams {
+                shooter_pos,
+                shooter: None,
+                owner,
+                weapon,
+                direction,
+                damage_multiplier: 1.0,
+                level: 1,
+                modifiers: Modifiers::default(),
+            },
+            state,
+        );
+    }
+}
+
+/// Simple scripted pilot: tracks the nearest enemy, fires, summons and parries
+fn autopilot(state: &GameState) -> InputState {
+    let player = &state.player;
+    let mut input = InputState::default();
+
+    // Line up under the nearest enemy (horizontal tracking only)
+    let target = state.enemies.iter().min_by(|a, b| {
+        let dist_a = (a.pos - player.pos).length_squared();
+        let dist_b = (b.pos - player.pos).length_squared();
+        dist_a.total_cmp(&dist_b)
+    });
+
+    if let Some(enemy) = target {
+        let dx = enemy.pos.x - player.pos.x;
+        if dx > 10.0 {
+            input.hold(Action::MoveRight);
+        } else if dx < -10.0 {
+            input.hold(Action::MoveLeft);
+        }
+
+        // Bullets for steady damage, missiles for homing
+        input.hold(Action::FireSlot(0));
+        input.hold(Action::FireSlot(2));
+    }
+
+    // Parry incoming enemy missiles
+    let parry_radius = state.config.collision.player_radius + 20.0;
+    let missile_incoming = state.projectiles.iter().any(|p| {
+        p.owner == ProjectileOwner::Enemy
+            && p.weapon_type.config(&state.config.weapons).parryable
+            && (p.pos - player.pos).length() < parry_radius
+    });
+    if player.parry_cooldown <= 0.0 && missile_incoming {
+        input.press(Action::Parry);
+    }
+
+    // Deploy ghosts whenever the current formation can be filled
+    if state
+        .ghost_formation
+        .is_valid_for_count(player.available_ghosts.len())
+    {
+        input.press(Action::SummonFormation);
+    }
+
+    input
+}
@@ -3,14 +3,18 @@ use macroquad::prelude::*;
 mod config;
 mod defaults;
 mod game;
+mod headless;
 mod models;
 mod rendering;
+mod replay;
 mod scripting;
 
 use crate::config::GameConfig;
-use game::update_all_systems;
+use game::{Bindings, InputState, update_all_systems};
 use models::GameState;
-use rendering::{render_game, render_ui};
+use rendering::{render_game, render_replay_overlay, render_ui};
+use replay::{Replay, ReplayPlayer, ReplayRecorder};
+use std::path::PathBuf;
 
 /// Window configuration
 fn window_conf() -> Conf {
@@ -23,9 +27,41 @@ fn window_conf() -> Conf {
     }
 }
 
-#[macroquad::main(window_conf)]
-async fn main() {
-    let space_texture = match load_texture("assets/art/space_2.png").await {
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Headless mode: simulate waves without opening a window
+    if args.iter().any(|arg| arg == "--headless") {
+        headless::run(headless::HeadlessOptions::from_args(&args));
+        return;
+    }
+
+    // Replay playback: watch a recorded run
+    if let Some(path) = arg_value(&args, "--replay") {
+        match Replay::load(path.as_ref()) {
+            Ok(replay) => {
+                macroquad::Window::from_config(
+                    window_conf(),
+                    run_replay(ReplayPlayer::new(replay)),
+                );
+            }
+            Err(e) => println!("✗ Failed to load replay {path}: {e}"),
+        }
+        return;
+    }
+
+    let seed = arg_value(&args, "--seed").and_then(|v| v.parse().ok());
+    macroquad::Window::from_config(window_conf(), run_game(seed));
+}
+
+/// Value following a flag (`--seed 42` -> "42")
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a String> {
+    let idx = args.iter().position(|arg| arg == flag)?;
+    args.get(idx + 1)
+}
+
+async fn load_background() -> Option<Texture2D> {
+    match load_texture("assets/art/space_2.png").await {
         Ok(tex) => {
             println!("✓ Space background loaded successfully!");
             Some(tex)
@@ -34,19 +70,44 @@ async fn main() {
             println!("✗ Failed to load background: {e}");
             None
         }
-    };
+    }
+}
+
+/// Windowed game loop
+async fn run_game(seed: Option<u64>) {
+    let space_texture = load_background().await;
 
     let mut game_state = GameState::new();
+    if let Some(seed) = seed {
+        game_state.reseed(seed);
+    }
+    let mut bindings =
+        Bindings::from_config(&game_state.config.bindings, &game_state.config.archetypes);
+
+    // Every run is recorded (F9 saves, death saves automatically)
+    let mut recorder = ReplayRecorder::new(&game_state);
+    let replay_path = PathBuf::from(format!("replays/replay-{}.toml", game_state.seed));
+    let mut death_saved = false;
+
+    // Fixed-timestep accumulator (simulation runs at tick_rate, rendering at display rate)
+    let mut accumulator = 0.0;
+    let mut pending_input = InputState::default();
+    let mut show_ai = false; // F10 overlay (render-only, survives config reloads)
 
     loop {
-        let delta = get_frame_time();
+        let delta = get_frame_time().min(game_state.config.simulation.max_frame_time);
 
-        // Hot-reload config with R key
+        // Hot-reload config with R key (R/F9/F10 are kept out of [bindings], see RESERVED_KEYS)
         if is_key_pressed(KeyCode::R) {
             match GameConfig::try_load_from_file() {
                 Ok(new_config) => {
                     println!("✓ Config reloaded from config.toml!");
                     game_state.apply_config(&new_config);
+                    bindings = Bindings::from_config(
+                        &game_state.config.bindings,
+                        &game_state.config.archetypes,
+                    );
+                    println!("✗ Replay keeps the startup config - playback may diverge after this");
                 }
                 Err(e) => {
                     println!("✗ Failed to reload config: {e}");
@@ -57,11 +118,102 @@ async fn main() {
         // Update background scroll offset
         game_state.bg_scroll_offset += game_state.config.background.scroll_speed * delta;
 
-        // Update game logic
-        update_all_systems(&mut game_state, delta);
+        // Update game logic in fixed steps
+        let tick = 1.0 / game_state.config.simulation.tick_rate;
+        pending_input.accumulate(bindings.read_keyboard());
+        accumulator += delta;
+
+        while accumulator >= tick {
+            update_all_systems(&mut game_state, &pending_input, tick);
+            recorder.record_tick(&pending_input, &game_state);
+            pending_input.clear_presses();
+            accumulator -= tick;
+        }
+
+        // Save replay on demand, or once when the player dies
+        if is_key_pressed(KeyCode::F9) {
+            recorder.save(&replay_path);
+        }
+        if is_key_pressed(KeyCode::F10) {
+            show_ai = !show_ai;
+        }
+        if game_state.player.stats.health <= 0.0 && !death_saved {
+            recorder.save(&replay_path);
+            death_saved = true;
+        }
+
+        // Blend factor between previous and current simulation state
+        let alpha = accumulator / tick;
+
+        render_game(&game_state, &space_texture, alpha, show_ai);
+        render_ui(&game_state);
+
+        next_frame().await
+    }
+}
+
+/// Replay playback loop (P = pause, N = step one tick while paused, 1/2/4 = speed, F10 = AI overlay)
+async fn run_replay(mut player: ReplayPlayer) {
+    let space_texture = load_background().await;
+
+    let mut game_state = player.new_state();
+    let mut accumulator = 0.0;
+    let mut paused = false;
+    let mut speed = 1.0;
+    let mut reported = false;
+    let mut show_ai = false;
+
+    loop {
+        let delta = get_frame_time().min(game_state.config.simulation.max_frame_time);
+
+        // Playback controls
+        if is_key_pressed(KeyCode::P) {
+            paused = !paused;
+        }
+        if is_key_pressed(KeyCode::Key1) {
+            speed = 1.0;
+        }
+        if is_key_pressed(KeyCode::Key2) {
+            speed = 2.0;
+        }
+        if is_key_pressed(KeyCode::Key4) {
+            speed = 4.0;
+        }
+        if is_key_pressed(KeyCode::F10) {
+            show_ai = !show_ai;
+        }
+
+        game_state.bg_scroll_offset += game_state.config.background.scroll_speed * delta;
+
+        let tick = 1.0 / game_state.config.simulation.tick_rate;
+        if paused {
+            accumulator = 0.0;
+            if is_key_pressed(KeyCode::N) {
+                player.step(&mut game_state);
+            }
+        } else {
+            accumulator += delta * speed;
+            while accumulator >= tick && !player.is_finished() {
+                player.step(&mut game_state);
+                accumulator -= tick;
+            }
+        }
+
+        if player.is_finished() && !reported {
+            player.report();
+            reported = true;
+        }
+
+        // Paused/finished frames show the latest tick as-is
+        let alpha = if paused || player.is_finished() {
+            1.0
+        } else {
+            accumulator / tick
+        };
 
-        render_game(&game_state, &space_texture);
+        render_game(&game_state, &space_texture, alpha, show_ai);
         render_ui(&game_state);
+        render_replay_overlay(&player, paused, speed);
 
         next_frame().await
     }
@@ -1,38 +1,93 @@
-use crate::config::GameConfig;
+use crate::config::{ArchetypeConfig, GameConfig, HomingTrait, Modifiers, WeaponConfig};
+use crate::game::rng::GameRng;
 use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
 
 /// Type alias for Position - using macroquad's Vec2 for built-in vector math
 pub type Position = Vec2;
 
+/// Stable handle for the player, enemies and ghosts (never reused, survives Vec removals)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntityId(pub u64);
+
+impl EntityId {
+    pub const PLAYER: EntityId = EntityId(0);
+}
+
+/// Hands out fresh entity ids (0 is reserved for the player)
+#[derive(Clone, Debug)]
+pub struct EntityIds {
+    next: u64,
+}
+
+impl Default for EntityIds {
+    fn default() -> Self {
+        Self { next: 1 }
+    }
+}
+
+impl EntityIds {
+    pub fn next(&mut self) -> EntityId {
+        let id = EntityId(self.next);
+        self.next += 1;
+        id
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct Stats {
     pub health: f32,
     pub max_health: f32,
     pub damage: f32,
+    pub shield: Option<Shield>, // From the archetype's [archetypes.shield]
 }
 
-pub struct WeaponStats {
-    pub damage: f32,           // Base damage per projectile
-    pub fire_rate: f32,        // Cooldown between shots (in seconds)
-    pub projectile_speed: f32, // How fast projectiles travel (pixels/sec)
+/// Runtime state of a shield layer (config in `ShieldTrait`)
+#[derive(Clone, Copy, Debug)]
+pub struct Shield {
+    pub current: f32,
+    pub max: f32,
+    pub recharge_timer: f32, // Regen starts when this runs out (reset by every absorbed hit)
 }
 
 pub struct Projectile {
     pub pos: Position,
+    pub prev_pos: Position, // Position at start of tick (render interpolation)
     pub velocity: Position,
     pub damage: f32,
-    pub weapon_type: WeaponType, // Determines behavior (piercing, homing, etc)
+    pub weapon_type: WeaponType, // Weapon that fired it (visuals, trails, lifetime)
     pub owner: ProjectileOwner,  // To differentiate between player, ghost and enemy projectiles
 
-    // Weapon-specific behavior flags
-    pub piercing: bool,        // Laser: doesn't despawn on hit
-    pub homing: bool,          // Missile: tracks nearest enemy
-    pub explosion_radius: f32, // Bombs: AOE damage on impact (0.0 = no explosion)
+    // Behavior copied from the weapon when fired
+    pub modifiers: Modifiers, // Affixes left (pierce/ricochet counts tick down)
+    pub homing: Option<HomingTrait>, // Steering (player or enemy tuning, None = flies straight)
+    pub explosion_radius: f32, // AOE damage on impact (0.0 = no explosion)
+    pub detonate: bool,       // Bomb: blow up this tick (remote detonation)
 
     // Homing missile data
-    pub locked_target_index: Option<usize>, // Which enemy index is locked (None = find new target)
-    pub lifetime: f32,                      // How long projectile has existed (for cleanup)
-    pub trail_timer: f32,                   // Timer for spawning trail particles
+    pub target: Option<EntityId>, // Locked target (None = find new target)
+    pub lifetime: f32,            // How long projectile has existed (for cleanup)
+    pub trail_timer: f32,         // Timer for spawning trail particles
+    pub last_hit: Option<EntityId>, // Enemy it just hit (pierce/split don't hit it again)
+    pub graze: Graze,             // Near-miss tracking (enemy projectiles only)
+}
+
+/// How close an enemy projectile has come to the player (see game/graze.rs)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Graze {
+    #[default]
+    Clear, // Hasn't come close yet
+    Inside,  // In the graze radius - counts once it leaves without hitting
+    Counted, // Already rewarded
+}
+
+impl Projectile {
+    /// Collision radius (widened by a homing proximity fuse)
+    pub fn hit_radius(&self, projectile_radius: f32) -> f32 {
+        self.homing.map_or(projectile_radius, |h| {
+            projectile_radius.max(h.proximity_radius)
+        })
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -53,128 +108,94 @@ pub enum ProjectileOwner {
     Ghost,
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum WeaponType {
-    Bullet,
-    Laser,
-    Missile,
-    Plasma, // Not implemented yet
-    Bombs,  // Not implemented yet
-}
+/// Handle into `config.weapons` (weapons are data, see `[[weapons]]`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct WeaponType(pub u16);
 
 impl WeaponType {
-    /// Parse weapon type from string
-    pub fn from_string(s: &str) -> Option<Self> {
-        match s {
-            "Bullet" => Some(WeaponType::Bullet),
-            "Laser" => Some(WeaponType::Laser),
-            "Missile" => Some(WeaponType::Missile),
-            "Plasma" => Some(WeaponType::Plasma),
-            "Bombs" => Some(WeaponType::Bombs),
-            _ => None,
-        }
+    /// Look up a weapon by name (as used in archetypes and `starting_weapons`)
+    pub fn from_name(name: &str, weapons: &[WeaponConfig]) -> Option<Self> {
+        weapons
+            .iter()
+            .position(|w| w.name == name)
+            .map(|index| WeaponType(index as u16))
     }
 
-    /// Get weapon stats from config
-    pub fn get_weapon_stats(&self, config: &crate::config::WeaponsConfig) -> WeaponStats {
-        let weapon_cfg = match self {
-            WeaponType::Bullet => &config.bullet,
-            WeaponType::Laser => &config.laser,
-            WeaponType::Missile => &config.missile,
-            WeaponType::Plasma => &config.plasma,
-            WeaponType::Bombs => &config.bombs,
-        };
+    /// Resolve a list of names, skipping unknown ones (falls back to the first weapon if none are valid)
+    pub fn from_names(names: &[String], weapons: &[WeaponConfig]) -> Vec<Self> {
+        let resolved: Vec<WeaponType> = names
+            .iter()
+            .filter_map(|name| WeaponType::from_name(name, weapons))
+            .collect();
 
-        WeaponStats {
-            damage: weapon_cfg.damage,
-            fire_rate: weapon_cfg.fire_rate,
-            projectile_speed: weapon_cfg.projectile_speed,
+        if resolved.is_empty() {
+            vec![WeaponType(0)]
+        } else {
+            resolved
         }
     }
 
-    pub fn _get_damage_value(&self, config: &crate::config::WeaponsConfig) -> f32 {
-        self.get_weapon_stats(config).damage
-    }
-
-    pub fn _get_fire_rate(&self, config: &crate::config::WeaponsConfig) -> f32 {
-        self.get_weapon_stats(config).fire_rate
+    pub fn config<'a>(&self, weapons: &'a [WeaponConfig]) -> &'a WeaponConfig {
+        &weapons[self.0 as usize]
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub enum EntityType {
-    BasicFighter,
-    Sniper,
-    Tank,
-    Elite,
-    Healer,
-    Splitter, // Splits into 2-3 smaller versions when killed
-}
+/// Handle into `config.archetypes` (enemy/ghost kinds are data, see `[[archetypes]]`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct EntityType(pub u16);
 
 impl EntityType {
+    /// Look up an archetype by name (as used in wave scripts and `[bindings.summon]`)
+    pub fn from_name(name: &str, archetypes: &[ArchetypeConfig]) -> Option<Self> {
+        archetypes
+            .iter()
+            .position(|a| a.name == name)
+            .map(|index| EntityType(index as u16))
+    }
+
+    /// Every archetype, in config order
+    pub fn all(archetypes: &[ArchetypeConfig]) -> impl Iterator<Item = EntityType> + use<> {
+        (0..archetypes.len() as u16).map(EntityType)
+    }
+
+    pub fn archetype<'a>(&self, archetypes: &'a [ArchetypeConfig]) -> &'a ArchetypeConfig {
+        &archetypes[self.0 as usize]
+    }
+
+    pub fn name<'a>(&self, archetypes: &'a [ArchetypeConfig]) -> &'a str {
+        &self.archetype(archetypes).name
+    }
+
     /// Get stats from config
-    pub fn get_stats(&self, config: &crate::config::EntitiesConfig) -> Stats {
-        match self {
-            EntityType::BasicFighter => Stats {
-                health: config.basic_fighter.health,
-                max_health: config.basic_fighter.health,
-                damage: config.basic_fighter.damage,
-            },
-            EntityType::Sniper => Stats {
-                health: config.sniper.health,
-                max_health: config.sniper.health,
-                damage: config.sniper.damage,
-            },
-            EntityType::Tank => Stats {
-                health: config.tank.health,
-                max_health: config.tank.health,
-                damage: config.tank.damage,
-            },
-            EntityType::Elite => Stats {
-                health: config.elite.health,
-                max_health: config.elite.health,
-                damage: config.elite.damage,
-            },
-            EntityType::Healer => Stats {
-                health: config.healer.health,
-                max_health: config.healer.health,
-                damage: config.healer.damage,
-            },
-            EntityType::Splitter => Stats {
-                health: config.splitter.health,
-                max_health: config.splitter.health,
-                damage: config.splitter.damage,
-            },
+    pub fn get_stats(&self, archetypes: &[ArchetypeConfig]) -> Stats {
+        let archetype = self.archetype(archetypes);
+        Stats {
+            health: archetype.health,
+            max_health: archetype.health,
+            damage: archetype.damage,
+            shield: archetype.shield.map(|shield| Shield {
+                current: shield.capacity,
+                max: shield.capacity,
+                recharge_timer: 0.0,
+            }),
         }
     }
 
-    pub fn get_energy_cost(&self, config: &crate::config::EntitiesConfig) -> f32 {
-        match self {
-            EntityType::BasicFighter => config.basic_fighter.energy_cost,
-            EntityType::Sniper => config.sniper.energy_cost,
-            EntityType::Tank => config.tank.energy_cost,
-            EntityType::Elite => config.elite.energy_cost,
-            EntityType::Healer => config.healer.energy_cost,
-            EntityType::Splitter => config.splitter.energy_cost,
-        }
+    /// Get weapons from config (falls back to the first weapon if none are valid)
+    pub fn get_weapons(&self, config: &GameConfig) -> Vec<WeaponType> {
+        WeaponType::from_names(&self.archetype(&config.archetypes).weapons, &config.weapons)
     }
 
-    pub fn get_fire_interval(&self, config: &crate::config::EntitiesConfig) -> f32 {
-        match self {
-            EntityType::BasicFighter => config.basic_fighter.fire_interval,
-            EntityType::Sniper => config.sniper.fire_interval,
-            EntityType::Tank => config.tank.fire_interval,
-            EntityType::Elite => config.elite.fire_interval,
-            EntityType::Healer => config.healer.fire_interval,
-            EntityType::Splitter => config.splitter.fire_interval,
-        }
+    pub fn get_energy_cost(&self, archetypes: &[ArchetypeConfig]) -> f32 {
+        self.archetype(archetypes).energy_cost
     }
 }
 
 // Player
 pub struct Player {
+    pub id: EntityId, // Always EntityId::PLAYER
     pub pos: Position,
+    pub prev_pos: Position, // Position at start of tick (render interpolation)
     pub last_pos: Position, // Previous frame position (for velocity calculation)
     pub velocity: Position, // Current movement velocity (for lead targeting)
     pub input_direction: Position, // Input direction from WASD (normalized, for physics)
@@ -182,6 +203,7 @@ pub struct Player {
     pub energy: f32,
     pub max_energy: f32,
     pub weapon: Vec<WeaponType>,
+    pub slots: Vec<WeaponSlot>, // Cooldown/heat/ammo per weapon (same order as `weapon`)
     pub available_ghosts: Vec<EntityType>, // Queue of defeated enemies (can summon as ghosts)
 
     // Parry system
@@ -204,6 +226,33 @@ pub struct Player {
     pub dash_cooldown_timer: f32, // Time until dash available again
     pub i_frame_timer: f32,       // Invincibility frames during dash
     pub dash_trail_timer: f32,    // Timer for spawning trail particles
+
+    // Weapon upgrades
+    pub downgrade_grace_timer: f32, // Hits don't cost levels while this runs
+
+    pub status: crate::game::StatusEffects, // Burn, slow, stun, energy drain
+}
+
+/// State of one player weapon slot (each slot cools down on its own)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WeaponSlot {
+    pub cooldown: f32,    // Time until the slot can fire again
+    pub heat: f32,        // 0.0..=1.0 (weapons with [weapons.heat])
+    pub overheated: bool, // Locked until heat drops to recover_at
+    pub ammo: u32,        // Rounds left (weapons with [weapons.ammo])
+    pub regen_timer: f32, // Progress towards regaining one round
+    pub level: usize,     // Upgrade level (1..=weapon max_level)
+}
+
+impl WeaponSlot {
+    /// Fresh slot: no cooldown, cold, full ammo
+    pub fn new(weapon: &WeaponConfig) -> Self {
+        WeaponSlot {
+            ammo: weapon.ammo.map_or(0, |ammo| ammo.max),
+            level: 1,
+            ..Default::default()
+        }
+    }
 }
 
 // Bezier path for enemy entry curves
@@ -231,13 +280,43 @@ pub enum EnemyMovementState {
 // Enemy
 #[derive(Clone)] // Needed for splitter system
 pub struct Enemy {
+    pub id: EntityId,
     pub pos: Position,
+    pub prev_pos: Position, // Position at start of tick (render interpolation)
     pub stats: Stats,
     pub weapon: Vec<WeaponType>,
     pub entity_type: EntityType,
     pub anim: EntityAnimState, // Animation state (hit flash, spawn, despawn)
     pub movement_state: EnemyMovementState, // Path-following or free movement
     pub fire_timer: f32,       // Time until next shot (embedded, no sync issues!)
+    pub last_hit_by: Option<ProjectileOwner>, // Credited with the kill
+    pub status: crate::game::StatusEffects, // Burn, slow, stun
+    pub ai: crate::game::AiMemory, // Behavior tree timers and active nodes
+}
+
+impl Enemy {
+    /// Create enemy from EntityType (free movement, ready to fire)
+    pub fn from_entity_type(
+        id: EntityId,
+        entity_type: EntityType,
+        pos: Position,
+        config: &crate::config::GameConfig,
+    ) -> Self {
+        Enemy {
+            id,
+            pos,
+            prev_pos: pos,
+            stats: entity_type.get_stats(&config.archetypes),
+            weapon: entity_type.get_weapons(config),
+            entity_type,
+            anim: EntityAnimState::default(),
+            movement_state: EnemyMovementState::FreeMovement,
+            fire_timer: 0.0,
+            last_hit_by: None,
+            status: Default::default(),
+            ai: Default::default(),
+        }
+    }
 }
 
 // Animation state for entities (ghosts, enemies, etc.)
@@ -298,17 +377,20 @@ impl EntityAnimState {
 // Ghost
 #[derive(Clone)]
 pub struct Ghost {
+    pub id: EntityId,
     pub pos: Position,
+    pub prev_pos: Position, // Position at start of tick (render interpolation)
     pub stats: Stats,
     pub weapon_type: Vec<WeaponType>,
     pub entity_type: EntityType,
     pub energy_drain_per_sec: f32,
-    pub anim: EntityAnimState, // Animation state
-    pub fire_timer: f32,       // Time until next shot (embedded, no sync issues!)
+    pub anim: EntityAnimState,              // Animation state
+    pub fire_timer: f32,                    // Time until next shot (embedded, no sync issues!)
+    pub status: crate::game::StatusEffects, // Burn, slow, stun
 }
 
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum GhostFormation {
     VShape, // Classic V formation (good for spread coverage)
     Line,   // Horizontal line (maximum firepower forward)
@@ -343,80 +425,22 @@ impl GhostFormation {
 impl Ghost {
     /// Create ghost directly from EntityType
     pub fn from_entity_type(
+        id: EntityId,
         entity_type: EntityType,
         spawn_pos: Position,
         config: &crate::config::GameConfig,
     ) -> Self {
-        // Get entity config (handle healer's special struct)
-        let (weapons_vec, base_stats) = match entity_type {
-            EntityType::BasicFighter => (
-                &config.entities.basic_fighter.weapons,
-                Stats {
-                    health: config.entities.basic_fighter.health,
-                    max_health: config.entities.basic_fighter.health,
-                    damage: config.entities.basic_fighter.damage,
-                },
-            ),
-            EntityType::Sniper => (
-                &config.entities.sniper.weapons,
-                Stats {
-                    health: config.entities.sniper.health,
-                    max_health: config.entities.sniper.health,
-                    damage: config.entities.sniper.damage,
-                },
-            ),
-            EntityType::Tank => (
-                &config.entities.tank.weapons,
-                Stats {
-                    health: config.entities.tank.health,
-                    max_health: config.entities.tank.health,
-                    damage: config.entities.tank.damage,
-                },
-            ),
-            EntityType::Elite => (
-                &config.entities.elite.weapons,
-                Stats {
-                    health: config.entities.elite.health,
-                    max_health: config.entities.elite.health,
-                    damage: config.entities.elite.damage,
-                },
-            ),
-            EntityType::Healer => (
-                &config.entities.healer.weapons,
-                Stats {
-                    health: config.entities.healer.health,
-                    max_health: config.entities.healer.health,
-                    damage: config.entities.healer.damage,
-                },
-            ),
-            EntityType::Splitter => (
-                &config.entities.splitter.weapons,
-                Stats {
-                    health: config.entities.splitter.health,
-                    max_health: config.entities.splitter.health,
-                    damage: config.entities.splitter.damage,
-                },
-            ),
-        };
-
-        // Parse weapons from config (inherit from entity type!)
-        let weapons: Vec<WeaponType> = weapons_vec
-            .iter()
-            .filter_map(|w| WeaponType::from_string(w))
-            .collect();
-
         Ghost {
+            id,
             pos: spawn_pos,
-            stats: base_stats,
-            weapon_type: if weapons.is_empty() {
-                vec![WeaponType::Bullet] // Fallback only if config invalid
-            } else {
-                weapons // Uses entity's configured weapons!
-            },
+            prev_pos: spawn_pos,
+            stats: entity_type.get_stats(&config.archetypes),
+            weapon_type: entity_type.get_weapons(config), // Inherits the archetype's weapons
             entity_type,
-            energy_drain_per_sec: entity_type.get_energy_cost(&config.entities) * 0.1,
+            energy_drain_per_sec: entity_type.get_energy_cost(&config.archetypes) * 0.1,
             anim: EntityAnimState::new_spawning(0.5), // 0.5s spawn animation
             fire_timer: 0.0,                          // Ready to fire immediately
+            status: Default::default(),
         }
     }
 }
@@ -455,26 +479,20 @@ impl WaveDefinition {
     /// Convert from Lua wave definition
     pub fn from_lua(
         lua_wave: crate::scripting::LuaWaveDefinition,
-        _config: &crate::config::GameConfig, // Reserved for future difficulty scaling
+        config: &crate::config::GameConfig,
     ) -> Option<Self> {
         let mut spawns = Vec::new();
 
         for lua_spawn in lua_wave.spawns {
-            // Parse entity type from string
-            let entity_type = match lua_spawn.enemy_type.as_str() {
-                "BasicFighter" => EntityType::BasicFighter,
-                "Sniper" => EntityType::Sniper,
-                "Tank" => EntityType::Tank,
-                "Elite" => EntityType::Elite,
-                "Healer" => EntityType::Healer,
-                "Splitter" => EntityType::Splitter,
-                unknown => {
-                    println!(
-                        "✗ Unknown enemy type in wave {}: {}",
-                        lua_wave.wave_number, unknown
-                    );
-                    continue; // Skip invalid enemy types
-                }
+            // Resolve archetype by name
+            let Some(entity_type) =
+                EntityType::from_name(&lua_spawn.enemy_type, &config.archetypes)
+            else {
+                println!(
+                    "✗ Unknown enemy type in wave {}: {}",
+                    lua_wave.wave_number, lua_spawn.enemy_type
+                );
+                continue; // Skip invalid enemy types
             };
 
             spawns.push(WaveSpawn {
@@ -487,7 +505,7 @@ impl WaveDefinition {
             });
         }
 
-        if spawns.is_empty() {
+        if spawns.is_empty() && lua_wave.run.is_none() {
             println!("✗ Wave {} has no valid spawns!", lua_wave.wave_number);
             return None;
         }
@@ -514,8 +532,8 @@ pub struct GameState {
     pub enemies: Vec<Enemy>,
     pub ghosts: Vec<Ghost>,
     pub projectiles: Vec<Projectile>,
+    pub pending_shots: Vec<crate::game::PendingShot>, // Remaining shots of bursts
     pub particles: Vec<Particle>,
-    pub player_fire_timer: f32,
     // NOTE: enemy_fire_timers and ghost_fire_timers removed!
     // Fire timers are now embedded directly in Enemy and Ghost structs
     pub spawn_timer: f32,
@@ -524,39 +542,91 @@ pub struct GameState {
     // Screen shake
     pub screen_shake_duration: f32,
     pub screen_shake_intensity: f32,
+    pub screen_shake_offset: (f32, f32), // Picked per tick from fx_rng
 
     // Background scroll
     pub bg_scroll_offset: f32,
 
+    // Playfield size (window size when rendering, fixed when headless)
+    pub arena: Vec2,
+
+    // Run statistics
+    pub enemies_killed: usize,
+    pub time: f32,     // Simulation seconds since the run started
+    pub grazes: usize, // Enemy projectiles dodged by a hair
+
+    // Entity handles + broadphase (rebuilt each tick after movement)
+    pub entity_ids: EntityIds,
+    pub spatial: crate::game::spatial::SpatialGrid,
+
+    // Gameplay events from the current (or last finished) tick
+    pub events: Vec<crate::game::events::GameEvent>,
+
+    // Bomb shockwave rings (cosmetic)
+    pub shockwaves: Vec<crate::game::Shockwave>,
+
+    // Active beam lasers (warming up or firing)
+    pub beams: Vec<crate::game::Beam>,
+
+    // Weapon upgrade pickups dropped by enemies
+    pub pickups: Vec<crate::game::Pickup>,
+
+    // Randomness (same seed + same inputs = same run)
+    pub seed: u64,
+    pub rng: GameRng,    // Gameplay stream (spawns, paths, weapon picks)
+    pub fx_rng: GameRng, // Cosmetic stream (particles, shake)
+
     // Wave system
     pub wave_manager: crate::game::wave::WaveManager,
 }
 
 impl GameState {
+    /// Create game state sized to the current window
     pub fn new() -> Self {
-        use macroquad::prelude::*;
+        use macroquad::prelude::{screen_height, screen_width};
+
+        Self::with_arena(screen_width(), screen_height())
+    }
 
+    /// Create game state with a fixed arena size (no window required)
+    pub fn with_arena(arena_width: f32, arena_height: f32) -> Self {
         // ALWAYS start with compiled defaults - never try to load config.toml at startup
-        let config = crate::defaults::default_config();
+        Self::with_config(crate::defaults::default_config(), arena_width, arena_height)
+    }
+
+    /// Create game state from an explicit config (replays restore their snapshot this way)
+    pub fn with_config(config: GameConfig, arena_width: f32, arena_height: f32) -> Self {
+        // Seed 0 in config means "pick one" - logged so the run can be reproduced
+        let seed = match config.simulation.seed {
+            0 => GameRng::seed_from_time(),
+            seed => seed,
+        };
+        println!("✓ RNG seed: {seed}");
+
+        let starting_weapons =
+            WeaponType::from_names(&config.player.starting_weapons, &config.weapons);
+        let slots = starting_weapons
+            .iter()
+            .map(|weapon| WeaponSlot::new(weapon.config(&config.weapons)))
+            .collect();
 
         GameState {
             config: config.clone(),
             player: Player {
-                pos: Vec2::new(screen_width() / 2.0, screen_height() - 50.0),
-                last_pos: Vec2::new(screen_width() / 2.0, screen_height() - 50.0),
+                id: EntityId::PLAYER,
+                pos: Vec2::new(arena_width / 2.0, arena_height - 50.0),
+                prev_pos: Vec2::new(arena_width / 2.0, arena_height - 50.0),
+                last_pos: Vec2::new(arena_width / 2.0, arena_height - 50.0),
                 velocity: Vec2::ZERO,
                 input_direction: Vec2::ZERO, // No input at start
                 stats: Stats {
                     health: config.player.starting_health,
                     max_health: config.player.max_health,
                     damage: 20.0,
+                    shield: None,
                 },
-                weapon: vec![
-                    WeaponType::Bullet,
-                    WeaponType::Laser,
-                    WeaponType::Missile,
-                    WeaponType::Plasma,
-                ],
+                weapon: starting_weapons,
+                slots,
                 energy: config.player.starting_energy,
                 max_energy: config.player.max_energy,
                 available_ghosts: Vec::new(),
@@ -581,40 +651,117 @@ impl GameState {
                 dash_cooldown_timer: 0.0,
                 i_frame_timer: 0.0,
                 dash_trail_timer: 0.0,
+
+                downgrade_grace_timer: 0.0,
+                status: Default::default(),
             },
             enemies: Vec::new(),
             ghosts: Vec::new(),
             projectiles: Vec::new(),
+            pending_shots: Vec::new(),
             particles: Vec::new(),
-            player_fire_timer: 0.0,
             spawn_timer: 0.0,
             ghost_formation: GhostFormation::Line,
 
             // Screen shake
             screen_shake_duration: 0.0,
             screen_shake_intensity: 0.0,
+            screen_shake_offset: (0.0, 0.0),
 
             // Starts at bottom of texture
             bg_scroll_offset: 0.0,
 
+            arena: Vec2::new(arena_width, arena_height),
+            enemies_killed: 0,
+            time: 0.0,
+            grazes: 0,
+
+            entity_ids: EntityIds::default(),
+            spatial: crate::game::spatial::SpatialGrid::default(),
+            events: Vec::new(),
+            shockwaves: Vec::new(),
+            beams: Vec::new(),
+            pickups: Vec::new(),
+
+            seed,
+            rng: GameRng::new_stream(seed, 0),
+            fx_rng: GameRng::new_stream(seed, 1),
+
             // Wave system - initialized based on config
             wave_manager: {
-                let cfg = crate::defaults::default_config();
-                if cfg.spawning.wave_mode {
+                if config.spawning.wave_mode {
                     println!(
                         "✓ Initializing Lua wave system ({} waves)",
-                        cfg.spawning.wave_count
+                        config.spawning.wave_count
                     );
                 } else {
                     println!("✓ Wave system ready (classic random mode)");
                 }
-                crate::game::wave::WaveManager::new(cfg.spawning.wave_count)
+                crate::game::wave::WaveManager::new(config.spawning.wave_count)
             },
         }
     }
+
+    /// Position of a live entity as of the last spatial rebuild (None once dead or removed)
+    pub fn entity_pos(&self, id: EntityId) -> Option<Position> {
+        use crate::game::spatial::EntityKind;
+
+        let entry = self.spatial.find(id)?;
+        let stats = match entry.kind {
+            EntityKind::Player => &self.player.stats,
+            EntityKind::Enemy => &self.enemies.get(entry.index).filter(|e| e.id == id)?.stats,
+            EntityKind::Ghost => &self.ghosts.get(entry.index).filter(|g| g.id == id)?.stats,
+        };
+        (stats.health > 0.0).then_some(entry.pos) // Killed this tick, not cleaned up yet
+    }
+
+    /// Restart both random streams from a seed (e.g. `--seed` on the command line)
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = GameRng::new_stream(seed, 0);
+        self.fx_rng = GameRng::new_stream(seed, 1);
+        println!("✓ RNG seed overridden: {seed}");
+    }
+
     pub fn apply_config(&mut self, config: &GameConfig) {
+        // Live entities hold archetype indices - only accept the new list if it lines up
+        let same_archetypes = self.config.archetypes.len() == config.archetypes.len()
+            && self
+                .config
+                .archetypes
+                .iter()
+                .zip(&config.archetypes)
+                .all(|(old, new)| old.name == new.name);
+
+        // Same for weapon indices (projectiles, entity arsenals)
+        let same_weapons = self.config.weapons.len() == config.weapons.len()
+            && self
+                .config
+                .weapons
+                .iter()
+                .zip(&config.weapons)
+                .all(|(old, new)| old.name == new.name);
+
+        let old_seed = self.config.simulation.seed;
+        let old_archetypes = std::mem::take(&mut self.config.archetypes);
+        let old_weapons = std::mem::take(&mut self.config.weapons);
         self.config = config.clone();
+        if !same_archetypes {
+            println!("✗ Archetypes were added, removed or reordered - restart to apply them");
+            self.config.archetypes = old_archetypes;
+        }
+        if !same_weapons {
+            println!("✗ Weapons were added, removed or reordered - restart to apply them");
+            self.config.weapons = old_weapons;
+        }
+
         self.player.stats.max_health = config.player.max_health;
         self.player.max_energy = config.player.max_energy;
+
+        // A new non-zero seed restarts the random streams (0 keeps the current ones)
+        let seed = config.simulation.seed;
+        if seed != 0 && seed != old_seed {
+            self.reseed(seed);
+        }
     }
 }
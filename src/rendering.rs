@@ -1,10 +1,19 @@
+use crate::config::{ArchetypeConfig, Shape, StatusConfig, StatusKind, WeaponConfig};
 use crate::game::get_shake_offset;
+use crate::game::{GHOST_SHIELD_FACING, StatusEffects, enemy_shield_facing};
 use crate::models::*;
 use macroquad::prelude::*;
 use std::collections::HashMap;
 
 /// Render all game entities with scrolling background
-pub fn render_game(state: &GameState, space_texture: &Option<Texture2D>) {
+/// `alpha` blends entity positions between the last two simulation ticks (0.0 - 1.0)
+/// `show_ai` draws the active behavior tree nodes above enemies (F10, not part of the config)
+pub fn render_game(
+    state: &GameState,
+    space_texture: &Option<Texture2D>,
+    alpha: f32,
+    show_ai: bool,
+) {
     // Draw scrolling background BEFORE camera setup
     draw_scrolling_background(space_texture, state.bg_scroll_offset);
 
@@ -23,10 +32,27 @@ pub fn render_game(state: &GameState, space_texture: &Option<Texture2D>) {
     });
 
     // Render entities
-    draw_player(&state.player, state);
-    draw_enemies(&state.enemies);
-    draw_ghosts(&state.ghosts);
-    draw_projectiles(&state.projectiles);
+    draw_pickups(&state.pickups, alpha);
+    draw_player(&state.player, state, alpha);
+    draw_enemies(
+        &state.enemies,
+        &state.config.archetypes,
+        &state.config.status,
+        alpha,
+    );
+    draw_ghosts(
+        &state.ghosts,
+        &state.config.archetypes,
+        &state.config.status,
+        alpha,
+    );
+    draw_shields(state, alpha);
+    if show_ai {
+        draw_ai_debug(&state.enemies, alpha);
+    }
+    draw_projectiles(&state.projectiles, &state.config.weapons, alpha);
+    draw_beams(&state.beams, &state.config.weapons, alpha);
+    draw_shockwaves(&state.shockwaves);
     draw_particles(&state.particles);
 
     // Reset camera for UI
@@ -74,7 +100,7 @@ fn draw_scrolling_background(space_texture: &Option<Texture2D>, scroll_offset: f
 /// Render UI overlay
 pub fn render_ui(state: &GameState) {
     // Player Stats (Top Left)
-    draw_panel(10.0, 10.0, 210.0, 150.0);
+    draw_panel(10.0, 10.0, 210.0, 162.0); // Down to y=172, below the Grazes row
 
     let mut ui_y = 20.0;
 
@@ -135,7 +161,8 @@ pub fn render_ui(state: &GameState) {
     // Calculate total energy cost for full formation
     let mut formation_cost = 0.0;
     for i in 0..available_count.min(optimal) {
-        formation_cost += state.player.available_ghosts[i].get_energy_cost(&state.config.entities);
+        formation_cost +=
+            state.player.available_ghosts[i].get_energy_cost(&state.config.archetypes);
     }
 
     // Determine color based on formation readiness AND energy
@@ -185,10 +212,25 @@ pub fn render_ui(state: &GameState) {
     };
 
     draw_text(parry_text, 20.0, ui_y, 16.0, parry_color);
+    ui_y += 20.0;
+
+    // Near misses this run
+    draw_text(
+        &format!("Grazes: {}", state.grazes),
+        20.0,
+        ui_y,
+        16.0,
+        Color::new(0.7, 0.9, 1.0, 1.0),
+    );
+
+    // Weapon slots (Bottom Right)
+    draw_weapon_slots(state);
 
-    // Available Ghosts (Bottom Left)
-    let ghost_panel_y = screen_height() - 180.0;
-    draw_panel(10.0, ghost_panel_y, 170.0, 170.0);
+    // Available Ghosts (Bottom Left) - one line per archetype
+    let archetypes = &state.config.archetypes;
+    let panel_h = 40.0 + 22.0 * archetypes.len() as f32;
+    let ghost_panel_y = screen_height() - panel_h - 10.0;
+    draw_panel(10.0, ghost_panel_y, 170.0, panel_h);
 
     draw_text("Available Ghosts:", 20.0, ghost_panel_y + 20.0, 18.0, WHITE);
 
@@ -201,40 +243,113 @@ pub fn render_ui(state: &GameState) {
 
     let mut line_y = ghost_panel_y + 45.0;
 
-    // BasicFighter
-    let count = ghost_counts.get(&EntityType::BasicFighter);
-    draw_circle(25.0, line_y - 5.0, 6.0, RED);
-    draw_text(&format!("Fighter: {count:?}"), 40.0, line_y, 16.0, WHITE);
-    line_y += 22.0;
-
-    // Sniper
-    let count = ghost_counts.get(&EntityType::Sniper);
-    draw_circle(25.0, line_y - 5.0, 6.0, BLUE);
-    draw_text(&format!("Sniper: {count:?}"), 40.0, line_y, 16.0, WHITE);
-    line_y += 22.0;
-
-    // Tank
-    let count = ghost_counts.get(&EntityType::Tank);
-    draw_circle(25.0, line_y - 5.0, 6.0, GREEN);
-    draw_text(&format!("Tank: {count:?}"), 40.0, line_y, 16.0, WHITE);
-    line_y += 22.0;
-
-    // Healer
-    let count = ghost_counts.get(&EntityType::Healer);
-    draw_circle(25.0, line_y - 5.0, 6.0, LIME);
-    draw_text(&format!("Healer: {count:?}"), 40.0, line_y, 16.0, WHITE);
-    line_y += 22.0;
-
-    // Splitter
-    let count = ghost_counts.get(&EntityType::Splitter);
-    draw_circle(25.0, line_y - 5.0, 6.0, ORANGE);
-    draw_text(&format!("Splitter: {count:?}"), 40.0, line_y, 16.0, WHITE);
-    line_y += 22.0;
-
-    // Elite
-    let count = ghost_counts.get(&EntityType::Elite);
-    draw_circle(25.0, line_y - 5.0, 6.0, GOLD);
-    draw_text(&format!("Elite: {count:?}"), 40.0, line_y, 16.0, WHITE);
+    for entity_type in EntityType::all(archetypes) {
+        let count = ghost_counts.get(&entity_type).copied().unwrap_or(0);
+        draw_circle(
+            25.0,
+            line_y - 5.0,
+            6.0,
+            archetype_color(entity_type, archetypes),
+        );
+        draw_text(
+            &format!("{}: {count}", entity_type.name(archetypes)),
+            40.0,
+            line_y,
+            16.0,
+            WHITE,
+        );
+        line_y += 22.0;
+    }
+}
+
+/// One row per weapon slot: key, name and readiness (heat, ammo or cooldown)
+fn draw_weapon_slots(state: &GameState) {
+    let player = &state.player;
+    let row_h = 22.0;
+    let panel_w = 250.0;
+    let panel_h = 35.0 + row_h * player.weapon.len() as f32;
+    let x = screen_width() - panel_w - 10.0;
+    let y = screen_height() - panel_h - 10.0;
+    draw_panel(x, y, panel_w, panel_h);
+
+    draw_text("Weapons:", x + 10.0, y + 20.0, 18.0, WHITE);
+
+    let mut line_y = y + 30.0;
+    for (index, (weapon, slot)) in player.weapon.iter().zip(&player.slots).enumerate() {
+        let config = weapon.config(&state.config.weapons);
+        let [r, g, b] = config.color;
+        let weapon_color = Color::new(r, g, b, 1.0);
+        let key = state
+            .config
+            .bindings
+            .fire_slots
+            .get(index)
+            .and_then(|keys| keys.first())
+            .map_or("-", String::as_str);
+
+        let (ratio, bar_color, label) = if let Some(heat) = config.heat {
+            if slot.overheated {
+                (slot.heat, RED, "OVERHEAT".to_string())
+            } else {
+                let color = if slot.heat + heat.per_shot >= 1.0 {
+                    ORANGE // Next shot overheats
+                } else {
+                    weapon_color
+                };
+                (slot.heat, color, format!("Heat {:.0}%", slot.heat * 100.0))
+            }
+        } else if let Some(ammo) = config.ammo {
+            let color = if slot.ammo == 0 { RED } else { weapon_color };
+            let ratio = slot.ammo as f32 / ammo.max.max(1) as f32;
+            (ratio, color, format!("{}/{}", slot.ammo, ammo.max))
+        } else {
+            let ratio = 1.0 - slot.cooldown / config.fire_rate.max(f32::EPSILON);
+            (ratio, weapon_color, String::new())
+        };
+
+        let ready = crate::game::slot_ready(slot, config);
+        let name_color = if ready { WHITE } else { GRAY };
+        let name = if config.max_level() > 1 {
+            format!("{key}  {} Lv{}", config.name, slot.level)
+        } else {
+            format!("{key}  {}", config.name)
+        };
+        draw_text(&name, x + 10.0, line_y + 13.0, 16.0, name_color);
+        draw_stat_bar(x + 145.0, line_y, 95.0, 16.0, ratio, bar_color, &label);
+
+        line_y += row_h;
+    }
+}
+
+/// Replay playback status (Top Right)
+pub fn render_replay_overlay(player: &crate::replay::ReplayPlayer, paused: bool, speed: f32) {
+    let x = screen_width() - 230.0;
+    draw_panel(x, 10.0, 220.0, 80.0);
+
+    let status = if player.is_finished() {
+        "REPLAY END".to_string()
+    } else if paused {
+        "REPLAY PAUSED".to_string()
+    } else {
+        format!("REPLAY {speed:.0}x")
+    };
+    draw_text(&status, x + 10.0, 30.0, 18.0, YELLOW);
+
+    draw_text(
+        &format!("Tick {}/{}", player.tick(), player.total_ticks()),
+        x + 10.0,
+        50.0,
+        16.0,
+        WHITE,
+    );
+
+    let (sync_text, sync_color) = match player.divergence {
+        Some(d) => (format!("Diverged at tick {}", d.tick), RED),
+        None => ("In sync".to_string(), GREEN),
+    };
+    draw_text(&sync_text, x + 10.0, 70.0, 16.0, sync_color);
+
+    draw_text("P pause  N step  1/2/4 speed", x + 10.0, 85.0, 14.0, GRAY);
 }
 
 /// Draw modern panel with shadow
@@ -260,8 +375,9 @@ fn draw_stat_bar(x: f32, y: f32, w: f32, h: f32, ratio: f32, color: Color, label
 }
 
 /// Draw player entity with enhanced visuals
-fn draw_player(player: &Player, state: &GameState) {
+fn draw_player(player: &Player, state: &GameState, alpha: f32) {
     let anim_cfg = &state.config.animations;
+    let pos = player.prev_pos.lerp(player.pos, alpha); // Interpolated render position
 
     // Calculate parry animation states
     let mut player_scale = 1.0;
@@ -310,6 +426,10 @@ fn draw_player(player: &Player, state: &GameState) {
         1.0,
     );
 
+    // Status effects tint the body (burn, slow, stun, drain)
+    let base_white = status_tint(base_white, &player.status, &state.config.status);
+    let base_skyblue = status_tint(base_skyblue, &player.status, &state.config.status);
+
     // Apply hit flash (lerp toward white)
     let white_color = Color::new(
         base_white.r + (1.0 - base_white.r) * hit_flash_intensity,
@@ -348,8 +468,8 @@ fn draw_player(player: &Player, state: &GameState) {
 
         // Pulsing blue aura (with burst effect!)
         draw_circle(
-            player.pos.x,
-            player.pos.y,
+            pos.x,
+            pos.y,
             glow_radius + 15.0,
             Color::new(0.0, 0.5, 1.0, glow_intensity * 0.4),
         );
@@ -358,15 +478,15 @@ fn draw_player(player: &Player, state: &GameState) {
         let ring_radius = state.config.collision.player_radius + 25.0;
         let ring_pulse = (time * 10.0).sin() as f32 * 3.0;
         draw_circle_lines(
-            player.pos.x,
-            player.pos.y,
+            pos.x,
+            pos.y,
             ring_radius + ring_pulse,
             4.0,
             Color::new(0.0, 0.5, 1.0, glow_intensity),
         );
         draw_circle_lines(
-            player.pos.x,
-            player.pos.y,
+            pos.x,
+            pos.y,
             ring_radius + ring_pulse - 5.0,
             2.0,
             Color::new(0.53, 0.81, 0.92, glow_intensity * 0.8),
@@ -377,14 +497,14 @@ fn draw_player(player: &Player, state: &GameState) {
     if player.is_dashing {
         let dash_glow_intensity = state.config.dash.glow_intensity;
         draw_circle(
-            player.pos.x,
-            player.pos.y,
+            pos.x,
+            pos.y,
             glow_radius + 15.0,
             Color::new(0.0, 0.5, 1.0, dash_glow_intensity * 0.5), // Cyan glow
         );
         draw_circle(
-            player.pos.x,
-            player.pos.y,
+            pos.x,
+            pos.y,
             glow_radius + 10.0,
             Color::new(0.53, 0.81, 0.92, dash_glow_intensity), // Bright core
         );
@@ -404,28 +524,22 @@ fn draw_player(player: &Player, state: &GameState) {
     };
 
     draw_circle(
-        player.pos.x,
-        player.pos.y,
+        pos.x,
+        pos.y,
         glow_radius + (hit_flash_intensity * 10.0), // Expand when hit
         glow_color,
     );
 
     // Main player body (with scale and color)
-    draw_circle(player.pos.x, player.pos.y, body_radius, white_color);
+    draw_circle(pos.x, pos.y, body_radius, white_color);
 
     // Inner core (with scale and color)
-    draw_circle(player.pos.x, player.pos.y, core_radius, skyblue_color);
+    draw_circle(pos.x, pos.y, core_radius, skyblue_color);
 
     // Health bar above player
     let health_ratio = player.stats.health / player.stats.max_health;
-    draw_rectangle(player.pos.x - 20.0, player.pos.y - 27.0, 40.0, 4.0, BLACK);
-    draw_rectangle(
-        player.pos.x - 20.0,
-        player.pos.y - 30.0,
-        40.0 * health_ratio,
-        4.0,
-        GREEN,
-    );
+    draw_rectangle(pos.x - 20.0, pos.y - 27.0, 40.0, 4.0, BLACK);
+    draw_rectangle(pos.x - 20.0, pos.y - 30.0, 40.0 * health_ratio, 4.0, GREEN);
 
     // Dash cooldown indicator (circular ring around player)
     if state.config.dash.enabled && player.dash_cooldown_timer > 0.0 {
@@ -441,10 +555,10 @@ fn draw_player(player: &Player, state: &GameState) {
             let angle1 = i as f32 * angle_per_segment - std::f32::consts::PI / 2.0; // Start at top
             let angle2 = (i + 1) as f32 * angle_per_segment - std::f32::consts::PI / 2.0;
 
-            let x1 = player.pos.x + angle1.cos() * ring_radius;
-            let y1 = player.pos.y + angle1.sin() * ring_radius;
-            let x2 = player.pos.x + angle2.cos() * ring_radius;
-            let y2 = player.pos.y + angle2.sin() * ring_radius;
+            let x1 = pos.x + angle1.cos() * ring_radius;
+            let y1 = pos.y + angle1.sin() * ring_radius;
+            let x2 = pos.x + angle2.cos() * ring_radius;
+            let y2 = pos.y + angle2.sin() * ring_radius;
 
             draw_line(
                 x1,
@@ -459,21 +573,30 @@ fn draw_player(player: &Player, state: &GameState) {
 }
 
 /// Draw all enemies with enhanced visuals
-fn draw_enemies(enemies: &[Enemy]) {
+fn draw_enemies(
+    enemies: &[Enemy],
+    archetypes: &[ArchetypeConfig],
+    status_cfg: &StatusConfig,
+    alpha: f32,
+) {
     for enemy in enemies {
+        let pos = enemy.prev_pos.lerp(enemy.pos, alpha); // Interpolated render position
+
         // Apply animation state
         let anim = &enemy.anim;
 
+        let archetype = enemy.entity_type.archetype(archetypes);
+
         // Draw healing field for healers (pulsing green circle)
-        if enemy.entity_type == EntityType::Healer {
+        if let Some(healer) = &archetype.healer {
             // Pulse effect using sine wave
             let pulse = (macroquad::time::get_time() * 2.0).sin() as f32 * 0.1 + 0.9;
-            let heal_radius = 150.0 * pulse; // From config
+            let heal_radius = healer.heal_radius * pulse;
 
             // Draw healing radius (transparent green circle)
             draw_circle_lines(
-                enemy.pos.x,
-                enemy.pos.y,
+                pos.x,
+                pos.y,
                 heal_radius,
                 2.0,
                 Color::new(0.2, 1.0, 0.2, 0.3),
@@ -481,14 +604,15 @@ fn draw_enemies(enemies: &[Enemy]) {
 
             // Inner healing glow
             draw_circle(
-                enemy.pos.x,
-                enemy.pos.y,
+                pos.x,
+                pos.y,
                 heal_radius * 0.5,
                 Color::new(0.2, 1.0, 0.2, 0.05),
             );
         }
 
-        let base_color = get_enemy_color(enemy.entity_type);
+        let base_color = archetype_color(enemy.entity_type, archetypes);
+        let base_color = status_tint(base_color, &enemy.status, status_cfg);
 
         // Apply hit flash (lerp toward white when hit)
         let flash_intensity = anim.hit_flash_timer / 0.15; // Normalize (assumes 0.15s duration)
@@ -501,39 +625,105 @@ fn draw_enemies(enemies: &[Enemy]) {
 
         // Glow effect (with hit flash)
         draw_circle(
-            enemy.pos.x,
-            enemy.pos.y,
+            pos.x,
+            pos.y,
             20.0,
             Color::new(color.r, color.g, color.b, 0.2),
         );
 
         // Main body (with hit flash)
-        draw_circle(enemy.pos.x, enemy.pos.y, 15.0, color);
+        draw_shape(archetype.shape, pos.x, pos.y, 15.0, color);
 
         // Inner detail (darker, with hit flash)
-        draw_circle(
-            enemy.pos.x,
-            enemy.pos.y,
+        draw_shape(
+            archetype.shape,
+            pos.x,
+            pos.y,
             10.0,
             Color::new(color.r * 0.7, color.g * 0.7, color.b * 0.7, 1.0),
         );
 
         // Health bar
         let health_ratio = enemy.stats.health / enemy.stats.max_health;
-        draw_rectangle(enemy.pos.x - 15.0, enemy.pos.y - 22.0, 30.0, 3.0, BLACK);
-        draw_rectangle(
-            enemy.pos.x - 15.0,
-            enemy.pos.y - 22.0,
-            30.0 * health_ratio,
+        draw_rectangle(pos.x - 15.0, pos.y - 22.0, 30.0, 3.0, BLACK);
+        draw_rectangle(pos.x - 15.0, pos.y - 22.0, 30.0 * health_ratio, 3.0, RED);
+    }
+}
+
+/// Draw shield arcs over the front of shielded enemies and ghosts (fades as it drains)
+fn draw_shields(state: &GameState, alpha: f32) {
+    let player_pos = state.player.prev_pos.lerp(state.player.pos, alpha);
+    let enemies = state.enemies.iter().map(|enemy| {
+        let pos = enemy.prev_pos.lerp(enemy.pos, alpha);
+        let facing = enemy_shield_facing(pos, player_pos);
+        (enemy.entity_type, &enemy.stats, pos, facing, 1.0)
+    });
+    let ghosts = state.ghosts.iter().map(|ghost| {
+        let pos = ghost.prev_pos.lerp(ghost.pos, alpha);
+        (
+            ghost.entity_type,
+            &ghost.stats,
+            pos,
+            GHOST_SHIELD_FACING,
+            ghost.anim.alpha,
+        )
+    });
+
+    for (entity_type, stats, pos, facing, fade) in enemies.chain(ghosts) {
+        let (Some(shield), Some(cfg)) = (
+            stats.shield,
+            entity_type.archetype(&state.config.archetypes).shield,
+        ) else {
+            continue;
+        };
+        if shield.current <= 0.0 {
+            continue; // Broken
+        }
+
+        let ratio = shield.current / shield.max;
+        let rotation = facing.y.atan2(facing.x).to_degrees() - cfg.arc.min(360.0) / 2.0;
+        draw_arc(
+            pos.x,
+            pos.y,
+            32,
+            24.0,
+            rotation,
             3.0,
-            RED,
+            cfg.arc.min(360.0),
+            Color::new(0.4, 0.8, 1.0, (0.25 + 0.6 * ratio) * fade), // Shield blue
+        );
+    }
+}
+
+/// Debug overlay: behavior tree leaves each enemy ran this tick
+fn draw_ai_debug(enemies: &[Enemy], alpha: f32) {
+    for enemy in enemies {
+        if enemy.ai.active.is_empty() {
+            continue;
+        }
+        let pos = enemy.prev_pos.lerp(enemy.pos, alpha);
+        let label = enemy.ai.active.join(" + ");
+        let width = measure_text(&label, None, 14, 1.0).width;
+        draw_text(
+            &label,
+            pos.x - width / 2.0,
+            pos.y - 30.0,
+            14.0,
+            Color::new(1.0, 1.0, 0.6, 0.9),
         );
     }
 }
 
 /// Draw all ghosts with transparency
-fn draw_ghosts(ghosts: &[Ghost]) {
+fn draw_ghosts(
+    ghosts: &[Ghost],
+    archetypes: &[ArchetypeConfig],
+    status_cfg: &StatusConfig,
+    alpha: f32,
+) {
     for ghost in ghosts {
+        let pos = ghost.prev_pos.lerp(ghost.pos, alpha); // Interpolated render position
+
         // Apply animation state
         let anim = &ghost.anim;
         let base_radius = 12.0;
@@ -543,16 +733,18 @@ fn draw_ghosts(ghosts: &[Ghost]) {
         let radius = base_radius * anim.scale;
         let glow_rad = glow_radius * anim.scale;
 
+        let archetype = ghost.entity_type.archetype(archetypes);
+
         // Draw healing field for healer ghosts (pulsing green circle)
-        if ghost.entity_type == EntityType::Healer {
+        if let Some(healer) = &archetype.healer {
             // Pulse effect using sine wave
             let pulse = (macroquad::time::get_time() * 2.0).sin() as f32 * 0.1 + 0.9;
-            let heal_radius = 150.0 * pulse * anim.scale; // Scale healing field too
+            let heal_radius = healer.heal_radius * pulse * anim.scale; // Scale healing field too
 
             // Draw healing radius (transparent green circle)
             draw_circle_lines(
-                ghost.pos.x,
-                ghost.pos.y,
+                pos.x,
+                pos.y,
                 heal_radius,
                 2.0,
                 Color::new(0.2, 1.0, 0.2, 0.3 * anim.alpha), // Apply alpha
@@ -560,14 +752,15 @@ fn draw_ghosts(ghosts: &[Ghost]) {
 
             // Inner healing glow
             draw_circle(
-                ghost.pos.x,
-                ghost.pos.y,
+                pos.x,
+                pos.y,
                 heal_radius * 0.5,
                 Color::new(0.2, 1.0, 0.2, 0.05 * anim.alpha), // Apply alpha
             );
         }
 
-        let base_color = get_ghost_color(ghost.entity_type);
+        let base_color = archetype_color(ghost.entity_type, archetypes);
+        let base_color = status_tint(base_color, &ghost.status, status_cfg);
 
         // Apply hit flash (lerp toward white when hit)
         let flash_intensity = anim.hit_flash_timer / 0.15; // Normalize (assumes 0.15s duration)
@@ -580,16 +773,17 @@ fn draw_ghosts(ghosts: &[Ghost]) {
 
         // Ghost glow (with animation alpha and hit flash)
         draw_circle(
-            ghost.pos.x,
-            ghost.pos.y,
+            pos.x,
+            pos.y,
             glow_rad,
             Color::new(color.r, color.g, color.b, 0.3 * anim.alpha),
         );
 
         // Ghost body (with animation scale, alpha, and hit flash)
-        draw_circle(
-            ghost.pos.x,
-            ghost.pos.y,
+        draw_shape(
+            archetype.shape,
+            pos.x,
+            pos.y,
             radius,
             Color::new(color.r, color.g, color.b, 0.7 * anim.alpha),
         );
@@ -601,15 +795,15 @@ fn draw_ghosts(ghosts: &[Ghost]) {
             let bar_offset = radius + 7.0;
 
             draw_rectangle(
-                ghost.pos.x - bar_width / 2.0,
-                ghost.pos.y - bar_offset,
+                pos.x - bar_width / 2.0,
+                pos.y - bar_offset,
                 bar_width,
                 2.0,
                 Color::new(0.0, 0.0, 0.0, anim.alpha),
             );
             draw_rectangle(
-                ghost.pos.x - bar_width / 2.0,
-                ghost.pos.y - bar_offset,
+                pos.x - bar_width / 2.0,
+                pos.y - bar_offset,
                 bar_width * health_ratio,
                 2.0,
                 Color::new(0.53, 0.81, 0.92, anim.alpha), // SKYBLUE with alpha
@@ -619,51 +813,169 @@ fn draw_ghosts(ghosts: &[Ghost]) {
 }
 
 /// Draw all projectiles with weapon-specific visuals
-fn draw_projectiles(projectiles: &[Projectile]) {
+fn draw_projectiles(projectiles: &[Projectile], weapons: &[WeaponConfig], alpha: f32) {
     for proj in projectiles {
+        let pos = proj.prev_pos.lerp(proj.pos, alpha); // Interpolated render position
+        let weapon = proj.weapon_type.config(weapons);
+
         let (color, size, glow) = match proj.owner {
-            ProjectileOwner::Player | ProjectileOwner::Ghost => match proj.weapon_type {
-                WeaponType::Bullet => (YELLOW, 4.0, false),
-                WeaponType::Laser => (GREEN, 6.0, true),
-                WeaponType::Missile => (ORANGE, 5.0, true),
-                WeaponType::Plasma => (PURPLE, 5.0, true),
-                WeaponType::Bombs => (RED, 8.0, true),
-            },
+            ProjectileOwner::Player | ProjectileOwner::Ghost => {
+                let [r, g, b] = weapon.color;
+                (Color::new(r, g, b, 1.0), weapon.size, weapon.glow)
+            }
             ProjectileOwner::Enemy => (RED, 5.0, false),
         };
 
         // Draw glow effect
         if glow {
             draw_circle(
-                proj.pos.x,
-                proj.pos.y,
+                pos.x,
+                pos.y,
                 size + 5.0,
                 Color::new(color.r, color.g, color.b, 0.3),
             );
         }
 
         // Main projectile
-        draw_circle(proj.pos.x, proj.pos.y, size, color);
+        draw_circle(pos.x, pos.y, size, color);
 
-        // Laser beam trail
-        if matches!(proj.weapon_type, WeaponType::Laser) {
+        // Beam trail (piercing shots)
+        if proj.modifiers.pierce > 0 {
             draw_line(
-                proj.pos.x,
-                proj.pos.y,
-                proj.pos.x + 5.0,
-                proj.pos.y + 5.0,
+                pos.x,
+                pos.y,
+                pos.x + 5.0,
+                pos.y + 5.0,
                 2.0,
                 Color::new(color.r, color.g, color.b, 0.5),
             );
         }
 
-        // Missile fins
-        if matches!(proj.weapon_type, WeaponType::Missile) {
-            draw_circle(proj.pos.x - 5.0, proj.pos.y + 5.0, 2.0, GRAY);
-            draw_circle(proj.pos.x - 5.0, proj.pos.y - 5.0, 2.0, GRAY);
-            draw_circle(proj.pos.x + 5.0, proj.pos.y + 5.0, 2.0, GRAY);
-            draw_circle(proj.pos.x + 5.0, proj.pos.y - 5.0, 2.0, GRAY);
+        // Missile fins (homing shots)
+        if proj.homing.is_some() {
+            draw_circle(pos.x - 5.0, pos.y + 5.0, 2.0, GRAY);
+            draw_circle(pos.x - 5.0, pos.y - 5.0, 2.0, GRAY);
+            draw_circle(pos.x + 5.0, pos.y + 5.0, 2.0, GRAY);
+            draw_circle(pos.x + 5.0, pos.y - 5.0, 2.0, GRAY);
+        }
+    }
+}
+
+/// Upgrade pickups: pulsing diamond that blinks before it vanishes
+fn draw_pickups(pickups: &[crate::game::Pickup], alpha: f32) {
+    for pickup in pickups {
+        if pickup.lifetime < 2.0 && (pickup.lifetime * 8.0).sin() < 0.0 {
+            continue; // Blink when about to expire
+        }
+
+        let pos = pickup.prev_pos.lerp(pickup.pos, alpha);
+        let pulse = 1.0 + 0.15 * (pickup.lifetime * 6.0).sin();
+        let size = 14.0 * pulse;
+
+        draw_circle(pos.x, pos.y, size, Color::new(0.3, 1.0, 0.5, 0.25));
+        draw_rotated_square(pos.x, pos.y, size * 1.4, Color::new(0.3, 1.0, 0.5, 1.0));
+        draw_text("+", pos.x - 5.0, pos.y + 6.0, 20.0, WHITE);
+    }
+}
+
+/// Draw bomb shockwaves (expanding ring + faint flash that fade out)
+fn draw_shockwaves(shockwaves: &[crate::game::Shockwave]) {
+    for wave in shockwaves {
+        let t = (wave.elapsed / wave.duration).clamp(0.0, 1.0);
+        let radius = wave.radius * crate::game::animation::ease_out_cubic(t);
+        let fade = 1.0 - t;
+        let color = wave.color;
+
+        draw_circle(
+            wave.pos.x,
+            wave.pos.y,
+            radius,
+            Color::new(color.r, color.g, color.b, 0.15 * fade),
+        );
+        draw_circle_lines(
+            wave.pos.x,
+            wave.pos.y,
+            radius,
+            2.0 + 4.0 * fade,
+            Color::new(color.r, color.g, color.b, 0.9 * fade),
+        );
+        draw_circle_lines(
+            wave.pos.x,
+            wave.pos.y,
+            radius * 0.7,
+            1.5,
+            Color::new(1.0, 1.0, 1.0, 0.5 * fade),
+        );
+    }
+}
+
+/// Beams: flickering telegraph while warming up, then a thick glowing ray
+fn draw_beams(beams: &[crate::game::Beam], weapons: &[WeaponConfig], alpha: f32) {
+    for beam in beams {
+        let config = beam.weapon.config(weapons);
+        let Some(beam_cfg) = &config.beam else {
+            continue;
+        };
+        let [r, g, b] = config.color;
+        let start = beam.prev_origin.lerp(beam.origin, alpha);
+        let end = start + beam.direction * beam_cfg.length;
+
+        if !beam.is_firing(beam_cfg) {
+            // Telegraph: thin line that brightens as the charge builds
+            let charge = (beam.elapsed / beam_cfg.warmup.max(0.001)).clamp(0.0, 1.0);
+            let flicker = 0.75 + 0.25 * (beam.elapsed * 40.0).sin();
+            draw_line(
+                start.x,
+                start.y,
+                end.x,
+                end.y,
+                1.0,
+                Color::new(r, g, b, (0.15 + 0.5 * charge) * flicker),
+            );
+            draw_circle(
+                start.x,
+                start.y,
+                beam_cfg.width * (0.5 + 1.5 * charge),
+                Color::new(r, g, b, 0.3 + 0.5 * charge),
+            );
+            continue;
         }
+
+        // Fade out over the last 20% of the duration
+        let remaining = beam_cfg.warmup + beam_cfg.duration - beam.elapsed;
+        let fade = (remaining / (beam_cfg.duration * 0.2).max(0.001)).clamp(0.0, 1.0);
+        let width = beam_cfg.width * (0.5 + 0.5 * fade);
+
+        draw_line(
+            start.x,
+            start.y,
+            end.x,
+            end.y,
+            width * 2.5,
+            Color::new(r, g, b, 0.25 * fade),
+        );
+        draw_line(
+            start.x,
+            start.y,
+            end.x,
+            end.y,
+            width,
+            Color::new(r, g, b, 0.9 * fade),
+        );
+        draw_line(
+            start.x,
+            start.y,
+            end.x,
+            end.y,
+            width * 0.4,
+            Color::new(1.0, 1.0, 1.0, fade),
+        );
+        draw_circle(
+            start.x,
+            start.y,
+            width * 1.2,
+            Color::new(1.0, 1.0, 1.0, 0.8 * fade),
+        );
     }
 }
 
@@ -763,26 +1075,37 @@ fn draw_rotated_square(x: f32, y: f32, size: f32, color: Color) {
     draw_triangle(points[0], points[2], points[3], color);
 }
 
-/// Get color based on enemy type
-fn get_enemy_color(entity_type: EntityType) -> Color {
-    match entity_type {
-        EntityType::BasicFighter => RED,
-        EntityType::Sniper => BLUE,
-        EntityType::Tank => GREEN,
-        EntityType::Elite => GOLD,
-        EntityType::Healer => LIME,
-        EntityType::Splitter => BROWN,
+/// Draw an archetype body shape roughly `radius` in size
+fn draw_shape(shape: Shape, x: f32, y: f32, radius: f32, color: Color) {
+    match shape {
+        Shape::Circle => draw_circle(x, y, radius, color),
+        Shape::Square => draw_rotated_square(x, y, radius * 1.7, color),
+        Shape::Star => draw_star(x, y, radius * 1.4, color),
     }
 }
 
-/// Get color based on ghost type
-fn get_ghost_color(entity_type: EntityType) -> Color {
-    match entity_type {
-        EntityType::BasicFighter => RED,
-        EntityType::Sniper => BLUE,
-        EntityType::Tank => GREEN,
-        EntityType::Elite => GOLD,
-        EntityType::Healer => LIME,
-        EntityType::Splitter => BROWN,
-    }
+/// Base color of an archetype (enemies and ghosts share it)
+fn archetype_color(entity_type: EntityType, archetypes: &[ArchetypeConfig]) -> Color {
+    let [r, g, b] = entity_type.archetype(archetypes).color;
+    Color::new(r, g, b, 1.0)
+}
+
+/// Blend a color towards the tint of the strongest status effect (unchanged without effects)
+fn status_tint(color: Color, status: &StatusEffects, cfg: &StatusConfig) -> Color {
+    let Some(kind) = status.strongest() else {
+        return color;
+    };
+    let [r, g, b] = match kind {
+        StatusKind::Burn => cfg.burn_tint,
+        StatusKind::Slow => cfg.slow_tint,
+        StatusKind::Stun => cfg.stun_tint,
+        StatusKind::EnergyDrain => cfg.drain_tint,
+    };
+    let t = cfg.tint_strength;
+    Color::new(
+        color.r + (r - color.r) * t,
+        color.g + (g - color.g) * t,
+        color.b + (b - color.b) * t,
+        color.a,
+    )
 }
@@ -0,0 +1,357 @@
+//! Input replays - seed + config snapshot + per-tick actions, played back through `update_all_systems`
+
+use crate::config::GameConfig;
+use crate::game::{Action, InputState, update_all_systems};
+use crate::models::GameState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Bump when the file layout or action encoding changes
+const REPLAY_VERSION: u32 = 1;
+
+/// Ticks between state checksums (0.25s at 120 Hz)
+const CHECKSUM_INTERVAL: u64 = 30;
+
+/// Replay file contents (TOML)
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub arena: (f32, f32),
+    pub ticks: u64,                 // Total recorded ticks
+    pub checksums: Vec<(u64, u32)>, // (tick, state checksum after that tick)
+    pub frames: Vec<ReplayFrame>,   // Only ticks where input changed
+    pub config: GameConfig,         // Config snapshot at record time
+}
+
+/// Input for one tick - held actions persist until the next frame
+#[derive(Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub tick: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub held: Vec<Action>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pressed: Vec<Action>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let replay: Replay = toml::from_str(&contents)?;
+
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "replay version {} not supported (expected {REPLAY_VERSION})",
+                replay.version
+            )
+            .into());
+        }
+        replay.config.validate()?;
+
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// Records the actions fed into each tick
+pub struct ReplayRecorder {
+    replay: Replay,
+    last_held: BTreeSet<Action>,
+}
+
+impl ReplayRecorder {
+    /// Start recording from a freshly created state (snapshots seed, config and arena)
+    pub fn new(state: &GameState) -> Self {
+        Self {
+            replay: Replay {
+                version: REPLAY_VERSION,
+                seed: state.seed,
+                arena: (state.arena.x, state.arena.y),
+                ticks: 0,
+                checksums: Vec::new(),
+                frames: Vec::new(),
+                config: state.config.clone(),
+            },
+            last_held: BTreeSet::new(),
+        }
+    }
+
+    /// Call after `update_all_systems` with the input that tick consumed
+    pub fn record_tick(&mut self, input: &InputState, state: &GameState) {
+        let tick = self.replay.ticks;
+
+        if input.held != self.last_held || !input.pressed.is_empty() {
+            self.replay.frames.push(ReplayFrame {
+                tick,
+                held: input.held.iter().copied().collect(),
+                pressed: input.pressed.iter().copied().collect(),
+            });
+            self.last_held = input.held.clone();
+        }
+
+        if tick.is_multiple_of(CHECKSUM_INTERVAL) {
+            self.replay.checksums.push((tick, state_checksum(state)));
+        }
+
+        self.replay.ticks += 1;
+    }
+
+    pub fn save(&self, path: &Path) {
+        match self.replay.save(path) {
+            Ok(()) => println!(
+                "✓ Replay saved to {} ({} ticks)",
+                path.display(),
+                self.replay.ticks
+            ),
+            Err(e) => println!("✗ Failed to save replay: {e}"),
+        }
+    }
+}
+
+/// First checkpoint where the playback state no longer matches the recording
+#[derive(Clone, Copy, Debug)]
+pub struct Divergence {
+    pub tick: u64,
+    pub last_match: Option<u64>, // Last checkpoint that still matched
+}
+
+/// Feeds a recorded action stream back into the simulation
+pub struct ReplayPlayer {
+    replay: Replay,
+    tick: u64,
+    next_frame: usize,
+    next_checksum: usize,
+    held: BTreeSet<Action>,
+    last_match: Option<u64>,
+    pub divergence: Option<Divergence>,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            tick: 0,
+            next_frame: 0,
+            next_checksum: 0,
+            held: BTreeSet::new(),
+            last_match: None,
+            divergence: None,
+        }
+    }
+
+    /// Build the starting state exactly as it was recorded
+    pub fn new_state(&self) -> GameState {
+        let (width, height) = self.replay.arena;
+        let mut state = GameState::with_config(self.replay.config.clone(), width, height);
+        state.reseed(self.replay.seed);
+        state
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn total_ticks(&self) -> u64 {
+        self.replay.ticks
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.replay.ticks
+    }
+
+    /// Simulate one recorded tick and check it against the recording
+    pub fn step(&mut self, state: &mut GameState) {
+        if self.is_finished() {
+            return;
+        }
+
+        let mut input = InputState {
+            held: self.held.clone(),
+            pressed: BTreeSet::new(),
+        };
+
+        if let Some(frame) = self.replay.frames.get(self.next_frame)
+            && frame.tick == self.tick
+        {
+            self.held = frame.held.iter().copied().collect();
+            input.held = self.held.clone();
+            input.pressed = frame.pressed.iter().copied().collect();
+            self.next_frame += 1;
+        }
+
+        let delta = 1.0 / self.replay.config.simulation.tick_rate;
+        update_all_systems(state, &input, delta);
+
+        self.verify(state);
+        self.tick += 1;
+    }
+
+    fn verify(&mut self, state: &GameState) {
+        let Some(&(tick, expected)) = self.replay.checksums.get(self.next_checksum) else {
+            return;
+        };
+        if tick != self.tick {
+            return;
+        }
+        self.next_checksum += 1;
+
+        if self.divergence.is_some() {
+            return; // Only the first divergence is interesting
+        }
+
+        if state_checksum(state) == expected {
+            self.last_match = Some(tick);
+        } else {
+            let divergence = Divergence {
+                tick,
+                last_match: self.last_match,
+            };
+            match divergence.last_match {
+                Some(good) => {
+                    println!("✗ Replay diverged at tick {tick} (last match: tick {good})")
+                }
+                None => println!("✗ Replay diverged at tick {tick} (first checkpoint)"),
+            }
+            self.divergence = Some(divergence);
+        }
+    }
+
+    /// Print the verification result once playback is done
+    pub fn report(&self) {
+        match self.divergence {
+            Some(d) => println!("✘ Replay diverged at tick {}", d.tick),
+            None => println!(
+                "✔ Replay matched all {} checkpoints ({} ticks)",
+                self.replay.checksums.len(),
+                self.replay.ticks
+            ),
+        }
+    }
+}
+
+/// Hash of the gameplay-relevant state (FNV-1a over positions, health and counters)
+pub fn state_checksum(state: &GameState) -> u32 {
+    let mut hash: u32 = 0x811C_9DC5;
+    let mut mix = |value: u32| {
+        for byte in value.to_le_bytes() {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+    };
+
+    let player = &state.player;
+    mix(player.pos.x.to_bits());
+    mix(player.pos.y.to_bits());
+    mix(player.stats.health.to_bits());
+    mix(player.energy.to_bits());
+
+    for enemy in &state.enemies {
+        mix(enemy.pos.x.to_bits());
+        mix(enemy.pos.y.to_bits());
+        mix(enemy.stats.health.to_bits());
+    }
+    for ghost in &state.ghosts {
+        mix(ghost.pos.x.to_bits());
+        mix(ghost.pos.y.to_bits());
+        mix(ghost.stats.health.to_bits());
+    }
+
+    mix(state.projectiles.len() as u32);
+    mix(state.beams.len() as u32);
+    mix(state.enemies_killed as u32);
+    mix(state.grazes as u32);
+    mix(state.wave_manager.current_wave_number as u32);
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_round_trip() {
+        let frame = ReplayFrame {
+            tick: 42,
+            held: vec![Action::MoveLeft, Action::FireSlot(2)],
+            pressed: vec![Action::SetFormation(crate::models::GhostFormation::Circle)],
+        };
+
+        let text = toml::to_string(&frame).unwrap();
+        let parsed: ReplayFrame = toml::from_str(&text).unwrap();
+
+        assert_eq!(parsed.tick, 42);
+        assert_eq!(parsed.held, frame.held);
+        assert_eq!(parsed.pressed, frame.pressed);
+    }
+
+    /// Record `ticks` ticks from a fixed seed: fire throughout, strafe right, summon once
+    fn record(ticks: u64) -> Replay {
+        let mut state = GameState::with_arena(800.0, 600.0);
+        state.reseed(1234);
+        state.config.spawning.wave_mode = true;
+        let mut recorder = ReplayRecorder::new(&state);
+
+        let delta = 1.0 / state.config.simulation.tick_rate;
+        for tick in 0..ticks {
+            let mut input = InputState::default();
+            input.hold(Action::FireSlot(0));
+            if tick < 60 {
+                input.hold(Action::MoveRight);
+            }
+            if tick == 10 {
+                input.pressed.insert(Action::SummonFormation);
+            }
+            update_all_systems(&mut state, &input, delta);
+            recorder.record_tick(&input, &state);
+        }
+        recorder.replay
+    }
+
+    fn play(replay: Replay) -> ReplayPlayer {
+        let mut player = ReplayPlayer::new(replay);
+        let mut state = player.new_state();
+        while !player.is_finished() {
+            player.step(&mut state);
+        }
+        player
+    }
+
+    #[test]
+    fn test_recorded_run_replays_without_divergence() {
+        let replay = record(300);
+        assert_eq!(replay.checksums.len(), 10); // Every 30 ticks
+        assert!(replay.frames.len() < 300); // Only changes are stored
+
+        let player = play(replay);
+        assert_eq!(player.tick(), 300);
+        assert!(player.divergence.is_none());
+    }
+
+    #[test]
+    fn test_tampered_input_reports_first_divergent_checkpoint() {
+        let mut replay = record(300);
+        let at = replay.frames.partition_point(|frame| frame.tick < 95);
+        replay.frames.insert(
+            at,
+            ReplayFrame {
+                tick: 95,
+                held: vec![Action::MoveLeft],
+                pressed: Vec::new(),
+            },
+        );
+
+        let divergence = play(replay).divergence.unwrap();
+        assert_eq!(divergence.tick, 120);
+        assert_eq!(divergence.last_match, Some(90));
+    }
+}
@@ -1,15 +1,153 @@
+use crate::models::GameState;
+use macroquad::prelude::Vec2;
 use mlua::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::future::{Future, poll_fn};
 use std::path::Path;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
 
 /// Lua scripting system for wave definitions and AI behaviors
 pub struct LuaScripting {
     pub(crate) lua: Lua, // pub(crate) for WaveManager::new_dummy()
+    commands: Rc<RefCell<Vec<ScriptCommand>>>, // Queued by the `game` table, applied after the callback
+    view: Rc<RefCell<ScriptView>>,             // What the `game` queries read
+    clock: Rc<Cell<f32>>,                      // Seconds since the running timeline started
+    global_hooks: Rc<RefCell<EventHooks>>,     // Registered with game.on(...), live all run
+    instructions_left: Rc<Cell<u32>>,          // Behavior budget for the current tick
+    failed_behaviors: RefCell<HashSet<String>>, // Errored once - built-in AI from then on
+}
+
+/// What an enemy behavior gets to see (a copy - scripts can't change the game directly)
+#[derive(Clone, Copy, Debug)]
+pub struct BehaviorView {
+    pub id: u64,
+    pub pos: Vec2,
+    pub hp: f32,
+    pub max_hp: f32,
+    pub player_pos: Vec2,
+    pub time: f32,        // Simulation seconds since the run started
+    pub fire_ready: bool, // Fire timer has run out
+}
+
+/// What an enemy behavior asks for (applied by `update_enemies`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BehaviorIntent {
+    pub velocity: Vec2,         // Pixels per second (capped by lua_max_speed)
+    pub fire: bool,             // Fire once the timer allows
+    pub weapon: Option<String>, // One of its weapons (None = random)
+    pub aim: Option<Vec2>,      // Point to shoot at (None = the archetype's fire_direction)
+}
+
+/// Gameplay events scripts can react to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HookKind {
+    EnemyKilled,   // (type, x, y, killer) - killer is "Player", "Ghost", "Enemy" or nil
+    PlayerHit,     // (damage, x, y)
+    Parry,         // (deflected, x, y)
+    GhostSummoned, // (type, x, y)
+    Tick,          // (seconds since the last call) - throttled by spawning.tick_hook_interval
+}
+
+impl HookKind {
+    pub const ALL: [HookKind; 5] = [
+        HookKind::EnemyKilled,
+        HookKind::PlayerHit,
+        HookKind::Parry,
+        HookKind::GhostSummoned,
+        HookKind::Tick,
+    ];
+
+    /// Name used by game.on(...) (a wave table uses "on_" + name)
+    pub fn name(self) -> &'static str {
+        match self {
+            HookKind::EnemyKilled => "enemy_killed",
+            HookKind::PlayerHit => "player_hit",
+            HookKind::Parry => "parry",
+            HookKind::GhostSummoned => "ghost_summoned",
+            HookKind::Tick => "tick",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// Lua event handlers, per kind (a wave's `on_*` fields, or the global `game.on` set)
+#[derive(Clone, Debug, Default)]
+pub struct EventHooks {
+    handlers: Vec<(HookKind, LuaFunction)>,
+}
+
+impl EventHooks {
+    pub fn add(&mut self, kind: HookKind, handler: LuaFunction) {
+        self.handlers.push((kind, handler));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Call the handlers for `kind` in registration order; a handler that errors is logged
+    /// and dropped so one broken script can't take the run down (or spam every tick)
+    fn call(&mut self, kind: HookKind, args: &LuaMultiValue) {
+        self.handlers.retain(|(handler_kind, handler)| {
+            if *handler_kind != kind {
+                return true;
+            }
+            match handler.call::<()>(args.clone()) {
+                Ok(()) => true,
+                Err(e) => {
+                    println!("✘ Lua on_{} handler error (removed): {e}", kind.name());
+                    false
+                }
+            }
+        });
+    }
+}
+
+/// A wave's `run()` function, running as an async Lua thread (resumed once per tick)
+pub struct Timeline {
+    thread: Pin<Box<mlua::AsyncThread<()>>>,
+}
+
+/// Gameplay change requested from Lua (applied to the live state by `game::apply_script_commands`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptCommand {
+    SpawnEnemy {
+        enemy_type: String,   // Archetype name
+        x: Option<f32>,       // Spawn column (None = random, like wave spawns)
+        path: Option<String>, // EntryPath name (None = the archetype's own)
+    },
+    SetFormation(String), // GhostFormation name
+    GrantEnergy(f32),     // Negative takes energy away
+    Shake {
+        duration: f32,
+        intensity: f32,
+    },
+}
+
+/// Read-only snapshot of the game for Lua queries (refreshed before callbacks run)
+#[derive(Debug, Clone, Default)]
+pub struct ScriptView {
+    pub player_pos: Vec2,
+    pub player_hp: f32,
+    pub player_max_hp: f32,
+    pub player_energy: f32,
+    pub enemy_count: usize,
+    pub arena: Vec2,
 }
 
 impl LuaScripting {
     /// Initialize Lua runtime and expose Rust API
     pub fn new() -> LuaResult<Self> {
-        let lua = Lua::new();
+        let scripting = Self::bare();
+        scripting.register_game_api()?;
+        scripting.register_timeline_api()?;
+        let lua = &scripting.lua;
 
         // Load init.lua for helper functions
         if Path::new("scripts/init.lua").exists() {
@@ -23,7 +161,347 @@ impl LuaScripting {
             ));
         }
 
-        Ok(Self { lua })
+        // Enemy behaviors (optional - archetypes name functions defined here)
+        if Path::new("scripts/behaviors.lua").exists() {
+            let behaviors = std::fs::read_to_string("scripts/behaviors.lua").map_err(|e| {
+                LuaError::RuntimeError(format!("✘ Failed to read behaviors.lua: {e}"))
+            })?;
+            lua.load(&behaviors).set_name("behaviors.lua").exec()?;
+            println!("✓ Loaded scripts/behaviors.lua");
+        }
+
+        Ok(scripting)
+    }
+
+    /// Lua runtime without init.lua or the `game` table (placeholder for mem::replace)
+    pub fn bare() -> Self {
+        Self {
+            lua: Lua::new(),
+            commands: Default::default(),
+            view: Default::default(),
+            clock: Default::default(),
+            global_hooks: Default::default(),
+            instructions_left: Default::default(),
+            failed_behaviors: Default::default(),
+        }
+    }
+
+    /// Register the global `game` table (queries read the view, actions queue commands)
+    fn register_game_api(&self) -> LuaResult<()> {
+        let lua = &self.lua;
+        let game = lua.create_table()?;
+
+        let view = self.view.clone();
+        game.set(
+            "player_pos",
+            lua.create_function(move |_, ()| {
+                let pos = view.borrow().player_pos;
+                Ok((pos.x, pos.y))
+            })?,
+        )?;
+        let view = self.view.clone();
+        game.set(
+            "player_hp",
+            lua.create_function(move |_, ()| {
+                let view = view.borrow();
+                Ok((view.player_hp, view.player_max_hp))
+            })?,
+        )?;
+        let view = self.view.clone();
+        game.set(
+            "player_energy",
+            lua.create_function(move |_, ()| Ok(view.borrow().player_energy))?,
+        )?;
+        let view = self.view.clone();
+        game.set(
+            "enemy_count",
+            lua.create_function(move |_, ()| Ok(view.borrow().enemy_count))?,
+        )?;
+        let view = self.view.clone();
+        game.set(
+            "arena",
+            lua.create_function(move |_, ()| {
+                let arena = view.borrow().arena;
+                Ok((arena.x, arena.y))
+            })?,
+        )?;
+
+        let commands = self.commands.clone();
+        game.set(
+            "spawn_enemy",
+            lua.create_function(
+                move |_, (enemy_type, x, path): (String, Option<f32>, Option<String>)| {
+                    let command = ScriptCommand::SpawnEnemy {
+                        enemy_type,
+                        x,
+                        path,
+                    };
+                    commands.borrow_mut().push(command);
+                    Ok(())
+                },
+            )?,
+        )?;
+        let commands = self.commands.clone();
+        game.set(
+            "set_formation",
+            lua.create_function(move |_, name: String| {
+                commands
+                    .borrow_mut()
+                    .push(ScriptCommand::SetFormation(name));
+                Ok(())
+            })?,
+        )?;
+        let commands = self.commands.clone();
+        game.set(
+            "grant_energy",
+            lua.create_function(move |_, amount: f32| {
+                commands
+                    .borrow_mut()
+                    .push(ScriptCommand::GrantEnergy(amount));
+                Ok(())
+            })?,
+        )?;
+        let commands = self.commands.clone();
+        game.set(
+            "shake",
+            lua.create_function(move |_, (duration, intensity): (f32, f32)| {
+                let command = ScriptCommand::Shake {
+                    duration,
+                    intensity,
+                };
+                commands.borrow_mut().push(command);
+                Ok(())
+            })?,
+        )?;
+
+        // game.on(name, handler) - global event handler (init.lua); waves use on_* fields
+        let hooks = self.global_hooks.clone();
+        game.set(
+            "on",
+            lua.create_function(move |_, (name, handler): (String, LuaFunction)| {
+                let kind = HookKind::from_name(&name)
+                    .ok_or_else(|| LuaError::RuntimeError(format!("unknown event '{name}'")))?;
+                hooks.borrow_mut().add(kind, handler);
+                Ok(())
+            })?,
+        )?;
+
+        lua.globals().set("game", game)
+    }
+
+    /// Call the wave's handlers for an event, then the global ones
+    pub fn call_hooks(
+        &self,
+        wave_hooks: &mut EventHooks,
+        kind: HookKind,
+        args: impl IntoLuaMulti,
+    ) -> LuaResult<()> {
+        if wave_hooks.is_empty() && self.global_hooks.borrow().is_empty() {
+            return Ok(()); // Nothing listening - skip the conversion
+        }
+        let args = args.into_lua_multi(&self.lua)?;
+        wave_hooks.call(kind, &args);
+
+        // Take the global set out while calling (a handler may register more with game.on)
+        let mut global = std::mem::take(&mut *self.global_hooks.borrow_mut());
+        global.call(kind, &args);
+        let mut hooks = self.global_hooks.borrow_mut();
+        let added = std::mem::replace(&mut *hooks, global);
+        hooks.handlers.extend(added.handlers);
+        Ok(())
+    }
+
+    /// Register the async timeline primitives: `wait`, `wait_until_clear` and `parallel`
+    /// (only usable inside a wave's `run()`; see scripts/init.lua for the Lua-side helpers)
+    fn register_timeline_api(&self) -> LuaResult<()> {
+        let lua = &self.lua;
+
+        // wait(seconds) - always lasts at least one tick
+        let clock = self.clock.clone();
+        let wait = lua.create_async_function(move |_, seconds: f32| {
+            let clock = clock.clone();
+            let until = clock.get() + seconds.max(0.0);
+            let mut first = true;
+            poll_fn(move |_| {
+                if !std::mem::take(&mut first) && clock.get() >= until {
+                    Poll::Ready(Ok(()))
+                } else {
+                    Poll::Pending
+                }
+            })
+        })?;
+        lua.globals().set("wait", wait)?;
+
+        // wait_until_clear() - until no enemies are left (checked from the next tick,
+        // so enemies spawned just before are counted)
+        let view = self.view.clone();
+        let wait_until_clear = lua.create_async_function(move |_, ()| {
+            let view = view.clone();
+            let mut first = true;
+            poll_fn(move |_| {
+                if !std::mem::take(&mut first) && view.borrow().enemy_count == 0 {
+                    Poll::Ready(Ok(()))
+                } else {
+                    Poll::Pending
+                }
+            })
+        })?;
+        lua.globals().set("wait_until_clear", wait_until_clear)?;
+
+        // parallel{f, g, ...} - run the functions side by side, return once all have
+        let parallel = lua.create_async_function(|lua, branches: LuaTable| async move {
+            let mut running = Vec::new();
+            for branch in branches.sequence_values::<LuaFunction>() {
+                let thread = lua.create_thread(branch?)?.into_async::<()>(())?;
+                running.push(Box::pin(thread));
+            }
+            poll_fn(move |cx| {
+                // Branches resume in table order every tick (keeps spawns deterministic)
+                let mut error = None;
+                running.retain_mut(|thread| match thread.as_mut().poll(cx) {
+                    Poll::Ready(Ok(())) => false,
+                    Poll::Ready(Err(e)) => {
+                        error = Some(e);
+                        false
+                    }
+                    Poll::Pending => true,
+                });
+                if let Some(e) = error {
+                    Poll::Ready(Err(e))
+                } else if running.is_empty() {
+                    Poll::Ready(Ok(()))
+                } else {
+                    Poll::Pending
+                }
+            })
+            .await
+        })?;
+        lua.globals().set("parallel", parallel)
+    }
+
+    /// Start a wave's `run()` function (timeline clock starts at zero)
+    pub fn start_timeline(&self, run: LuaFunction) -> LuaResult<Timeline> {
+        self.clock.set(0.0);
+        let thread = self.lua.create_thread(run)?.into_async::<()>(())?;
+        Ok(Timeline {
+            thread: Box::pin(thread),
+        })
+    }
+
+    /// Advance the clock by `delta` and resume the timeline until it waits again
+    /// (returns true once `run()` has returned)
+    pub fn resume_timeline(&self, timeline: &mut Timeline, delta: f32) -> LuaResult<bool> {
+        self.clock.set(self.clock.get() + delta);
+
+        // Polled every tick anyway - nothing needs waking
+        let mut cx = Context::from_waker(Waker::noop());
+        match timeline.thread.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => result.map(|()| true),
+            Poll::Pending => Ok(false),
+        }
+    }
+
+    /// Snapshot what the `game` queries return (call before running callbacks)
+    pub fn refresh_view(&self, state: &GameState) {
+        *self.view.borrow_mut() = ScriptView {
+            player_pos: state.player.pos,
+            player_hp: state.player.stats.health,
+            player_max_hp: state.player.stats.max_health,
+            player_energy: state.player.energy,
+            enemy_count: state.enemies.len(),
+            arena: state.arena,
+        };
+    }
+
+    /// Commands queued by scripts since the last call
+    pub fn take_commands(&self) -> Vec<ScriptCommand> {
+        std::mem::take(&mut self.commands.borrow_mut())
+    }
+
+    /// Instructions between budget checks (the budget is spent in steps this size)
+    const BUDGET_STEP: u32 = 1000;
+
+    /// Start a tick's behavior pass: every behavior call shares `budget` VM instructions
+    pub fn begin_behaviors(&self, budget: u32) {
+        self.instructions_left.set(budget);
+        let left = self.instructions_left.clone();
+        let triggers = LuaHookTriggers::new().every_nth_instruction(Self::BUDGET_STEP);
+        let result = self.lua.set_hook(triggers, move |_, _| {
+            if left.get() < Self::BUDGET_STEP {
+                left.set(0);
+                return Err(LuaError::RuntimeError(
+                    "instruction budget exceeded".to_string(),
+                ));
+            }
+            left.set(left.get() - Self::BUDGET_STEP);
+            Ok(LuaVmState::Continue)
+        });
+        if let Err(e) = result {
+            println!("✘ Failed to set Lua instruction budget: {e}");
+        }
+    }
+
+    /// End the behavior pass (other scripts run without a budget)
+    pub fn end_behaviors(&self) {
+        self.lua.remove_hook();
+    }
+
+    /// Call the global Lua function `name` for one enemy (between begin/end_behaviors)
+    /// None = use the built-in AI this tick: the script returned nil, the budget ran out,
+    /// or the behavior is missing/broken (logged once, then skipped for the rest of the run)
+    pub fn call_behavior(&self, name: &str, view: &BehaviorView) -> Option<BehaviorIntent> {
+        if self.instructions_left.get() == 0 || self.failed_behaviors.borrow().contains(name) {
+            return None;
+        }
+
+        match self.try_call_behavior(name, view) {
+            Ok(intent) => intent,
+            Err(_) if self.instructions_left.get() == 0 => None, // Out of budget until next tick
+            Err(e) => {
+                println!("✘ Lua behavior '{name}' failed, using built-in AI: {e}");
+                self.failed_behaviors.borrow_mut().insert(name.to_string());
+                None
+            }
+        }
+    }
+
+    fn try_call_behavior(
+        &self,
+        name: &str,
+        view: &BehaviorView,
+    ) -> LuaResult<Option<BehaviorIntent>> {
+        let behavior: LuaFunction = self.lua.globals().get(name)?;
+
+        let enemy = self.lua.create_table()?;
+        enemy.set("id", view.id)?;
+        enemy.set("x", view.pos.x)?;
+        enemy.set("y", view.pos.y)?;
+        enemy.set("hp", view.hp)?;
+        enemy.set("max_hp", view.max_hp)?;
+        enemy.set("player_x", view.player_pos.x)?;
+        enemy.set("player_y", view.player_pos.y)?;
+        enemy.set("time", view.time)?;
+        enemy.set("fire_ready", view.fire_ready)?;
+
+        let Some(intent) = behavior.call::<Option<LuaTable>>(enemy)? else {
+            return Ok(None);
+        };
+        let aim = match (
+            intent.get::<Option<f32>>("aim_x")?,
+            intent.get::<Option<f32>>("aim_y")?,
+        ) {
+            (Some(x), Some(y)) => Some(Vec2::new(x, y)),
+            _ => None,
+        };
+        Ok(Some(BehaviorIntent {
+            velocity: Vec2::new(
+                intent.get::<Option<f32>>("vx")?.unwrap_or(0.0),
+                intent.get::<Option<f32>>("vy")?.unwrap_or(0.0),
+            ),
+            fire: intent.get::<Option<bool>>("fire")?.unwrap_or(false),
+            weapon: intent.get("weapon")?,
+            aim,
+        }))
     }
 
     /// Load a wave definition from Lua script
@@ -47,11 +525,14 @@ impl LuaScripting {
         let name = wave_table.get::<String>("name")?;
         let prep_time = wave_table.get::<f32>("prep_time").unwrap_or(3.0);
 
-        // Parse spawns array
-        let spawns_table: LuaTable = wave_table.get("spawns")?;
+        // Parse spawns array (optional when the wave has a run() timeline)
+        let spawns_table: Option<LuaTable> = wave_table.get("spawns")?;
         let mut spawns = Vec::new();
 
-        for pair in spawns_table.sequence_values::<LuaTable>() {
+        for pair in spawns_table
+            .iter()
+            .flat_map(|table| table.sequence_values::<LuaTable>())
+        {
             let spawn_table = pair?;
             let enemy_type = spawn_table.get::<String>("type")?;
             let count = spawn_table.get::<usize>("count")?;
@@ -69,12 +550,23 @@ impl LuaScripting {
         // Get callbacks (optional)
         let on_start = wave_table.get::<Option<LuaFunction>>("on_start")?;
         let on_complete = wave_table.get::<Option<LuaFunction>>("on_complete")?;
+        let run = wave_table.get::<Option<LuaFunction>>("run")?;
+
+        // Event handlers (on_enemy_killed, on_player_hit, ...) live while the wave does
+        let mut hooks = EventHooks::default();
+        for kind in HookKind::ALL {
+            let field = format!("on_{}", kind.name());
+            if let Some(handler) = wave_table.get::<Option<LuaFunction>>(field)? {
+                hooks.add(kind, handler);
+            }
+        }
 
         println!(
-            "✓ Loaded wave {}: {} ({} spawn groups)",
+            "✓ Loaded wave {}: {} ({} spawn groups{})",
             wave_num,
             name,
-            spawns.len()
+            spawns.len(),
+            if run.is_some() { " + timeline" } else { "" }
         );
 
         Ok(LuaWaveDefinition {
@@ -84,6 +576,8 @@ impl LuaScripting {
             spawns,
             on_start,
             on_complete,
+            run,
+            hooks,
         })
     }
 
@@ -104,7 +598,7 @@ impl LuaScripting {
     }
 
     /// Get reference to Lua runtime (for advanced usage)
-    #[allow(dead_code)] // Reserved for future Lua API exposure (AI behaviors, custom scripts)
+    #[allow(dead_code)] // Custom scripts and tests (waves go through load_wave)
     pub fn lua(&self) -> &Lua {
         &self.lua
     }
@@ -118,6 +612,8 @@ pub struct LuaWaveDefinition {
     pub spawns: Vec<LuaSpawnDefinition>,
     pub on_start: Option<LuaFunction>,
     pub on_complete: Option<LuaFunction>,
+    pub run: Option<LuaFunction>, // Timeline coroutine, runs alongside the spawn groups
+    pub hooks: EventHooks, // on_enemy_killed, on_player_hit, on_parry, on_ghost_summoned, on_tick
 }
 
 /// Individual spawn group within a wave